@@ -46,6 +46,25 @@ module = {
 
         flight_condition = "WingsLevel", -- "WingsLevel" | "Turning" | "PullUp" | "Roll" | optional
 
+        diagnostic = false, -- stream coefficients, forces and moments of each step | optional
+
+        -- partial override of the model constants, unset fields keep the model value | optional
+        -- constants = { m = 636.94, x_cg = 0.30 },
+
+        -- partial override of the model control limits, unset fields keep the model value | optional
+        -- ctrl_limit = { alpha_limit_top = 45.0, alpha_limit_bottom = -20.0 },
+
+        -- start from an explicit state and control, trim is skipped when set | optional
+        -- init_state = {
+        --     state = {
+        --         npos = 0.0, epos = 0.0, altitude = 15000.0,
+        --         phi = 0.0, theta = 0.5, psi = 0.0,
+        --         velocity = 500.0, alpha = 0.5, beta = 0.0,
+        --         p = 0.0, q = 0.0, r = 0.0
+        --     },
+        --     control = { thrust = 5000.0, elevator = -5.0, aileron = 0.0, rudder = 0.0 }
+        -- },
+
         -- -- optional
         optim_options = {
             max_fun_evals = 50000,
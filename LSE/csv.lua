@@ -28,7 +28,23 @@ local function output_to_csv(output)
         state_extend.nz,
         state_extend.mach,
         state_extend.qbar,
-        state_extend.ps
+        state_extend.ps,
+        state_extend.u,
+        state_extend.v,
+        state_extend.w,
+        state_extend.v_north,
+        state_extend.v_east,
+        state_extend.v_down,
+        LSE.to_degrees(state_extend.gamma),
+        LSE.to_degrees(state_extend.track),
+        state_extend.ground_speed,
+        state_extend.climb_rate,
+        state_extend.tas,
+        state_extend.cas,
+        state_extend.eas,
+        state_extend.load_factor,
+        state_extend.specific_energy,
+        state_extend.specific_excess_power
     }
     return v
 end
@@ -48,6 +64,12 @@ function CSVWriter.new(filename, header)
             "thrust(lbs)", "elevator(degree)", "aileron(degree)", "rudder(degree)",
             "nx(g)", "ny(g)", "nz(g)",
             "mach", "qbar(lb/ft ft)", "ps(lb/ft ft)",
+            "u(ft/s)", "v(ft/s)", "w(ft/s)",
+            "v_north(ft/s)", "v_east(ft/s)", "v_down(ft/s)",
+            "gamma(degree)", "track(degree)",
+            "ground_speed(ft/s)", "climb_rate(ft/s)",
+            "tas(ft/s)", "cas(ft/s)", "eas(ft/s)",
+            "load_factor(g)", "specific_energy(ft)", "specific_excess_power(ft/s)",
         }
     else
         self.header = header
@@ -5,9 +5,14 @@ use fly_ruler_plugin::{PluginInfo, PluginState};
 use fly_ruler_utils::error::FrResult;
 use fly_ruler_utils::plane_model::CoreOutput;
 use fly_ruler_utils::CancellationToken;
-use fly_ruler_utils::{error::FrError, InputSender, OutputReceiver};
+use fly_ruler_utils::{
+    error::FrError, DiagnosticReceiver, InputSender, OutputReceiver, PlaneCommandSender,
+};
 use lua_runtime::{prelude::*, CancellationTokenWrapper};
-use lua_runtime::{InputSenderWrapper, OutputReceiverWrapper, UuidWrapper};
+use lua_runtime::{
+    DiagnosticReceiverWrapper, InputSenderWrapper, OutputReceiverWrapper,
+    PlaneCommandSenderWrapper, UuidWrapper,
+};
 use std::{
     collections::HashMap,
     path::{Path, PathBuf},
@@ -116,6 +121,8 @@ impl System {
             InputSender,
             JoinHandle<FrResult<()>>,
             TrimOutput,
+            Option<DiagnosticReceiver>,
+            PlaneCommandSender,
         ),
         SysError,
     > {
@@ -271,7 +278,7 @@ impl LuaUserData for SystemWrapper {
             )| {
                 let cancellation_token = CancellationToken::new();
                 let init_cfg: PlaneInitCfg = lua.from_value(init_cfg)?;
-                let (id, viewer, controller, handler, trim_output) = this
+                let (id, viewer, controller, handler, trim_output, diagnostic, commander) = this
                     .0
                     .lock()
                     .unwrap()
@@ -293,6 +300,10 @@ impl LuaUserData for SystemWrapper {
                     trim_output.control,
                     trim_output.state_extend,
                 ))?)?;
+                t.push(PlaneCommandSenderWrapper::from(commander))?;
+                if let Some(diagnostic) = diagnostic {
+                    t.push(DiagnosticReceiverWrapper::from(diagnostic))?;
+                }
                 t.into_lua(lua)
             },
         );
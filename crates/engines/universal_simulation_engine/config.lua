@@ -12,8 +12,31 @@ log = {
 
 server = {
     addr = "127.0.0.1:2350",
-    tick_timeout = 1500, -- ms
+    tick_timeout = 1500, -- ms, pilots without `Tick` are dropped, observers do not tick
+    liveness_timeout = 30000, -- ms, clients without any call, such as `Ping`, are dropped, 0 to disable | optional
     read_rate = 1,
+    batch_interval = 50, -- ms, group the outputs sent within the interval, 0 to disable | optional
+    output_queue = 256, -- messages waiting for a client, the backpressure policy applies beyond | optional
+    backpressure = "drop_oldest", -- drop_oldest, conflate to keep the latest output and diagnostic of every plane, or disconnect | optional
+    compression_threshold = 1024, -- bytes, smaller frames are sent uncompressed | optional
+    max_frame_size = 16777216, -- bytes, larger frames drop the client | optional
+    -- grpc_addr = "127.0.0.1:2351", -- serve FlyRulerService over grpc | optional
+    -- ws_addr = "127.0.0.1:2352", -- accept websocket clients, protobuf or json | optional
+    -- udp_addr = "127.0.0.1:2353", -- udp side channel of controls and outputs | optional
+    -- uds_path = "/tmp/fly_ruler.sock", -- accept local clients on a unix domain socket | optional
+    -- shm_path = "/dev/shm/fly_ruler", -- latest outputs of the default room in shared memory for any local reader, refused with `auth_*` | optional
+    -- shm_capacity = 64, -- planes in the shared memory | optional
+    -- tls_cert_path = "cert.pem", -- serve tls on the tcp, websocket and grpc addresses | optional
+    -- tls_key_path = "key.pem", -- private key of the certificate | optional
+    -- auth_token = "change me", -- token shared by every client, it grants any role | optional
+    -- auth_token_file = "tokens.txt", -- one `name role token` line per user, role is admin, pilot or observer | optional
+    session_grace = 60000, -- ms, planes of a dropped client keep flying until it reconnects with its session, 0 to disable | optional
+    -- rooms = { -- worlds isolated from each other, joined with the `room` of the hello, the others join the default one | optional
+    --     training = {
+    --         clock_mode = { Realtime = true }, -- clock of the planes of the room, the one of the core if missing | optional
+    --         access = { "alice", "bob" }, -- users of the token file allowed in, admins always are, every user if missing | optional
+    --     },
+    -- },
 }
 
 core_init_cfg = {
@@ -52,6 +75,25 @@ plane_init_cfg = {
 
     flight_condition = "WingsLevel", -- "WingsLevel" | "Turning" | "PullUp" | "Roll" | optional
 
+    diagnostic = false, -- stream coefficients, forces and moments of each step | optional
+
+    -- partial override of the model constants, unset fields keep the model value | optional
+    -- constants = { m = 636.94, x_cg = 0.30 },
+
+    -- partial override of the model control limits, unset fields keep the model value | optional
+    -- ctrl_limit = { alpha_limit_top = 45.0, alpha_limit_bottom = -20.0 },
+
+    -- start from an explicit state and control, trim is skipped when set | optional
+    -- init_state = {
+    --     state = {
+    --         npos = 0.0, epos = 0.0, altitude = 15000.0,
+    --         phi = 0.0, theta = 0.5, psi = 0.0,
+    --         velocity = 500.0, alpha = 0.5, beta = 0.0,
+    --         p = 0.0, q = 0.0, r = 0.0
+    --     },
+    --     control = { thrust = 5000.0, elevator = -5.0, aileron = 0.0, rudder = 0.0 }
+    -- },
+
     -- -- optional
     optim_options = {
         max_fun_evals = 50000,
@@ -0,0 +1,131 @@
+use anyhow::{anyhow, Result};
+use fly_ruler_codec::ClientRole;
+use std::{fmt, path::Path, sync::Arc};
+
+/// a user of the token file
+struct User {
+    name: String,
+    token: String,
+    role: ClientRole,
+}
+
+/// checks the token of the clients during the handshake,
+/// every client gets the role it asks for if no token is configured
+#[derive(Clone, Default)]
+pub struct Authenticator {
+    /// shared by every client, it grants any role
+    token: Option<String>,
+    users: Arc<Vec<User>>,
+}
+
+impl fmt::Debug for Authenticator {
+    // the tokens are kept out of the logs
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Authenticator")
+            .field("token", &self.token.is_some())
+            .field("users", &self.users.len())
+            .finish()
+    }
+}
+
+impl Authenticator {
+    /// `token` is shared by every client, `token_file` holds one `name role token` line per user,
+    /// the role is one of `admin`, `pilot` and `observer`, lines starting with `#` are skipped
+    pub fn load(token: Option<String>, token_file: Option<impl AsRef<Path>>) -> Result<Self> {
+        let users = match token_file {
+            Some(path) => {
+                let path = path.as_ref();
+                let content = std::fs::read_to_string(path)
+                    .map_err(|e| anyhow!("token file {} can not be read, {}", path.display(), e))?;
+                parse_users(&content)?
+            }
+            None => Vec::new(),
+        };
+        Ok(Self {
+            token: token.filter(|t| !t.is_empty()),
+            users: Arc::new(users),
+        })
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.token.is_some() || !self.users.is_empty()
+    }
+
+    /// the role granted to a client asking for `role` with `token`, or why it is rejected
+    pub fn authenticate(&self, token: &str, role: ClientRole) -> Result<ClientRole, String> {
+        if !self.is_enabled() {
+            return Ok(role);
+        }
+        if self.token.as_deref().is_some_and(|t| token_eq(t, token)) {
+            return Ok(role);
+        }
+        match self.users.iter().find(|u| token_eq(&u.token, token)) {
+            Some(user) if user.role.includes(role) => Ok(role),
+            Some(user) => Err(format!(
+                "user {} is not allowed to connect as {:?}",
+                user.name, role
+            )),
+            None => Err("invalid token".to_string()),
+        }
+    }
+
+    /// name of the user of the token file holding `token`
+    pub fn user(&self, token: &str) -> Option<&str> {
+        self.users
+            .iter()
+            .find(|u| token_eq(&u.token, token))
+            .map(|u| u.name.as_str())
+    }
+}
+
+/// least role allowed to make the call
+pub(crate) fn required_role(call: &str) -> ClientRole {
+    match call {
+        "PushPlane" | "SendControl" | "RemovePlane" | "PausePlane" | "ResumePlane"
+        | "ResetPlane" | "SetTimeScale" | "TransferPlane" | "SharePlane" => ClientRole::Pilot,
+        _ => ClientRole::Observer,
+    }
+}
+
+pub(crate) fn parse_role(role: &str) -> Option<ClientRole> {
+    match role.to_ascii_lowercase().as_str() {
+        "admin" => Some(ClientRole::Admin),
+        "pilot" => Some(ClientRole::Pilot),
+        "observer" => Some(ClientRole::Observer),
+        _ => None,
+    }
+}
+
+fn parse_users(content: &str) -> Result<Vec<User>> {
+    let mut users = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let [name, role, token] = fields[..] else {
+            return Err(anyhow!(
+                "line {} of the token file is not `name role token`",
+                index + 1
+            ));
+        };
+        let role = parse_role(role)
+            .ok_or_else(|| anyhow!("line {} has an unknown role {}", index + 1, role))?;
+        users.push(User {
+            name: name.to_string(),
+            token: token.to_string(),
+            role,
+        });
+    }
+    Ok(users)
+}
+
+/// compare the tokens in a time which does not depend on where they differ
+fn token_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0
+}
@@ -0,0 +1,882 @@
+use crate::{
+    auth::{parse_role, required_role, Authenticator},
+    handler::{
+        diagnostic_handler, error_code, filter_broadcast, list_planes, plane_command_args,
+        plane_command_handler, plane_event, plane_state, viewer_handler, Broadcast, ClientRegistry,
+        PlaneHandle, PlaneMap,
+    },
+    ownership::{authorize_plane, plane_controller, take_plane, PlaneOwner},
+    room::Room,
+    session::SessionStore,
+    subscription::OutputFilter,
+    system::System,
+    tls::TlsIdentity,
+};
+use anyhow::Result;
+use fly_ruler_codec::{
+    generated::{
+        core_output::PlaneMessage as PlaneMessageGen,
+        id::Id as IdGen,
+        service::{
+            fly_ruler_service_server::{FlyRulerService, FlyRulerServiceServer},
+            GetModelDetailsRequest as GetModelDetailsRequestGen,
+            GetModelDetailsResponse as GetModelDetailsResponseGen,
+            GetModelInfosResponse as GetModelInfosResponseGen,
+            GetPlaneStateRequest as GetPlaneStateRequestGen,
+            ListPlanesResponse as ListPlanesResponseGen, PlaneRequest as PlaneRequestGen,
+            PlaneResponse as PlaneResponseGen, PushPlaneRequest as PushPlaneRequestGen,
+            PushPlaneResponse as PushPlaneResponseGen, SendControlRequest as SendControlRequestGen,
+            SetTimeScaleRequest as SetTimeScaleRequestGen, Subscription as SubscriptionGen,
+            TrimRequest as TrimRequestGen, TrimResponse as TrimResponseGen,
+        },
+    },
+    Args, ClientRole, ErrorCode, GetModelDetailsResponse, GetModelInfosResponse,
+    ListPlanesResponse, PlaneResponse, PlaneStatus, PluginInfoTuple, PushPlaneResponse, Response,
+    ServiceCallResponse, Subscription, TrimResponse,
+};
+use fly_ruler_core::core::{ClockMode, PlaneInitCfg};
+use fly_ruler_utils::{
+    plane_model::{Control, PlaneCommand},
+    CancellationToken,
+};
+use futures_util::{Stream, StreamExt};
+use std::{
+    collections::{HashMap, HashSet},
+    io,
+    net::SocketAddr,
+    pin::Pin,
+    sync::{Arc, Weak},
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::{
+    io::{AsyncRead, AsyncWrite, ReadBuf},
+    net::{TcpListener, TcpStream},
+    sync::{
+        broadcast::{self, error::RecvError},
+        mpsc, Mutex,
+    },
+};
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::{
+    transport::server::{Connected, TcpConnectInfo},
+    Request, Status, Streaming,
+};
+use tracing::{event, Level};
+use uuid::Uuid;
+
+/// a connected grpc client
+struct GrpcPeer {
+    /// held by the connection
+    planes: Weak<PlaneMap>,
+    /// named by the first call carrying `session` metadata, empty without
+    session: String,
+}
+
+/// the connected grpc clients, by address
+type PeerRegistry = Arc<Mutex<HashMap<SocketAddr, GrpcPeer>>>;
+
+/// the session a grpc client names with `session` metadata
+#[derive(Clone)]
+struct GrpcSession(String);
+
+/// an accepted grpc connection, the planes of the client live as long as it,
+/// they are detached or released when it is closed
+struct GrpcConnection {
+    stream: TcpStream,
+    addr: SocketAddr,
+    planes: Arc<PlaneMap>,
+    closed: mpsc::UnboundedSender<(SocketAddr, Arc<PlaneMap>)>,
+}
+
+impl Drop for GrpcConnection {
+    fn drop(&mut self) {
+        let _ = self.closed.send((self.addr, self.planes.clone()));
+    }
+}
+
+impl Connected for GrpcConnection {
+    type ConnectInfo = TcpConnectInfo;
+
+    fn connect_info(&self) -> Self::ConnectInfo {
+        self.stream.connect_info()
+    }
+}
+
+impl AsyncRead for GrpcConnection {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.stream).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for GrpcConnection {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.stream).poll_write(cx, buf)
+    }
+
+    fn poll_write_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[io::IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.stream).poll_write_vectored(cx, bufs)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.stream.is_write_vectored()
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.stream).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.stream).poll_shutdown(cx)
+    }
+}
+
+/// accept the grpc connections and register their clients in the room
+fn incoming(
+    listener: TcpListener,
+    peers: PeerRegistry,
+    clients: ClientRegistry,
+    closed: mpsc::UnboundedSender<(SocketAddr, Arc<PlaneMap>)>,
+) -> impl Stream<Item = io::Result<GrpcConnection>> {
+    futures_util::stream::unfold(
+        (listener, peers, clients, closed),
+        |(listener, peers, clients, closed)| async move {
+            // an error ends the server, so a failed accept is only logged
+            let (stream, addr) = loop {
+                match listener.accept().await {
+                    Ok(accepted) => break accepted,
+                    Err(e) => {
+                        event!(Level::WARN, "gRPC accept failed, {}", e);
+                        tokio::time::sleep(Duration::from_millis(100)).await;
+                    }
+                }
+            };
+            let _ = stream.set_nodelay(true);
+            let planes = Arc::new(Mutex::new(HashMap::new()));
+            clients.lock().await.insert(addr, Arc::downgrade(&planes));
+            peers.lock().await.insert(
+                addr,
+                GrpcPeer {
+                    planes: Arc::downgrade(&planes),
+                    session: String::new(),
+                },
+            );
+            let connection = GrpcConnection {
+                stream,
+                addr,
+                planes,
+                closed: closed.clone(),
+            };
+            Some((Ok(connection), (listener, peers, clients, closed)))
+        },
+    )
+}
+
+/// the `FlyRulerService` over grpc, sharing the system and the planes of the framed server,
+/// the planes pushed over grpc are owned by the connection of the pushing client,
+/// they are released when it is closed, or kept for the grace period of its session
+struct GrpcService {
+    peers: PeerRegistry,
+    system: Arc<std::sync::Mutex<System>>,
+    init_cfg: PlaneInitCfg,
+    controller_buffer: usize,
+    clock_mode: Option<ClockMode>,
+    broadcast_channel_sender: broadcast::Sender<Broadcast>,
+    clients: ClientRegistry,
+    sessions: SessionStore,
+    cancellation_token: CancellationToken,
+}
+
+/// serve `FlyRulerService` on `addr` until the server is cancelled,
+/// the clients send their token as `authorization: Bearer <token>` and their role as `role` metadata,
+/// a client reconnecting with the `session` metadata of its lost connection reclaims its planes
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn grpc_handler(
+    addr: String,
+    tls: Option<TlsIdentity>,
+    auth: Authenticator,
+    system: Arc<std::sync::Mutex<System>>,
+    init_cfg: PlaneInitCfg,
+    controller_buffer: usize,
+    room: Room,
+    cancellation_token: CancellationToken,
+) -> Result<()> {
+    let Room {
+        clock_mode,
+        broadcast_channel_sender,
+        clients,
+        sessions,
+        ..
+    } = room;
+    let listener = TcpListener::bind(&addr).await?;
+    let peers: PeerRegistry = Default::default();
+    let (closed_sender, mut closed) = mpsc::unbounded_channel();
+    tokio::spawn({
+        let peers = peers.clone();
+        let clients = clients.clone();
+        let sessions = sessions.clone();
+        let broadcast_channel_sender = broadcast_channel_sender.clone();
+        async move {
+            while let Some((addr, planes)) = closed.recv().await {
+                let session = peers
+                    .lock()
+                    .await
+                    .remove(&addr)
+                    .map(|peer| peer.session)
+                    .unwrap_or_default();
+                event!(Level::DEBUG, "gRPC client: {} disconnected", addr);
+                sessions
+                    .detach(&session, addr, &planes, &clients, &broadcast_channel_sender)
+                    .await;
+            }
+        }
+    });
+    let incoming = incoming(listener, peers.clone(), clients.clone(), closed_sender);
+    let service = GrpcService {
+        peers,
+        system,
+        init_cfg,
+        controller_buffer,
+        clock_mode,
+        broadcast_channel_sender,
+        clients,
+        sessions,
+        cancellation_token: cancellation_token.clone(),
+    };
+
+    // the error is defined by tonic
+    #[allow(clippy::result_large_err)]
+    let interceptor = move |mut request: Request<()>| {
+        let token = request
+            .metadata()
+            .get("authorization")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "))
+            .unwrap_or_default();
+        let role = match request.metadata().get("role") {
+            Some(role) => role
+                .to_str()
+                .ok()
+                .and_then(parse_role)
+                .ok_or_else(|| Status::invalid_argument("unknown role"))?,
+            None => ClientRole::Pilot,
+        };
+        let role = auth
+            .authenticate(token, role)
+            .map_err(Status::unauthenticated)?;
+        request.extensions_mut().insert(role);
+        let session = request
+            .metadata()
+            .get("session")
+            .and_then(|v| v.to_str().ok())
+            .filter(|v| !v.is_empty())
+            .map(|v| GrpcSession(v.to_string()));
+        if let Some(session) = session {
+            request.extensions_mut().insert(session);
+        }
+        Ok(request)
+    };
+
+    event!(Level::INFO, "gRPC server started on {}", addr);
+    let mut builder = tonic::transport::Server::builder();
+    if let Some(tls) = tls {
+        builder = builder.tls_config(tls.grpc())?;
+    }
+    builder
+        .add_service(FlyRulerServiceServer::with_interceptor(
+            service,
+            interceptor,
+        ))
+        .serve_with_incoming_shutdown(incoming, cancellation_token.cancelled())
+        .await?;
+    Ok(())
+}
+
+fn status(code: ErrorCode, message: impl ToString) -> Status {
+    let message = message.to_string();
+    match code {
+        ErrorCode::InvalidRequest | ErrorCode::InvalidArgs | ErrorCode::InvalidId => {
+            Status::invalid_argument(message)
+        }
+        ErrorCode::PlaneNotFound => Status::not_found(message),
+        ErrorCode::ModelNotAvailable => Status::unavailable(message),
+        ErrorCode::TrimFailed => Status::failed_precondition(message),
+        ErrorCode::PermissionDenied | ErrorCode::NotPlaneOwner => {
+            Status::permission_denied(message)
+        }
+        ErrorCode::Internal | ErrorCode::HandshakeRequired | ErrorCode::MalformedFrame => {
+            Status::internal(message)
+        }
+    }
+}
+
+/// ends an output stream that fell behind the broadcast, the client reopens it
+fn lagged(skipped: u64) -> Status {
+    event!(
+        Level::WARN,
+        "gRPC stream lagged, {} outputs skipped",
+        skipped
+    );
+    Status::data_loss(format!(
+        "{} outputs skipped, the stream fell behind",
+        skipped
+    ))
+}
+
+/// role granted by the interceptor
+fn role<T>(request: &Request<T>) -> ClientRole {
+    request
+        .extensions()
+        .get::<ClientRole>()
+        .copied()
+        .unwrap_or(ClientRole::Observer)
+}
+
+/// reject a call which the role granted by the interceptor does not allow
+#[allow(clippy::result_large_err)]
+fn authorize<T>(request: &Request<T>, call: &str) -> Result<(), Status> {
+    let role = role(request);
+    if role.includes(required_role(call)) {
+        Ok(())
+    } else {
+        Err(status(
+            ErrorCode::PermissionDenied,
+            format!("`{}` is not allowed for {:?}", call, role),
+        ))
+    }
+}
+
+/// session named by the client
+fn session<T>(request: &Request<T>) -> Option<GrpcSession> {
+    request.extensions().get::<GrpcSession>().cloned()
+}
+
+/// id of a request, a missing id is rejected by the lookup instead of panicking
+fn id(id: Option<IdGen>) -> String {
+    id.map(|id| id.id).unwrap_or_default()
+}
+
+impl GrpcService {
+    /// the address of the client and its planes,
+    /// the first call naming a session reclaims the planes kept for it
+    #[allow(clippy::result_large_err)]
+    async fn peer(
+        &self,
+        remote_addr: Option<SocketAddr>,
+        session: Option<GrpcSession>,
+    ) -> Result<(SocketAddr, Arc<PlaneMap>), Status> {
+        let addr =
+            remote_addr.ok_or_else(|| status(ErrorCode::Internal, "unknown peer address"))?;
+        let (planes, reclaim) = {
+            let mut peers = self.peers.lock().await;
+            let peer = peers
+                .get_mut(&addr)
+                .ok_or_else(|| status(ErrorCode::Internal, "connection is closed"))?;
+            let planes = peer
+                .planes
+                .upgrade()
+                .ok_or_else(|| status(ErrorCode::Internal, "connection is closed"))?;
+            let reclaim = match session {
+                Some(GrpcSession(session))
+                    if peer.session.is_empty() && self.sessions.is_enabled() =>
+                {
+                    peer.session = session.clone();
+                    Some(session)
+                }
+                _ => None,
+            };
+            (planes, reclaim)
+        };
+        if let Some(session) = reclaim {
+            self.sessions
+                .reclaim(
+                    &session,
+                    addr,
+                    &planes,
+                    &self.clients,
+                    &self.broadcast_channel_sender,
+                )
+                .await;
+        }
+        Ok((addr, planes))
+    }
+
+    /// run a lifecycle command on a plane the client may control,
+    /// or on every plane pushed by the client
+    async fn plane_command<T>(
+        &self,
+        request: Request<T>,
+        name: &str,
+        args: impl FnOnce(T) -> Args,
+    ) -> Result<tonic::Response<PlaneResponseGen>, Status> {
+        let role = role(&request);
+        let (peer, peer_planes) = self.peer(request.remote_addr(), session(&request)).await?;
+        let args = args(request.into_inner());
+        let (plane_id, command) = plane_command_args(name, Some(args))
+            .ok_or_else(|| status(ErrorCode::InvalidArgs, "Invalid RPC args"))?;
+        let target = plane_id.clone().unwrap_or_default();
+        let (owner, planes) = match &plane_id {
+            Some(plane_id) => {
+                let owner_only = command == PlaneCommand::Remove;
+                authorize_plane(&self.clients, peer, role, plane_id, owner_only)
+                    .await
+                    .map_err(|(code, message)| status(code, message))?
+            }
+            None => (peer, peer_planes),
+        };
+        let plane_ids = plane_command_handler(&planes, plane_id, command)
+            .await
+            .ok_or_else(|| {
+                status(
+                    ErrorCode::PlaneNotFound,
+                    format!("Plane {} not found", target),
+                )
+            })?;
+        for plane_id in &plane_ids {
+            let _ = self.broadcast_channel_sender.send(Broadcast {
+                owner,
+                response: plane_event(plane_id.clone(), command),
+            });
+        }
+        Ok(tonic::Response::new(PlaneResponse { plane_ids }.into()))
+    }
+}
+
+#[tonic::async_trait]
+impl FlyRulerService for GrpcService {
+    type StreamOutputsStream = ReceiverStream<Result<PlaneMessageGen, Status>>;
+    type ControlStreamStream = ReceiverStream<Result<PlaneMessageGen, Status>>;
+
+    async fn get_model_infos(
+        &self,
+        _request: Request<()>,
+    ) -> Result<tonic::Response<GetModelInfosResponseGen>, Status> {
+        let models = self
+            .system
+            .lock()
+            .unwrap()
+            .get_models()
+            .map_err(|e| status(error_code(&e), e))?;
+        let model_infos = models
+            .into_iter()
+            .map(|m| PluginInfoTuple {
+                id: m.0.to_string(),
+                info: m.1 .0.into(),
+                state: m.1 .1,
+            })
+            .collect();
+        Ok(tonic::Response::new(
+            GetModelInfosResponse { model_infos }.into(),
+        ))
+    }
+
+    async fn push_plane(
+        &self,
+        request: Request<PushPlaneRequestGen>,
+    ) -> Result<tonic::Response<PushPlaneResponseGen>, Status> {
+        authorize(&request, "PushPlane")?;
+        let (peer, planes) = self.peer(request.remote_addr(), session(&request)).await?;
+        let request = request.into_inner();
+        let model_id =
+            Uuid::parse_str(&id(request.model_id)).map_err(|e| status(ErrorCode::InvalidId, e))?;
+        let init_cfg = request
+            .plane_init_cfg
+            .map_or_else(|| self.init_cfg, |c| c.into());
+
+        let plane_cancellation_token = CancellationToken::new();
+        let plane = tokio::task::spawn_blocking({
+            let system = self.system.clone();
+            let controller_buffer = self.controller_buffer;
+            let clock_mode = self.clock_mode;
+            let cancellation_token = plane_cancellation_token.clone();
+            move || {
+                system.lock().unwrap().push_plane(
+                    model_id,
+                    controller_buffer,
+                    init_cfg,
+                    clock_mode,
+                    cancellation_token,
+                )
+            }
+        })
+        .await
+        .map_err(|e| status(ErrorCode::Internal, e))?
+        .map_err(|e| status(error_code(&e), e))?;
+        let (id, viewer, controller, _handler, trim_output, diagnostic, commander) = plane;
+        let owner = PlaneOwner::new(peer);
+
+        planes.lock().await.insert(
+            id.to_string(),
+            PlaneHandle {
+                model_id: model_id.to_string(),
+                status: PlaneStatus::Running,
+                viewer: viewer.clone(),
+                controller,
+                commander,
+                owner: owner.clone(),
+                pilots: HashSet::new(),
+                cancellation_token: plane_cancellation_token.clone(),
+            },
+        );
+
+        tokio::spawn({
+            let owner = owner.clone();
+            let cancellation_token = self.cancellation_token.clone();
+            let plane_cancellation_token = plane_cancellation_token.clone();
+            let broadcast_channel_sender = self.broadcast_channel_sender.clone();
+            let clients = self.clients.clone();
+            async move {
+                let r = viewer_handler(
+                    id,
+                    owner,
+                    broadcast_channel_sender.clone(),
+                    viewer,
+                    cancellation_token,
+                    plane_cancellation_token,
+                )
+                .await;
+                if let Err(e) = r {
+                    // the plane has been removed by `RemovePlane`, or its owner is released
+                    let Some((owner, plane)) = take_plane(&clients, &id.to_string()).await else {
+                        return;
+                    };
+                    plane.cancellation_token.cancel();
+                    event!(Level::WARN, "gRPC plane {} lost, due to {}", id, e);
+                    let _ = broadcast_channel_sender.send(Broadcast {
+                        owner,
+                        response: ServiceCallResponse {
+                            name: "LostPlane".to_string(),
+                            request_id: 0,
+                            response: Some(Response::LostPlane(id.to_string())),
+                        },
+                    });
+                }
+            }
+        });
+
+        if let Some(diagnostic) = diagnostic {
+            tokio::spawn({
+                let cancellation_token = self.cancellation_token.clone();
+                let broadcast_channel_sender = self.broadcast_channel_sender.clone();
+                async move {
+                    let r = diagnostic_handler(
+                        id,
+                        owner,
+                        broadcast_channel_sender,
+                        diagnostic,
+                        cancellation_token,
+                        plane_cancellation_token,
+                    )
+                    .await;
+                    if let Err(e) = r {
+                        event!(
+                            Level::WARN,
+                            "Diagnostic of plane {} stopped, due to {}",
+                            id,
+                            e
+                        );
+                    }
+                }
+            });
+        }
+
+        let _ = self.broadcast_channel_sender.send(Broadcast {
+            owner: peer,
+            response: ServiceCallResponse {
+                name: "NewPlane".to_string(),
+                request_id: 0,
+                response: Some(Response::NewPlane(id.to_string())),
+            },
+        });
+
+        Ok(tonic::Response::new(
+            PushPlaneResponse {
+                plane_id: id.to_string(),
+                trim_output: Some(trim_output),
+            }
+            .into(),
+        ))
+    }
+
+    async fn send_control(
+        &self,
+        request: Request<SendControlRequestGen>,
+    ) -> Result<tonic::Response<()>, Status> {
+        authorize(&request, "SendControl")?;
+        let role = role(&request);
+        let (peer, planes) = self.peer(request.remote_addr(), session(&request)).await?;
+        let request = request.into_inner();
+        let plane_id = id(request.plane_id);
+        let control: Control = request.control.map(|c| c.into()).unwrap_or_default();
+        plane_controller(&self.clients, &planes, peer, role, &plane_id)
+            .await
+            .map_err(|(code, message)| status(code, message))?
+            .send(&control)
+            .await
+            .map_err(|e| status(ErrorCode::Internal, e))?;
+        Ok(tonic::Response::new(()))
+    }
+
+    async fn trim(
+        &self,
+        request: Request<TrimRequestGen>,
+    ) -> Result<tonic::Response<TrimResponseGen>, Status> {
+        let request = request.into_inner();
+        let model_id = id(request.model_id);
+        let uuid = Uuid::parse_str(&model_id).map_err(|e| status(ErrorCode::InvalidId, e))?;
+        let init_cfg = request
+            .plane_init_cfg
+            .map_or_else(|| self.init_cfg, |c| c.into());
+        let trim_output = tokio::task::spawn_blocking({
+            let system = self.system.clone();
+            move || system.lock().unwrap().trim(uuid, init_cfg)
+        })
+        .await
+        .map_err(|e| status(ErrorCode::Internal, e))?
+        .map_err(|e| status(error_code(&e), e))?;
+        Ok(tonic::Response::new(
+            TrimResponse {
+                model_id,
+                trim_output: Some(trim_output),
+            }
+            .into(),
+        ))
+    }
+
+    async fn remove_plane(
+        &self,
+        request: Request<PlaneRequestGen>,
+    ) -> Result<tonic::Response<PlaneResponseGen>, Status> {
+        authorize(&request, "RemovePlane")?;
+        self.plane_command(request, "RemovePlane", |r| Args::RemovePlane(r.into()))
+            .await
+    }
+
+    async fn pause_plane(
+        &self,
+        request: Request<PlaneRequestGen>,
+    ) -> Result<tonic::Response<PlaneResponseGen>, Status> {
+        authorize(&request, "PausePlane")?;
+        self.plane_command(request, "PausePlane", |r| Args::PausePlane(r.into()))
+            .await
+    }
+
+    async fn resume_plane(
+        &self,
+        request: Request<PlaneRequestGen>,
+    ) -> Result<tonic::Response<PlaneResponseGen>, Status> {
+        authorize(&request, "ResumePlane")?;
+        self.plane_command(request, "ResumePlane", |r| Args::ResumePlane(r.into()))
+            .await
+    }
+
+    async fn reset_plane(
+        &self,
+        request: Request<PlaneRequestGen>,
+    ) -> Result<tonic::Response<PlaneResponseGen>, Status> {
+        authorize(&request, "ResetPlane")?;
+        self.plane_command(request, "ResetPlane", |r| Args::ResetPlane(r.into()))
+            .await
+    }
+
+    async fn set_time_scale(
+        &self,
+        request: Request<SetTimeScaleRequestGen>,
+    ) -> Result<tonic::Response<PlaneResponseGen>, Status> {
+        authorize(&request, "SetTimeScale")?;
+        self.plane_command(request, "SetTimeScale", |r| Args::SetTimeScale(r.into()))
+            .await
+    }
+
+    async fn list_planes(
+        &self,
+        _request: Request<()>,
+    ) -> Result<tonic::Response<ListPlanesResponseGen>, Status> {
+        let planes = list_planes(&self.clients).await;
+        Ok(tonic::Response::new(ListPlanesResponse { planes }.into()))
+    }
+
+    async fn get_plane_state(
+        &self,
+        request: Request<GetPlaneStateRequestGen>,
+    ) -> Result<tonic::Response<PlaneMessageGen>, Status> {
+        let plane_id = id(request.into_inner().plane_id);
+        match plane_state(&self.clients, &plane_id).await {
+            Some(state) => Ok(tonic::Response::new(state.into())),
+            None => Err(status(
+                ErrorCode::PlaneNotFound,
+                format!("Plane {} not found", plane_id),
+            )),
+        }
+    }
+
+    async fn get_model_details(
+        &self,
+        request: Request<GetModelDetailsRequestGen>,
+    ) -> Result<tonic::Response<GetModelDetailsResponseGen>, Status> {
+        let model_id = id(request.into_inner().model_id);
+        let uuid = Uuid::parse_str(&model_id).map_err(|e| status(ErrorCode::InvalidId, e))?;
+        let (info, state, constants, control_limit) = self
+            .system
+            .lock()
+            .unwrap()
+            .get_model_details(uuid)
+            .map_err(|e| status(error_code(&e), e))?;
+        Ok(tonic::Response::new(
+            GetModelDetailsResponse {
+                model_info: Some(PluginInfoTuple {
+                    id: model_id,
+                    info: Some(info),
+                    state,
+                }),
+                constants: Some(constants),
+                control_limit: Some(control_limit),
+            }
+            .into(),
+        ))
+    }
+
+    async fn subscribe(
+        &self,
+        _request: Request<SubscriptionGen>,
+    ) -> Result<tonic::Response<SubscriptionGen>, Status> {
+        Err(Status::unimplemented(
+            "grpc clients pass their subscription to `StreamOutputs`",
+        ))
+    }
+
+    async fn unsubscribe(
+        &self,
+        _request: Request<SubscriptionGen>,
+    ) -> Result<tonic::Response<SubscriptionGen>, Status> {
+        Err(Status::unimplemented(
+            "grpc clients pass their subscription to `StreamOutputs`",
+        ))
+    }
+
+    async fn stream_outputs(
+        &self,
+        request: Request<SubscriptionGen>,
+    ) -> Result<tonic::Response<Self::StreamOutputsStream>, Status> {
+        let subscription: Subscription = request.into_inner().into();
+        let mut filter = OutputFilter::new(None);
+        // a selective subscription replaces the default of every plane
+        if !(subscription.all
+            || (subscription.plane_ids.is_empty() && subscription.owners.is_empty()))
+        {
+            filter
+                .unsubscribe(Subscription {
+                    all: true,
+                    ..Default::default()
+                })
+                .map_err(|e| status(ErrorCode::InvalidArgs, e))?;
+        }
+        filter
+            .subscribe(subscription)
+            .map_err(|e| status(ErrorCode::InvalidArgs, e))?;
+
+        let (tx, rx) = mpsc::channel(1024);
+        let mut receiver = self.broadcast_channel_sender.subscribe();
+        let cancellation_token = self.cancellation_token.clone();
+        tokio::spawn(async move {
+            while !cancellation_token.is_cancelled() {
+                let msg = match receiver.recv().await {
+                    Ok(msg) => msg,
+                    Err(RecvError::Lagged(skipped)) => {
+                        let _ = tx.send(Err(lagged(skipped))).await;
+                        break;
+                    }
+                    Err(RecvError::Closed) => break,
+                };
+                if let Some(ServiceCallResponse {
+                    response: Some(Response::Output(output)),
+                    ..
+                }) = filter_broadcast(&mut filter, msg)
+                {
+                    if tx.send(Ok(output.into())).await.is_err() {
+                        break;
+                    }
+                }
+            }
+        });
+        Ok(tonic::Response::new(ReceiverStream::new(rx)))
+    }
+
+    async fn control_stream(
+        &self,
+        request: Request<Streaming<SendControlRequestGen>>,
+    ) -> Result<tonic::Response<Self::ControlStreamStream>, Status> {
+        authorize(&request, "SendControl")?;
+        let role = role(&request);
+        let (peer, planes) = self.peer(request.remote_addr(), session(&request)).await?;
+        let mut controls = request.into_inner();
+        let (tx, rx) = mpsc::channel(1024);
+        let mut receiver = self.broadcast_channel_sender.subscribe();
+        let clients = self.clients.clone();
+        let cancellation_token = self.cancellation_token.clone();
+        tokio::spawn(async move {
+            let mut controlled = HashSet::new();
+            while !cancellation_token.is_cancelled() {
+                tokio::select! {
+                    control = controls.next() => {
+                        let control = match control {
+                            Some(Ok(control)) => control,
+                            Some(Err(e)) => {
+                                event!(Level::WARN, "gRPC control stream failed, {}", e);
+                                break;
+                            }
+                            None => break,
+                        };
+                        let plane_id = id(control.plane_id);
+                        let control: Control = control.control.map(|c| c.into()).unwrap_or_default();
+                        match plane_controller(&clients, &planes, peer, role, &plane_id).await {
+                            Ok(controller) => {
+                                if let Err(e) = controller.send(&control).await {
+                                    event!(Level::WARN, "Plane {} control failed, {}", plane_id, e);
+                                }
+                            }
+                            Err((code, message)) => {
+                                let _ = tx.send(Err(status(code, message))).await;
+                                break;
+                            }
+                        }
+                        controlled.insert(plane_id);
+                    }
+                    msg = receiver.recv() => {
+                        let output = match msg {
+                            Ok(Broadcast {
+                                response: ServiceCallResponse {
+                                    response: Some(Response::Output(output)),
+                                    ..
+                                },
+                                ..
+                            }) if controlled.contains(&output.id) => output,
+                            Ok(_) => continue,
+                            Err(RecvError::Lagged(skipped)) => {
+                                let _ = tx.send(Err(lagged(skipped))).await;
+                                break;
+                            }
+                            Err(RecvError::Closed) => break,
+                        };
+                        if tx.send(Ok(output.into())).await.is_err() {
+                            break;
+                        }
+                    }
+                }
+            }
+        });
+        Ok(tonic::Response::new(ReceiverStream::new(rx)))
+    }
+}
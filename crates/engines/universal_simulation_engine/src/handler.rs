@@ -1,252 +1,1026 @@
-use crate::system::System;
+#[cfg(unix)]
+use crate::ipc::uds_handler;
+use crate::{
+    auth::{required_role, Authenticator},
+    grpc::grpc_handler,
+    ipc::shm_handler,
+    ownership::{
+        authorize_plane, owner_changed, plane_controller, release_client, share_plane, take_plane,
+        transfer_plane, PlaneOwner,
+    },
+    queue::{Backpressure, OutputQueue, QueueRegistry},
+    room::{Room, Rooms},
+    subscription::OutputFilter,
+    system::{SysError, System},
+    tls::TlsIdentity,
+    udp::{udp_handler, UdpChannel, UdpLink},
+    websocket::websocket_handler,
+};
 use anyhow::{anyhow, Result};
 use fly_ruler_codec::{
-    Args, GetModelInfosResponse, PlaneMessage, PluginInfoTuple, PushPlaneResponse, RequestFrame,
-    Response, ResponseFrame, ServiceCallResponse,
+    Args, ClientRole, Compression, ErrorCode, ErrorResponse, Feature, GetModelDetailsResponse,
+    GetModelInfosResponse, GetStatusResponse, Hello, HelloAck, ListPlanesResponse, OpenUdpResponse,
+    PlaneDiagnostic, PlaneEvent, PlaneEventKind, PlaneInfo, PlaneMessage, PlaneMessageGroup,
+    PlaneResponse, PlaneStatus, PluginInfoTuple, PushPlaneResponse, RequestFrame, Response,
+    ResponseFrame, ServiceCall, ServiceCallResponse, TrimResponse, PROTOCOL_VERSION,
+};
+use fly_ruler_core::core::{ClockMode, PlaneInitCfg};
+use fly_ruler_utils::{
+    error::FrError, plane_model::PlaneCommand, CancellationToken, DiagnosticReceiver, InputSender,
+    OutputReceiver, PlaneCommandSender, Signal,
+};
+use futures_util::{Sink, SinkExt, Stream, StreamExt};
+use std::{
+    collections::{HashMap, HashSet},
+    net::SocketAddr,
+    sync::{Arc, Weak},
+    time::Duration,
 };
-use fly_ruler_core::core::PlaneInitCfg;
-use fly_ruler_utils::{CancellationToken, InputSender, OutputReceiver, Signal};
-use futures_util::{SinkExt, StreamExt};
-use std::{collections::HashMap, net::SocketAddr, ops::Deref, sync::Arc, time::Duration};
 use tokio::{
-    net::{
-        tcp::{OwnedReadHalf, OwnedWriteHalf},
-        TcpListener,
-    },
-    sync::{broadcast, mpsc, Mutex, Notify},
+    io::{AsyncRead, AsyncWrite},
+    net::TcpListener,
+    sync::{broadcast, Mutex, Notify},
 };
 use tokio_util::codec::{FramedRead, FramedWrite};
 use tracing::{event, instrument, Level};
 use uuid::Uuid;
 
+/// the senders of a plane owned by a client
+#[derive(Clone)]
+pub(crate) struct PlaneHandle {
+    pub(crate) model_id: String,
+    pub(crate) status: PlaneStatus,
+    pub(crate) viewer: OutputReceiver,
+    pub(crate) controller: InputSender,
+    pub(crate) commander: PlaneCommandSender,
+    pub(crate) owner: PlaneOwner,
+    /// clients allowed to control the plane besides the owner
+    pub(crate) pilots: HashSet<SocketAddr>,
+    /// stops the plane, it outlives the client which pushed it if the plane is transferred
+    pub(crate) cancellation_token: CancellationToken,
+}
+
+impl PlaneHandle {
+    pub(crate) fn info(&self, plane_id: &str) -> PlaneInfo {
+        PlaneInfo {
+            plane_id: plane_id.to_string(),
+            model_id: self.model_id.clone(),
+            owner: self.owner.get().to_string(),
+            status: self.status,
+            time: self.viewer.get().0,
+            pilots: self.pilots.iter().map(|p| p.to_string()).collect(),
+        }
+    }
+}
+
+pub(crate) type PlaneMap = Mutex<HashMap<String, PlaneHandle>>;
+
+/// planes of every connected client, keyed by client address
+pub(crate) type ClientRegistry = Arc<Mutex<HashMap<SocketAddr, Weak<PlaneMap>>>>;
+
+/// message sent to every client, tagged with the owner of the plane
+#[derive(Debug, Clone)]
+pub(crate) struct Broadcast {
+    pub(crate) owner: SocketAddr,
+    pub(crate) response: ServiceCallResponse,
+}
+
+/// options of the server, loaded from the `server` table of the config
+#[derive(Debug, Clone)]
+pub struct ServerCfg {
+    pub addr: String,
+    /// ms, the pilots which send no `Tick` within the timeout are dropped
+    pub tick_timeout: u64,
+    /// ms, the clients which send no call within the timeout are dropped, 0 disables the check
+    pub liveness_timeout: u64,
+    pub read_rate: u64,
+    /// ms, outputs sent within the interval are grouped in one frame, 0 disables grouping
+    pub batch_interval: u64,
+    /// messages waiting for a client, the backpressure policy applies beyond
+    pub output_queue: usize,
+    /// what happens to the outputs of a client whose queue is full
+    pub backpressure: Backpressure,
+    /// bytes, frames smaller than the threshold are sent uncompressed
+    pub compression_threshold: u32,
+    /// bytes, larger frames are rejected and the client is dropped
+    pub max_frame_size: usize,
+    /// serve `FlyRulerService` over grpc on this address too
+    pub grpc_addr: Option<String>,
+    /// accept websocket clients on this address too
+    pub ws_addr: Option<String>,
+    /// udp side channel of the controls and outputs, opened by the clients with `OpenUdp`
+    pub udp_addr: Option<String>,
+    /// accept local clients on this unix domain socket too, ignored on other platforms
+    pub uds_path: Option<String>,
+    /// publish the latest output of every plane of the default room in this shared memory file,
+    /// any local user may read it, so it is refused when a token is configured
+    pub shm_path: Option<String>,
+    /// planes in the shared memory file, outputs of the other planes are dropped
+    pub shm_capacity: u32,
+    /// serve tls on the tcp, websocket and grpc addresses, with this pem certificate chain
+    pub tls_cert_path: Option<String>,
+    /// pem private key of the certificate
+    pub tls_key_path: Option<String>,
+    /// tokens checked during the handshake
+    pub auth: Authenticator,
+    /// worlds of the clients, each one with its own planes, clock, sessions and access list
+    pub rooms: Rooms,
+}
+
+/// calls read from a client, whatever the transport
+pub(crate) trait CallReader:
+    Stream<Item = Result<ServiceCall, FrError>> + Unpin + Send + 'static
+{
+}
+
+impl<T> CallReader for T where
+    T: Stream<Item = Result<ServiceCall, FrError>> + Unpin + Send + 'static
+{
+}
+
+/// responses sent to a client, whatever the transport
+pub(crate) trait ResponseWriter:
+    Sink<ServiceCallResponse, Error = FrError> + Unpin + Send + 'static
+{
+}
+
+impl<T> ResponseWriter for T where
+    T: Sink<ServiceCallResponse, Error = FrError> + Unpin + Send + 'static
+{
+}
+
 #[instrument(skip(system, cancellation_token, init_cfg),level = Level::INFO)]
 pub async fn server_handler(
-    server_addr: &str,
-    tick_timeout: u64,
-    read_rate: u64,
+    server_cfg: ServerCfg,
     init_cfg: PlaneInitCfg,
     system: Arc<std::sync::Mutex<System>>,
     controller_buffer: usize,
     cancellation_token: CancellationToken,
 ) {
-    let listener = TcpListener::bind(server_addr).await.unwrap();
-    let (broadcast_channel_sender, _) = broadcast::channel::<ServiceCallResponse>(1024);
-    event!(Level::INFO, "Server started on {}", server_addr);
-
-    loop {
-        let cancellation_token = cancellation_token.clone();
-        if cancellation_token.is_cancelled() {
-            break;
+    let ServerCfg {
+        addr: server_addr,
+        grpc_addr,
+        ws_addr,
+        udp_addr,
+        uds_path,
+        shm_path,
+        shm_capacity,
+        tls_cert_path,
+        tls_key_path,
+        auth,
+        rooms,
+        ..
+    } = server_cfg.clone();
+    let tls = match (tls_cert_path, tls_key_path) {
+        (Some(cert_path), Some(key_path)) => match TlsIdentity::load(&cert_path, &key_path) {
+            Ok(tls) => Some(tls),
+            Err(e) => {
+                event!(Level::ERROR, "Server stopped, due to {}", e);
+                return;
+            }
+        },
+        (None, None) => None,
+        _ => {
+            event!(
+                Level::ERROR,
+                "Server stopped, TLS needs both `tls_cert_path` and `tls_key_path`"
+            );
+            return;
         }
-
-        let (client, client_addr) = listener.accept().await.unwrap();
-        event!(Level::INFO, "Accepted connection from {}", client_addr);
-
-        let (reader, writer) = client.into_split();
-        let reader = FramedRead::new(reader, RequestFrame);
-        let writer = FramedWrite::new(writer, ResponseFrame);
-        let controllers = Arc::new(Mutex::new(HashMap::new()));
-        let grct = CancellationToken::new();
-        let (private_channel_sender, private_channel_receiver) =
-            mpsc::channel::<ServiceCallResponse>(1024);
-        let tick_notify = Arc::new(Notify::new());
-        let run_signal = Signal::new();
-
-        let _rpc_task = tokio::spawn({
-            let controller1 = controllers.clone();
-            let controller2 = controllers.clone();
-            let gct1 = cancellation_token.clone();
-            let grct1 = grct.clone();
-            let grct2 = grct.clone();
-            let system1 = system.clone();
-            let broadcast_channel_sender1 = broadcast_channel_sender.clone();
-            let broadcast_channel_sender2 = broadcast_channel_sender.clone();
-            let tick_notify1 = tick_notify.clone();
-            let run_signal1 = run_signal.clone();
+    };
+    let acceptor = match tls.as_ref().map(|tls| tls.acceptor()).transpose() {
+        Ok(acceptor) => acceptor,
+        Err(e) => {
+            event!(Level::ERROR, "Server stopped, due to {}", e);
+            return;
+        }
+    };
+    if !auth.is_enabled() {
+        event!(
+            Level::WARN,
+            "No token is configured, every client gets the role it asks for"
+        );
+    }
+    let listener = TcpListener::bind(&server_addr).await.unwrap();
+    // the grpc clients and the shared memory live in the default room
+    let default_room = rooms.default_room();
+    if let Some(grpc_addr) = grpc_addr {
+        tokio::spawn({
+            let tls = tls.clone();
+            let auth = auth.clone();
+            let system = system.clone();
+            let room = default_room.clone();
+            let cancellation_token = cancellation_token.clone();
             async move {
-                let r = rpc_handler(
-                    client_addr,
-                    tick_timeout,
-                    read_rate,
-                    reader,
-                    system1,
+                let r = grpc_handler(
+                    grpc_addr,
+                    tls,
+                    auth,
+                    system,
                     init_cfg,
                     controller_buffer,
-                    broadcast_channel_sender1,
-                    private_channel_sender,
-                    controller1,
-                    run_signal1,
-                    tick_notify1,
-                    gct1,
-                    grct1,
+                    room,
+                    cancellation_token,
                 )
                 .await;
                 if let Err(e) = r {
-                    grct2.cancel();
-                    event!(
-                        Level::WARN,
-                        "RPC Client: {} dropped, due to {}",
-                        client_addr,
-                        e
-                    );
-                    for (id, _sender) in controller2.lock().await.deref().iter() {
-                        let _ = broadcast_channel_sender2.send(ServiceCallResponse {
-                            name: "LostPlane".to_string(),
-                            response: Some(Response::LostPlane(id.to_string())),
-                        });
-                    }
-                    controller2.lock().await.clear();
+                    event!(Level::ERROR, "gRPC server stopped, due to {}", e);
                 }
             }
         });
-
-        let _client_write_task = tokio::task::spawn({
-            let controller1 = controllers.clone();
-            let gct1 = cancellation_token.clone();
-            let grct1 = grct.clone();
-            let grct2 = grct.clone();
-            let broadcast_channel_receiver = broadcast_channel_sender.subscribe();
-            let broadcast_channel_sender1 = broadcast_channel_sender.clone();
+    }
+    let udp = match udp_addr {
+        Some(udp_addr) => match UdpChannel::bind(&udp_addr).await {
+            Ok(udp) => {
+                tokio::spawn(udp_handler(udp.clone(), cancellation_token.clone()));
+                Some(udp)
+            }
+            Err(e) => {
+                event!(Level::ERROR, "UDP side channel disabled, due to {}", e);
+                None
+            }
+        },
+        None => None,
+    };
+    if let Some(ws_addr) = ws_addr {
+        tokio::spawn({
+            let acceptor = acceptor.clone();
+            let server_cfg = server_cfg.clone();
+            let system = system.clone();
+            let cancellation_token = cancellation_token.clone();
             async move {
-                let r = client_write_handler(
-                    client_addr,
-                    broadcast_channel_receiver,
-                    private_channel_receiver,
-                    writer,
-                    gct1,
-                    grct1,
+                let r = websocket_handler(
+                    ws_addr,
+                    acceptor,
+                    server_cfg,
+                    init_cfg,
+                    system,
+                    controller_buffer,
+                    cancellation_token,
                 )
                 .await;
                 if let Err(e) = r {
-                    grct2.cancel();
-                    event!(
-                        Level::WARN,
-                        "RPC Client: {} dropped, due to {}",
-                        client_addr,
-                        e
-                    );
-                    for (id, _sender) in controller1.lock().await.deref().iter() {
-                        let _ = broadcast_channel_sender1.send(ServiceCallResponse {
-                            name: "LostPlane".to_string(),
-                            response: Some(Response::LostPlane(id.to_string())),
-                        });
-                    }
-                    controller1.lock().await.clear();
+                    event!(Level::ERROR, "WebSocket server stopped, due to {}", e);
                 }
             }
         });
-
-        let _tick_task = tokio::spawn({
-            let controller1 = controllers.clone();
-            let broadcast_channel_sender1 = broadcast_channel_sender.clone();
-            let gct1 = cancellation_token.clone();
-            let grct1 = grct.clone();
-            let grct2 = grct.clone();
-            let tick_notify1 = tick_notify.clone();
-            let run_signal1 = run_signal.clone();
+    }
+    #[cfg(unix)]
+    if let Some(uds_path) = uds_path {
+        tokio::spawn({
+            let server_cfg = server_cfg.clone();
+            let system = system.clone();
+            let udp = udp.clone();
+            let cancellation_token = cancellation_token.clone();
             async move {
-                let r = tick_handler(
+                let r = uds_handler(
+                    uds_path,
+                    server_cfg,
+                    init_cfg,
+                    system,
+                    controller_buffer,
+                    udp,
+                    cancellation_token,
+                )
+                .await;
+                if let Err(e) = r {
+                    event!(Level::ERROR, "Unix socket server stopped, due to {}", e);
+                }
+            }
+        });
+    }
+    #[cfg(not(unix))]
+    if uds_path.is_some() {
+        event!(
+            Level::WARN,
+            "Unix socket server is not supported on this platform"
+        );
+    }
+    // any local user may read the shared memory, whatever the tokens
+    if shm_path.is_some() && auth.is_enabled() {
+        event!(
+            Level::ERROR,
+            "Shared memory outputs disabled, they can not be used with `auth_token` or `auth_token_file`"
+        );
+    }
+    if let Some(shm_path) = shm_path.filter(|_| !auth.is_enabled()) {
+        tokio::spawn({
+            let receiver = default_room.broadcast_channel_sender.subscribe();
+            let clients = default_room.clients.clone();
+            let cancellation_token = cancellation_token.clone();
+            async move {
+                let r = shm_handler(
+                    shm_path,
+                    shm_capacity,
+                    receiver,
+                    clients,
+                    cancellation_token,
+                )
+                .await;
+                if let Err(e) = r {
+                    event!(Level::ERROR, "Shared memory outputs stopped, due to {}", e);
+                }
+            }
+        });
+    }
+    event!(Level::INFO, "Server started on {}", server_addr);
+
+    loop {
+        let (client, client_addr) = tokio::select! {
+            accepted = listener.accept() => accepted.unwrap(),
+            _ = cancellation_token.cancelled() => break,
+        };
+        let cancellation_token = cancellation_token.clone();
+        event!(Level::INFO, "Accepted connection from {}", client_addr);
+
+        match acceptor.clone() {
+            None => {
+                let (reader, writer) = client.into_split();
+                tokio::spawn(framed_client_handler(
+                    client_addr,
+                    reader,
+                    writer,
+                    server_cfg.clone(),
+                    init_cfg,
+                    system.clone(),
+                    controller_buffer,
+                    udp.clone(),
+                    cancellation_token,
+                ));
+            }
+            Some(acceptor) => {
+                tokio::spawn({
+                    let server_cfg = server_cfg.clone();
+                    let system = system.clone();
+                    let udp = udp.clone();
+                    async move {
+                        // the tls handshake runs in the task, a slow client does not block the others
+                        let stream = match acceptor.accept(client).await {
+                            Ok(stream) => stream,
+                            Err(e) => {
+                                event!(
+                                    Level::WARN,
+                                    "Client: {} tls handshake failed, due to {}",
+                                    client_addr,
+                                    e
+                                );
+                                return;
+                            }
+                        };
+                        let (reader, writer) = tokio::io::split(stream);
+                        framed_client_handler(
+                            client_addr,
+                            reader,
+                            writer,
+                            server_cfg,
+                            init_cfg,
+                            system,
+                            controller_buffer,
+                            udp,
+                            cancellation_token,
+                        )
+                        .await;
+                    }
+                });
+            }
+        }
+    }
+}
+
+/// serve a client of a byte stream, whose calls are framed by `RequestFrame`
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn framed_client_handler<R, W>(
+    client_addr: SocketAddr,
+    reader: R,
+    writer: W,
+    server_cfg: ServerCfg,
+    init_cfg: PlaneInitCfg,
+    system: Arc<std::sync::Mutex<System>>,
+    controller_buffer: usize,
+    udp: Option<Arc<UdpChannel>>,
+    cancellation_token: CancellationToken,
+) where
+    R: AsyncRead + Unpin + Send + 'static,
+    W: AsyncWrite + Unpin + Send + 'static,
+{
+    let ServerCfg {
+        tick_timeout,
+        compression_threshold,
+        max_frame_size,
+        ..
+    } = server_cfg;
+    let mut reader = FramedRead::new(reader, RequestFrame::new(max_frame_size));
+    let mut writer = FramedWrite::new(writer, ResponseFrame::new(max_frame_size));
+    let hello = handshake(
+        client_addr,
+        tick_timeout,
+        compression_threshold,
+        SERVER_FEATURES,
+        &server_cfg.auth,
+        &server_cfg.rooms,
+        &mut reader,
+        &mut writer,
+    )
+    .await;
+    let (hello, ack, room) = match hello {
+        Ok(hello) => hello,
+        Err(e) => {
+            event!(
+                Level::WARN,
+                "Client: {} rejected, due to {}",
+                client_addr,
+                e
+            );
+            return;
+        }
+    };
+    // the ack itself is sent uncompressed and without checksum
+    let encoder = writer.encoder_mut();
+    encoder.set_compression(ack.compression, compression_threshold as usize);
+    encoder.set_checksum(ack.features.contains(&Feature::Checksum));
+
+    client_handler(
+        client_addr,
+        hello,
+        room,
+        reader,
+        writer,
+        server_cfg,
+        init_cfg,
+        system,
+        controller_buffer,
+        udp,
+        cancellation_token,
+    )
+    .await;
+}
+
+/// serve a client which passed the handshake, until it disconnects
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn client_handler<R, W>(
+    client_addr: SocketAddr,
+    hello: Hello,
+    room: Room,
+    reader: R,
+    writer: W,
+    server_cfg: ServerCfg,
+    init_cfg: PlaneInitCfg,
+    system: Arc<std::sync::Mutex<System>>,
+    controller_buffer: usize,
+    udp: Option<Arc<UdpChannel>>,
+    cancellation_token: CancellationToken,
+) where
+    R: CallReader,
+    W: ResponseWriter,
+{
+    let ServerCfg {
+        tick_timeout,
+        liveness_timeout,
+        read_rate,
+        batch_interval,
+        output_queue: queue_capacity,
+        backpressure,
+        ..
+    } = server_cfg;
+    let Room {
+        name: room_name,
+        clock_mode,
+        broadcast_channel_sender,
+        clients,
+        sessions,
+        queues,
+        ..
+    } = room;
+    event!(
+        Level::INFO,
+        "Client: {} hello, {} {} as {:?} in room {:?}",
+        client_addr,
+        hello.client_name,
+        hello.client_version,
+        hello.role,
+        room_name
+    );
+
+    let planes = Arc::new(Mutex::new(HashMap::new()));
+    {
+        let mut clients = clients.lock().await;
+        clients.retain(|_, planes| planes.strong_count() > 0);
+        clients.insert(client_addr, Arc::downgrade(&planes));
+    }
+    let resumed = sessions
+        .reclaim(
+            &hello.session,
+            client_addr,
+            &planes,
+            &clients,
+            &broadcast_channel_sender,
+        )
+        .await;
+    if !resumed.is_empty() {
+        event!(
+            Level::INFO,
+            "Client: {} resumed {} planes",
+            client_addr,
+            resumed.len()
+        );
+    }
+    let grct = CancellationToken::new();
+    // the replies and broadcast messages of the client, a slow client only fills its own queue
+    let output_queue = Arc::new(OutputQueue::new(queue_capacity, backpressure));
+    {
+        let mut queues = queues.lock().unwrap();
+        queues.retain(|_, queue| queue.strong_count() > 0);
+        queues.insert(client_addr, Arc::downgrade(&output_queue));
+    }
+    let tick_notify = Arc::new(Notify::new());
+    let alive_notify = Arc::new(Notify::new());
+    let run_signal = Signal::new();
+    // clients which did not negotiate batching receive outputs one by one
+    let batch_interval = if hello.features.contains(&Feature::Batching) && batch_interval > 0 {
+        Some(Duration::from_millis(batch_interval))
+    } else {
+        None
+    };
+    let output_filter = Arc::new(Mutex::new(OutputFilter::new(batch_interval)));
+    let udp_link = Arc::new(Mutex::new(None));
+
+    let _rpc_task = tokio::spawn({
+        let planes1 = planes.clone();
+        let planes2 = planes.clone();
+        let gct1 = cancellation_token.clone();
+        let grct1 = grct.clone();
+        let grct2 = grct.clone();
+        let system1 = system.clone();
+        let clients1 = clients.clone();
+        let clients2 = clients.clone();
+        let broadcast_channel_sender1 = broadcast_channel_sender.clone();
+        let broadcast_channel_sender2 = broadcast_channel_sender.clone();
+        let sessions1 = sessions.clone();
+        let session = hello.session.clone();
+        let tick_notify1 = tick_notify.clone();
+        let alive_notify1 = alive_notify.clone();
+        let run_signal1 = run_signal.clone();
+        let output_filter1 = output_filter.clone();
+        let udp_link1 = udp_link.clone();
+        let output_queue1 = output_queue.clone();
+        async move {
+            let r = rpc_handler(
+                client_addr,
+                hello.role,
+                tick_timeout,
+                read_rate,
+                reader,
+                system1,
+                init_cfg,
+                controller_buffer,
+                clock_mode,
+                broadcast_channel_sender1,
+                output_queue1,
+                queues,
+                planes1,
+                clients1,
+                output_filter1,
+                udp,
+                udp_link1,
+                run_signal1,
+                tick_notify1,
+                alive_notify1,
+                gct1,
+                grct1,
+            )
+            .await;
+            if let Err(e) = r {
+                grct2.cancel();
+                event!(
+                    Level::WARN,
+                    "RPC Client: {} dropped, due to {}",
+                    client_addr,
+                    e
+                );
+                sessions1
+                    .detach(
+                        &session,
+                        client_addr,
+                        &planes2,
+                        &clients2,
+                        &broadcast_channel_sender2,
+                    )
+                    .await;
+            }
+        }
+    });
+
+    let _client_write_task = tokio::task::spawn({
+        let planes1 = planes.clone();
+        let gct1 = cancellation_token.clone();
+        let grct1 = grct.clone();
+        let grct2 = grct.clone();
+        let clients2 = clients.clone();
+        let sessions1 = sessions.clone();
+        let session = hello.session.clone();
+        let broadcast_channel_receiver = broadcast_channel_sender.subscribe();
+        let broadcast_channel_sender1 = broadcast_channel_sender.clone();
+        let output_filter1 = output_filter.clone();
+        let udp_link1 = udp_link.clone();
+        let output_queue1 = output_queue.clone();
+        async move {
+            let r = client_write_handler(
+                client_addr,
+                broadcast_channel_receiver,
+                output_queue1,
+                writer,
+                output_filter1,
+                udp_link1,
+                gct1,
+                grct1,
+            )
+            .await;
+            if let Err(e) = r {
+                grct2.cancel();
+                event!(
+                    Level::WARN,
+                    "RPC Client: {} dropped, due to {}",
+                    client_addr,
+                    e
+                );
+                sessions1
+                    .detach(
+                        &session,
+                        client_addr,
+                        &planes1,
+                        &clients2,
+                        &broadcast_channel_sender1,
+                    )
+                    .await;
+            }
+        }
+    });
+
+    // the pilots tick to keep their planes running, every client calls to stay alive
+    let _tick_task = tokio::spawn({
+        let planes1 = planes.clone();
+        let clients2 = clients.clone();
+        let sessions1 = sessions.clone();
+        let session = hello.session.clone();
+        let broadcast_channel_sender1 = broadcast_channel_sender.clone();
+        let gct1 = cancellation_token.clone();
+        let gct2 = cancellation_token.clone();
+        let grct1 = grct.clone();
+        let grct2 = grct.clone();
+        let grct3 = grct.clone();
+        let tick_notify1 = tick_notify.clone();
+        let alive_notify1 = alive_notify.clone();
+        let run_signal1 = run_signal.clone();
+        // observers only watch, they do not tick
+        let ticking = hello.role.includes(ClientRole::Pilot);
+        async move {
+            let r = tokio::select! {
+                r = tick_handler(
                     client_addr,
                     tick_timeout,
                     run_signal1,
                     tick_notify1,
                     gct1,
                     grct1,
-                )
-                .await;
-                if let Err(e) = r {
-                    grct2.cancel();
-                    event!(
-                        Level::WARN,
-                        "RPC Client: {} dropped, due to {}",
+                ), if ticking => r,
+                r = liveness_handler(client_addr, liveness_timeout, alive_notify1, gct2, grct3) => r,
+            };
+            if let Err(e) = r {
+                grct2.cancel();
+                event!(
+                    Level::WARN,
+                    "RPC Client: {} dropped, due to {}",
+                    client_addr,
+                    e
+                );
+                sessions1
+                    .detach(
+                        &session,
                         client_addr,
-                        e
-                    );
-                    for (id, _sender) in controller1.lock().await.deref().iter() {
-                        let _ = broadcast_channel_sender1.send(ServiceCallResponse {
-                            name: "LostPlane".to_string(),
-                            response: Some(Response::LostPlane(id.to_string())),
-                        });
-                    }
-                    controller1.lock().await.clear();
-                }
+                        &planes1,
+                        &clients2,
+                        &broadcast_channel_sender1,
+                    )
+                    .await;
             }
-        });
+        }
+    });
+}
 
-        tokio::time::sleep(Duration::from_millis(100)).await;
-        tokio::task::yield_now().await;
+/// protocol features implemented by the framed server
+const SERVER_FEATURES: &[Feature] = &[
+    Feature::Compression,
+    Feature::Batching,
+    Feature::Subscription,
+    Feature::Checksum,
+];
+
+/// wait for the `Hello` of a new client and answer it with a `HelloAck`,
+/// only the `features` of the transport are negotiated
+/// the features of the returned hello are narrowed to the negotiated ones,
+/// and its role is the one granted by `auth`, the client joins one of the `rooms`
+/// and gets a session of the room
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn handshake(
+    ip: SocketAddr,
+    timeout: u64,
+    compression_threshold: u32,
+    features: &[Feature],
+    auth: &Authenticator,
+    rooms: &Rooms,
+    client_reader: &mut impl CallReader,
+    client_writer: &mut impl ResponseWriter,
+) -> Result<(Hello, HelloAck, Room)> {
+    let call = tokio::time::timeout(Duration::from_millis(timeout), client_reader.next())
+        .await
+        .map_err(|_| anyhow!("Client {} handshake timeout", ip))?
+        .ok_or_else(|| anyhow!("Client {} closed before handshake", ip))?;
+    let call = match call {
+        Ok(call) => call,
+        Err(e) => {
+            malformed_frame(client_writer, &e).await;
+            return Err(e.into());
+        }
+    };
+    let mut hello = match call.args {
+        Some(Args::Hello(hello)) => hello,
+        _ => {
+            let message = format!("expected `Hello` as the first call, got `{}`", call.name);
+            let err = ServiceCallResponse {
+                name: call.name.clone(),
+                request_id: call.request_id,
+                response: Some(Response::Error(ErrorResponse {
+                    code: ErrorCode::HandshakeRequired,
+                    message: message.clone(),
+                    request: call.name,
+                    request_id: call.request_id,
+                })),
+            };
+            client_writer.send(err).await?;
+            return Err(anyhow!(message));
+        }
+    };
+
+    let mut ack = HelloAck {
+        accepted: true,
+        reason: String::new(),
+        protocol_version: PROTOCOL_VERSION,
+        server_name: env!("CARGO_PKG_NAME").to_string(),
+        server_version: env!("CARGO_PKG_VERSION").to_string(),
+        features: hello
+            .features
+            .iter()
+            .filter(|f| features.contains(f))
+            .copied()
+            .collect(),
+        compression: Compression::None,
+        compression_threshold,
+        role: hello.role,
+        session: String::new(),
+        resumed_planes: Vec::new(),
+        room: String::new(),
+    };
+    let mut room = None;
+    if ack.features.contains(&Feature::Compression) {
+        // the preferred codec of the client, every codec is supported by the server
+        match hello.compressions.iter().find(|c| **c != Compression::None) {
+            Some(compression) => ack.compression = *compression,
+            None => ack.features.retain(|f| *f != Feature::Compression),
+        }
+    }
+    if hello.protocol_version != PROTOCOL_VERSION {
+        ack.accepted = false;
+        ack.reason = format!(
+            "protocol version {} is not supported, server speaks version {}",
+            hello.protocol_version, PROTOCOL_VERSION
+        );
+        ack.features.clear();
+        ack.compression = Compression::None;
+    } else {
+        let joined = auth
+            .authenticate(&hello.token, hello.role)
+            .and_then(|role| {
+                let user = auth.user(&hello.token);
+                let room = rooms.join(&hello.room, hello.create_room, user, role)?;
+                Ok((role, room))
+            });
+        match joined {
+            Ok((role, joined)) => {
+                ack.role = role;
+                ack.room = joined.name.clone();
+                (ack.session, ack.resumed_planes) = joined.sessions.open(&hello.session).await;
+                room = Some(joined);
+            }
+            Err(reason) => {
+                ack.accepted = false;
+                ack.reason = reason;
+                ack.features.clear();
+                ack.compression = Compression::None;
+            }
+        }
     }
+    let response = ServiceCallResponse {
+        name: "Hello".to_string(),
+        request_id: call.request_id,
+        response: Some(Response::Hello(ack.clone())),
+    };
+    client_writer.send(response).await?;
+    let Some(room) = room else {
+        return Err(anyhow!(ack.reason));
+    };
+
+    hello.features = ack.features.clone();
+    hello.role = ack.role;
+    hello.session = ack.session.clone();
+    hello.room = ack.room.clone();
+    // the token is not needed after the handshake
+    hello.token.clear();
+    Ok((hello, ack, room))
 }
 
 #[instrument(skip(
     broadcast_channel_sender,
     viewer,
     global_cancellation_token,
-    group_cancellation_token
+    plane_cancellation_token
 ),level = Level::INFO)]
-async fn viewer_handler(
+pub(crate) async fn viewer_handler(
     id: Uuid,
-    broadcast_channel_sender: broadcast::Sender<ServiceCallResponse>,
+    owner: PlaneOwner,
+    broadcast_channel_sender: broadcast::Sender<Broadcast>,
     mut viewer: OutputReceiver,
     global_cancellation_token: CancellationToken,
-    group_cancellation_token: CancellationToken,
+    plane_cancellation_token: CancellationToken,
 ) -> Result<()> {
     let sender = broadcast_channel_sender.clone();
     let id = id;
     loop {
-        if global_cancellation_token.is_cancelled() || group_cancellation_token.is_cancelled() {
-            break;
+        // a paused plane sends no output, the viewer stops on the cancellation alone
+        tokio::select! {
+            changed = viewer.changed() => changed?,
+            _ = global_cancellation_token.cancelled() => break,
+            _ = plane_cancellation_token.cancelled() => break,
         }
-        viewer.changed().await?;
         let output = viewer.get_and_update();
         let output = ServiceCallResponse {
             name: "Output".to_string(),
+            request_id: 0,
             response: Some(Response::Output(PlaneMessage {
                 id: id.to_string(),
                 time: output.0,
                 output: Some(output.1.clone()),
             })),
         };
-        sender.send(output)?;
+        // the room has no client to receive the output
+        let _ = sender.send(Broadcast {
+            owner: owner.get(),
+            response: output,
+        });
         event!(Level::TRACE, "Received output from viewer: {}", id);
+    }
+    Ok(())
+}
 
-        tokio::task::yield_now().await;
+#[instrument(skip(
+    broadcast_channel_sender,
+    diagnostic,
+    global_cancellation_token,
+    plane_cancellation_token
+),level = Level::INFO)]
+pub(crate) async fn diagnostic_handler(
+    id: Uuid,
+    owner: PlaneOwner,
+    broadcast_channel_sender: broadcast::Sender<Broadcast>,
+    mut diagnostic: DiagnosticReceiver,
+    global_cancellation_token: CancellationToken,
+    plane_cancellation_token: CancellationToken,
+) -> Result<()> {
+    loop {
+        tokio::select! {
+            changed = diagnostic.changed() => changed?,
+            _ = global_cancellation_token.cancelled() => break,
+            _ = plane_cancellation_token.cancelled() => break,
+        }
+        let (time, d) = diagnostic.get_and_update();
+        let response = ServiceCallResponse {
+            name: "Diagnostic".to_string(),
+            request_id: 0,
+            response: Some(Response::Diagnostic(PlaneDiagnostic {
+                id: id.to_string(),
+                time,
+                diagnostic: Some(d),
+            })),
+        };
+        let _ = broadcast_channel_sender.send(Broadcast {
+            owner: owner.get(),
+            response,
+        });
+        event!(Level::TRACE, "Received diagnostic from plane: {}", id);
     }
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
 #[tracing::instrument(skip(
     client_writer,
-    private_channel_receiver,
+    output_queue,
+    output_filter,
+    udp_link,
     global_cancellation_token,
     group_cancellation_token
 ),level = Level::INFO)]
 async fn client_write_handler(
     ip: SocketAddr,
-    mut broadcast_channel_receiver: broadcast::Receiver<ServiceCallResponse>,
-    mut private_channel_receiver: mpsc::Receiver<ServiceCallResponse>,
-    mut client_writer: FramedWrite<OwnedWriteHalf, ResponseFrame>,
+    mut broadcast_channel_receiver: broadcast::Receiver<Broadcast>,
+    output_queue: Arc<OutputQueue>,
+    mut client_writer: impl ResponseWriter,
+    output_filter: Arc<Mutex<OutputFilter>>,
+    udp_link: Arc<Mutex<Option<UdpLink>>>,
     global_cancellation_token: CancellationToken,
     group_cancellation_token: CancellationToken,
 ) -> Result<()> {
-    loop {
-        if global_cancellation_token.is_cancelled() || group_cancellation_token.is_cancelled() {
-            break;
-        }
-
-        // Try receiving from broadcast channel
-        if let Ok(msg) = broadcast_channel_receiver.try_recv() {
-            client_writer.send(msg).await?;
-            event!(Level::DEBUG, "Broadcast client: {} send successfully", ip);
+    let batch_interval = output_filter.lock().await.batch_interval();
+    // the broadcast is drained as fast as it is filled, only the queue of the client grows
+    let intake = async {
+        loop {
+            let msg = tokio::select! {
+                _ = global_cancellation_token.cancelled() => break,
+                _ = group_cancellation_token.cancelled() => break,
+                msg = broadcast_channel_receiver.recv() => msg,
+            };
+            let msg = match msg {
+                Ok(msg) => msg,
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    event!(
+                        Level::WARN,
+                        "Broadcast client: {} lagged, {} messages skipped",
+                        ip,
+                        skipped
+                    );
+                    output_queue.lagged(skipped);
+                    continue;
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            };
+            let msg = filter_broadcast(&mut *output_filter.lock().await, msg);
+            // the outputs of a client with an udp side channel skip the connection
+            let udp_outputs = udp_link.lock().await.clone().filter(|link| link.outputs);
+            match (msg, udp_outputs) {
+                (
+                    Some(ServiceCallResponse {
+                        response: Some(Response::Output(output)),
+                        ..
+                    }),
+                    Some(link),
+                ) => link.send_output(output).await,
+                (Some(msg), _) => output_queue.push(msg)?,
+                (None, _) => {}
+            }
         }
-
-        // Try receiving from private channel
-        if let Ok(msg) = private_channel_receiver.try_recv() {
-            client_writer.send(msg).await?;
-            event!(Level::DEBUG, "Private client: {} send successfully", ip);
+        Ok::<(), anyhow::Error>(())
+    };
+    let write = async {
+        let mut group = Vec::new();
+        // armed by the first output of a group, the group is sent when it fires
+        let flush = tokio::time::sleep(Duration::ZERO);
+        tokio::pin!(flush);
+        loop {
+            tokio::select! {
+                _ = global_cancellation_token.cancelled() => break,
+                _ = group_cancellation_token.cancelled() => break,
+                msg = output_queue.pop() => match (msg, batch_interval) {
+                    (
+                        ServiceCallResponse {
+                            response: Some(Response::Output(output)),
+                            ..
+                        },
+                        Some(batch_interval),
+                    ) => {
+                        if group.is_empty() {
+                            flush
+                                .as_mut()
+                                .reset(tokio::time::Instant::now() + batch_interval);
+                        }
+                        group.push(output);
+                    }
+                    (msg, _) => {
+                        // keep the outputs in order with the other messages
+                        flush_group(&mut client_writer, &mut group).await?;
+                        client_writer.send(msg).await?;
+                        event!(Level::DEBUG, "Client: {} send successfully", ip);
+                    }
+                },
+                _ = &mut flush, if !group.is_empty() => {
+                    flush_group(&mut client_writer, &mut group).await?;
+                }
+            }
         }
-
-        tokio::task::yield_now().await;
+        Ok::<(), anyhow::Error>(())
+    };
+    tokio::try_join!(intake, write)?;
+    // the last replies, such as the error of a malformed frame, are sent before closing
+    while let Some(msg) = output_queue.try_pop() {
+        client_writer.send(msg).await?;
     }
     Ok(())
 }
@@ -255,54 +1029,100 @@ async fn client_write_handler(
     system,
     broadcast_channel_sender,
     client_reader,
-    private_channel_sender,
-    controllers,
+    output_queue,
+    queues,
+    planes,
+    clients,
+    output_filter,
+    udp,
+    udp_link,
     run_signal,
     tick_notify,
+    alive_notify,
     group_cancellation_token,
     global_cancellation_token,
     init_cfg
 ),level = Level::INFO)]
 async fn rpc_handler(
     ip: SocketAddr,
+    role: ClientRole,
     tick_timeout: u64,
     read_rate: u64,
-    mut client_reader: FramedRead<OwnedReadHalf, RequestFrame>,
+    mut client_reader: impl CallReader,
     system: Arc<std::sync::Mutex<System>>,
     init_cfg: PlaneInitCfg,
     controller_buffer: usize,
-    broadcast_channel_sender: broadcast::Sender<ServiceCallResponse>,
-    private_channel_sender: mpsc::Sender<ServiceCallResponse>,
-    controllers: Arc<Mutex<HashMap<String, InputSender>>>,
+    clock_mode: Option<ClockMode>,
+    broadcast_channel_sender: broadcast::Sender<Broadcast>,
+    output_queue: Arc<OutputQueue>,
+    queues: QueueRegistry,
+    planes: Arc<PlaneMap>,
+    clients: ClientRegistry,
+    output_filter: Arc<Mutex<OutputFilter>>,
+    udp: Option<Arc<UdpChannel>>,
+    udp_link: Arc<Mutex<Option<UdpLink>>>,
     run_signal: Signal,
     tick_notify: Arc<Notify>,
+    alive_notify: Arc<Notify>,
     global_cancellation_token: CancellationToken,
     group_cancellation_token: CancellationToken,
 ) -> Result<()> {
-    loop {
-        if global_cancellation_token.is_cancelled() || group_cancellation_token.is_cancelled() {
-            break;
-        }
-
+    'read: loop {
         let mut count = 0;
         while count < read_rate {
             count += 1;
-            let request = client_reader.next().await;
+            let request = tokio::select! {
+                request = client_reader.next() => request,
+                _ = global_cancellation_token.cancelled() => break 'read,
+                _ = group_cancellation_token.cancelled() => break 'read,
+            };
             match request {
                 Some(call) => {
-                    let call = call?;
+                    let call = match call {
+                        Ok(call) => call,
+                        Err(e) => {
+                            // the rest of the stream can not be trusted, explain before closing
+                            send_error(&output_queue, ip, "", 0, ErrorCode::MalformedFrame, &e)?;
+                            return Err(e.into());
+                        }
+                    };
+                    alive_notify.notify_one();
+                    let request_id = call.request_id;
                     event!(
                         Level::INFO,
                         "Client: {ip} request `{request}`",
                         ip = ip,
                         request = call.name.as_str()
                     );
+                    if !role.includes(required_role(&call.name)) {
+                        send_error(
+                            &output_queue,
+                            ip,
+                            &call.name,
+                            request_id,
+                            ErrorCode::PermissionDenied,
+                            format!("`{}` is not allowed for {:?}", call.name, role),
+                        )?;
+                        continue;
+                    }
                     match call.name.as_str() {
                         "GetModelInfos" => {
-                            let model_infos: Vec<_> = system
-                                .lock()
-                                .unwrap()
-                                .get_models()?
+                            let models = system.lock().unwrap().get_models();
+                            let models = match models {
+                                Ok(models) => models,
+                                Err(e) => {
+                                    send_error(
+                                        &output_queue,
+                                        ip,
+                                        "GetModelInfos",
+                                        request_id,
+                                        error_code(&e),
+                                        e,
+                                    )?;
+                                    continue;
+                                }
+                            };
+                            let model_infos: Vec<_> = models
                                 .into_iter()
                                 .map(|m| PluginInfoTuple {
                                     id: m.0.to_string(),
@@ -312,148 +1132,934 @@ async fn rpc_handler(
                                 .collect();
                             let response = ServiceCallResponse {
                                 name: "GetModelInfos".to_string(),
+                                request_id,
                                 response: Some(Response::GetModelInfos(GetModelInfosResponse {
                                     model_infos,
                                 })),
                             };
 
-                            private_channel_sender.send(response).await?;
+                            output_queue.push(response)?;
                         }
-                        "PushPlane" => {
-                            run_signal.red();
+                        "GetModelDetails" => {
+                            let model_id = match call.args {
+                                Some(Args::GetModelDetails(args)) => args.model_id,
+                                _ => {
+                                    send_error(
+                                        &output_queue,
+                                        ip,
+                                        "GetModelDetails",
+                                        request_id,
+                                        ErrorCode::InvalidArgs,
+                                        "Invalid RPC args",
+                                    )?;
+                                    continue;
+                                }
+                            };
+
+                            let details = match Uuid::parse_str(&model_id) {
+                                Ok(id) => system
+                                    .lock()
+                                    .unwrap()
+                                    .get_model_details(id)
+                                    .map_err(|e| (error_code(&e), e.to_string())),
+                                Err(e) => Err((ErrorCode::InvalidId, e.to_string())),
+                            };
+                            let details = match details {
+                                Ok((info, state, constants, control_limit)) => {
+                                    GetModelDetailsResponse {
+                                        model_info: Some(PluginInfoTuple {
+                                            id: model_id,
+                                            info: Some(info),
+                                            state,
+                                        }),
+                                        constants: Some(constants),
+                                        control_limit: Some(control_limit),
+                                    }
+                                }
+                                Err((code, message)) => {
+                                    send_error(
+                                        &output_queue,
+                                        ip,
+                                        "GetModelDetails",
+                                        request_id,
+                                        code,
+                                        message,
+                                    )?;
+                                    continue;
+                                }
+                            };
+                            let response = ServiceCallResponse {
+                                name: "GetModelDetails".to_string(),
+                                request_id,
+                                response: Some(Response::GetModelDetails(details)),
+                            };
+                            output_queue.push(response)?;
+                        }
+                        "ListPlanes" => {
+                            let planes = list_planes(&clients).await;
+                            let response = ServiceCallResponse {
+                                name: "ListPlanes".to_string(),
+                                request_id,
+                                response: Some(Response::ListPlanes(ListPlanesResponse { planes })),
+                            };
+                            output_queue.push(response)?;
+                        }
+                        "GetPlaneState" => {
+                            let plane_id = match call.args {
+                                Some(Args::GetPlaneState(args)) => args.plane_id,
+                                _ => {
+                                    send_error(
+                                        &output_queue,
+                                        ip,
+                                        "GetPlaneState",
+                                        request_id,
+                                        ErrorCode::InvalidArgs,
+                                        "Invalid RPC args",
+                                    )?;
+                                    continue;
+                                }
+                            };
 
+                            let state = match plane_state(&clients, &plane_id).await {
+                                Some(state) => state,
+                                None => {
+                                    send_error(
+                                        &output_queue,
+                                        ip,
+                                        "GetPlaneState",
+                                        request_id,
+                                        ErrorCode::PlaneNotFound,
+                                        format!("Plane {} not found", plane_id),
+                                    )?;
+                                    continue;
+                                }
+                            };
+                            let response = ServiceCallResponse {
+                                name: "GetPlaneState".to_string(),
+                                request_id,
+                                response: Some(Response::GetPlaneState(state)),
+                            };
+                            output_queue.push(response)?;
+                        }
+                        "PushPlane" => {
                             let args = match call.args {
                                 Some(Args::PushPlane(model_id)) => model_id,
                                 _ => {
-                                    let err = ServiceCallResponse {
-                                        name: "PushPlane".to_string(),
-                                        response: Some(Response::Error(
-                                            "Invalid RPC args".to_string(),
-                                        )),
-                                    };
-                                    private_channel_sender.send(err).await?;
+                                    send_error(
+                                        &output_queue,
+                                        ip,
+                                        "PushPlane",
+                                        request_id,
+                                        ErrorCode::InvalidArgs,
+                                        "Invalid RPC args",
+                                    )?;
                                     continue;
                                 }
                             };
 
-                            let (id, viewer, controller, _handler, _trim_output) =
-                                tokio::task::spawn_blocking({
-                                    let system = system.clone();
-                                    let group_cancellation_token = group_cancellation_token.clone();
-                                    move || {
-                                        system.lock().unwrap().push_plane(
-                                            Uuid::parse_str(&args.model_id).unwrap(),
-                                            controller_buffer,
-                                            args.plane_init_cfg
-                                                .map_or_else(|| init_cfg, |c| c.into()),
-                                            group_cancellation_token,
-                                        )
-                                    }
-                                })
-                                .await??;
+                            let model_id = match Uuid::parse_str(&args.model_id) {
+                                Ok(model_id) => model_id,
+                                Err(e) => {
+                                    send_error(
+                                        &output_queue,
+                                        ip,
+                                        "PushPlane",
+                                        request_id,
+                                        ErrorCode::InvalidId,
+                                        e,
+                                    )?;
+                                    continue;
+                                }
+                            };
+
+                            run_signal.red();
+                            let plane_cancellation_token = CancellationToken::new();
+                            let plane = tokio::task::spawn_blocking({
+                                let system = system.clone();
+                                let plane_cancellation_token = plane_cancellation_token.clone();
+                                move || {
+                                    system.lock().unwrap().push_plane(
+                                        model_id,
+                                        controller_buffer,
+                                        args.plane_init_cfg.map_or_else(|| init_cfg, |c| c.into()),
+                                        clock_mode,
+                                        plane_cancellation_token,
+                                    )
+                                }
+                            })
+                            .await;
+                            let plane = match plane {
+                                Ok(Ok(plane)) => plane,
+                                Ok(Err(e)) => {
+                                    run_signal.green();
+                                    send_error(
+                                        &output_queue,
+                                        ip,
+                                        "PushPlane",
+                                        request_id,
+                                        error_code(&e),
+                                        e,
+                                    )?;
+                                    continue;
+                                }
+                                Err(e) => {
+                                    run_signal.green();
+                                    send_error(
+                                        &output_queue,
+                                        ip,
+                                        "PushPlane",
+                                        request_id,
+                                        ErrorCode::Internal,
+                                        e,
+                                    )?;
+                                    continue;
+                                }
+                            };
+                            let (
+                                id,
+                                viewer,
+                                controller,
+                                _handler,
+                                trim_output,
+                                diagnostic,
+                                commander,
+                            ) = plane;
+                            let model_id = model_id.to_string();
+                            let owner = PlaneOwner::new(ip);
 
-                            controllers.lock().await.insert(id.to_string(), controller);
+                            planes.lock().await.insert(
+                                id.to_string(),
+                                PlaneHandle {
+                                    model_id,
+                                    status: PlaneStatus::Running,
+                                    viewer: viewer.clone(),
+                                    controller,
+                                    commander,
+                                    owner: owner.clone(),
+                                    pilots: HashSet::new(),
+                                    cancellation_token: plane_cancellation_token.clone(),
+                                },
+                            );
 
                             tokio::task::spawn({
                                 let gct1 = global_cancellation_token.clone();
-                                let grct1 = group_cancellation_token.clone();
-                                let grct2 = group_cancellation_token.clone();
+                                let pct1 = plane_cancellation_token.clone();
                                 let broadcast_channel_sender1 = broadcast_channel_sender.clone();
                                 let broadcast_channel_sender2 = broadcast_channel_sender.clone();
-                                let controllers = controllers.clone();
+                                let owner = owner.clone();
+                                let clients = clients.clone();
                                 async move {
                                     let rr = viewer_handler(
                                         id,
+                                        owner,
                                         broadcast_channel_sender1,
                                         viewer.clone(),
                                         gct1,
-                                        grct1,
+                                        pct1,
                                     )
                                     .await;
                                     if let Err(e) = rr {
-                                        grct2.cancel();
-                                        event!(
-                                            Level::WARN,
-                                            "RPC Client: {} dropped, due to {}",
-                                            ip,
-                                            e
-                                        );
+                                        // the plane has been removed by `RemovePlane`, or its owner is released
+                                        let Some((owner, plane)) =
+                                            take_plane(&clients, &id.to_string()).await
+                                        else {
+                                            return;
+                                        };
+                                        plane.cancellation_token.cancel();
+                                        event!(Level::WARN, "Plane {} lost, due to {}", id, e);
                                         let response = ServiceCallResponse {
                                             name: "LostPlane".to_string(),
+                                            request_id: 0,
                                             response: Some(Response::LostPlane(id.to_string())),
                                         };
-                                        controllers.lock().await.remove(&id.to_string());
-                                        let _ = broadcast_channel_sender2.send(response);
+                                        let _ = broadcast_channel_sender2
+                                            .send(Broadcast { owner, response });
                                     }
                                 }
                             });
 
+                            if let Some(diagnostic) = diagnostic {
+                                tokio::task::spawn({
+                                    let gct1 = global_cancellation_token.clone();
+                                    let pct1 = plane_cancellation_token.clone();
+                                    let broadcast_channel_sender1 =
+                                        broadcast_channel_sender.clone();
+                                    async move {
+                                        let rr = diagnostic_handler(
+                                            id,
+                                            owner,
+                                            broadcast_channel_sender1,
+                                            diagnostic,
+                                            gct1,
+                                            pct1,
+                                        )
+                                        .await;
+                                        if let Err(e) = rr {
+                                            event!(
+                                                Level::WARN,
+                                                "Diagnostic of plane {} stopped, due to {}",
+                                                id,
+                                                e
+                                            );
+                                        }
+                                    }
+                                });
+                            }
+
                             let response = ServiceCallResponse {
                                 name: "PushPlane".to_string(),
+                                request_id,
                                 response: Some(Response::PushPlane(PushPlaneResponse {
                                     plane_id: id.to_string(),
+                                    trim_output: Some(trim_output),
                                 })),
                             };
-                            private_channel_sender.send(response).await?;
+                            output_queue.push(response)?;
                             tokio::time::sleep(Duration::from_millis(10)).await;
 
                             let response = ServiceCallResponse {
                                 name: "NewPlane".to_string(),
+                                request_id: 0,
                                 response: Some(Response::NewPlane(id.to_string())),
                             };
-                            broadcast_channel_sender.send(response)?;
+                            let _ = broadcast_channel_sender.send(Broadcast {
+                                owner: ip,
+                                response,
+                            });
 
                             run_signal.green();
                         }
+                        "Trim" => {
+                            let args = match call.args {
+                                Some(Args::Trim(args)) => args,
+                                _ => {
+                                    send_error(
+                                        &output_queue,
+                                        ip,
+                                        "Trim",
+                                        request_id,
+                                        ErrorCode::InvalidArgs,
+                                        "Invalid RPC args",
+                                    )?;
+                                    continue;
+                                }
+                            };
+
+                            let model_id = args.model_id.clone();
+                            let trim_output = match Uuid::parse_str(&args.model_id) {
+                                Ok(id) => {
+                                    run_signal.red();
+                                    let trim_output = tokio::task::spawn_blocking({
+                                        let system = system.clone();
+                                        let init_cfg = args.plane_init_cfg.unwrap_or(init_cfg);
+                                        move || system.lock().unwrap().trim(id, init_cfg)
+                                    })
+                                    .await;
+                                    run_signal.green();
+                                    match trim_output {
+                                        Ok(Ok(trim_output)) => Ok(trim_output),
+                                        Ok(Err(e)) => Err((error_code(&e), e.to_string())),
+                                        Err(e) => Err((ErrorCode::Internal, e.to_string())),
+                                    }
+                                }
+                                Err(e) => Err((ErrorCode::InvalidId, e.to_string())),
+                            };
+
+                            let trim_output = match trim_output {
+                                Ok(trim_output) => trim_output,
+                                Err((code, message)) => {
+                                    send_error(
+                                        &output_queue,
+                                        ip,
+                                        "Trim",
+                                        request_id,
+                                        code,
+                                        message,
+                                    )?;
+                                    continue;
+                                }
+                            };
+                            let response = ServiceCallResponse {
+                                name: "Trim".to_string(),
+                                request_id,
+                                response: Some(Response::Trim(TrimResponse {
+                                    model_id,
+                                    trim_output: Some(trim_output),
+                                })),
+                            };
+                            output_queue.push(response)?;
+                        }
                         "SendControl" => {
                             let control = match call.args {
                                 Some(Args::SendControl(control)) => control,
                                 _ => {
-                                    let err = ServiceCallResponse {
-                                        name: "SendControl".to_string(),
-                                        response: Some(Response::Error(
-                                            "Invalid RPC args".to_string(),
-                                        )),
+                                    send_error(
+                                        &output_queue,
+                                        ip,
+                                        "SendControl",
+                                        request_id,
+                                        ErrorCode::InvalidArgs,
+                                        "Invalid RPC args",
+                                    )?;
+                                    continue;
+                                }
+                            };
+                            let controller =
+                                plane_controller(&clients, &planes, ip, role, &control.plane_id)
+                                    .await;
+                            let controller = match controller {
+                                Ok(controller) => controller,
+                                Err((code, message)) => {
+                                    send_error(
+                                        &output_queue,
+                                        ip,
+                                        "SendControl",
+                                        request_id,
+                                        code,
+                                        message,
+                                    )?;
+                                    continue;
+                                }
+                            };
+                            let plane_id = control.plane_id;
+                            let control = control.control.unwrap_or_default();
+                            if let Err(e) = controller.send(&control).await {
+                                event!(Level::WARN, "Plane {} control failed, {}", plane_id, e);
+                            }
+                        }
+                        "RemovePlane" | "PausePlane" | "ResumePlane" | "ResetPlane"
+                        | "SetTimeScale" => {
+                            let name = call.name.clone();
+                            let plane_ids = match plane_command_args(&name, call.args) {
+                                Some((plane_id, command)) => {
+                                    let target = plane_id.clone();
+                                    // without an id the command is sent to the own planes only,
+                                    // a pilot may not remove the plane of another client
+                                    let owner = match &plane_id {
+                                        Some(plane_id) => {
+                                            let owner_only = command == PlaneCommand::Remove;
+                                            authorize_plane(
+                                                &clients, ip, role, plane_id, owner_only,
+                                            )
+                                            .await
+                                        }
+                                        None => Ok((ip, planes.clone())),
+                                    };
+                                    let plane_ids = match owner {
+                                        Ok((owner, planes)) => {
+                                            plane_command_handler(&planes, plane_id, command)
+                                                .await
+                                                .map(|plane_ids| (owner, plane_ids))
+                                                .ok_or_else(|| {
+                                                    (
+                                                        ErrorCode::PlaneNotFound,
+                                                        format!(
+                                                            "Plane {} not found",
+                                                            target.unwrap_or_default()
+                                                        ),
+                                                    )
+                                                })
+                                        }
+                                        Err(e) => Err(e),
                                     };
-                                    private_channel_sender.send(err).await?;
-                                    event!(Level::WARN, "Invalid RPC args from client: {}", ip);
+                                    match plane_ids {
+                                        Ok((owner, plane_ids)) => {
+                                            for plane_id in &plane_ids {
+                                                let response =
+                                                    plane_event(plane_id.clone(), command);
+                                                let _ = broadcast_channel_sender
+                                                    .send(Broadcast { owner, response });
+                                            }
+                                            plane_ids
+                                        }
+                                        Err((code, message)) => {
+                                            send_error(
+                                                &output_queue,
+                                                ip,
+                                                &name,
+                                                request_id,
+                                                code,
+                                                message,
+                                            )?;
+                                            continue;
+                                        }
+                                    }
+                                }
+                                None => {
+                                    send_error(
+                                        &output_queue,
+                                        ip,
+                                        &name,
+                                        request_id,
+                                        ErrorCode::InvalidArgs,
+                                        "Invalid RPC args",
+                                    )?;
                                     continue;
                                 }
                             };
-                            if let Some(ref controller) =
-                                controllers.lock().await.get(&control.plane_id)
-                            {
-                                controller
-                                    .send(&control.control.unwrap_or_default())
-                                    .await?;
+
+                            let plane_response = PlaneResponse { plane_ids };
+                            let response = match name.as_str() {
+                                "RemovePlane" => Response::RemovePlane(plane_response),
+                                "PausePlane" => Response::PausePlane(plane_response),
+                                "ResumePlane" => Response::ResumePlane(plane_response),
+                                "ResetPlane" => Response::ResetPlane(plane_response),
+                                _ => Response::SetTimeScale(plane_response),
+                            };
+                            let response = ServiceCallResponse {
+                                name,
+                                request_id,
+                                response: Some(response),
+                            };
+                            output_queue.push(response)?;
+                        }
+                        "Subscribe" | "Unsubscribe" => {
+                            let name = call.name.clone();
+                            let r = match (name.as_str(), call.args) {
+                                ("Subscribe", Some(Args::Subscribe(subscription))) => {
+                                    output_filter.lock().await.subscribe(subscription)
+                                }
+                                ("Unsubscribe", Some(Args::Unsubscribe(subscription))) => {
+                                    output_filter.lock().await.unsubscribe(subscription)
+                                }
+                                _ => Err("Invalid RPC args".to_string()),
+                            };
+                            if let Err(e) = r {
+                                send_error(
+                                    &output_queue,
+                                    ip,
+                                    &name,
+                                    request_id,
+                                    ErrorCode::InvalidArgs,
+                                    e,
+                                )?;
+                                continue;
                             }
+
+                            let subscription = output_filter.lock().await.subscription();
+                            let response = match name.as_str() {
+                                "Subscribe" => Response::Subscribe(subscription),
+                                _ => Response::Unsubscribe(subscription),
+                            };
+                            let response = ServiceCallResponse {
+                                name,
+                                request_id,
+                                response: Some(response),
+                            };
+                            output_queue.push(response)?;
+                        }
+                        "OpenUdp" => {
+                            let request = match call.args {
+                                Some(Args::OpenUdp(request)) => request,
+                                _ => {
+                                    send_error(
+                                        &output_queue,
+                                        ip,
+                                        "OpenUdp",
+                                        request_id,
+                                        ErrorCode::InvalidArgs,
+                                        "Invalid RPC args",
+                                    )?;
+                                    continue;
+                                }
+                            };
+                            let Some(udp) = udp.clone() else {
+                                send_error(
+                                    &output_queue,
+                                    ip,
+                                    "OpenUdp",
+                                    request_id,
+                                    ErrorCode::InvalidRequest,
+                                    "UDP side channel is disabled",
+                                )?;
+                                continue;
+                            };
+                            let token = udp.open(ip, role, &planes, &clients).await;
+                            let port = udp.port();
+                            *udp_link.lock().await = Some(UdpLink {
+                                channel: udp,
+                                token,
+                                outputs: request.outputs,
+                            });
+                            let response = ServiceCallResponse {
+                                name: "OpenUdp".to_string(),
+                                request_id,
+                                response: Some(Response::OpenUdp(OpenUdpResponse { port, token })),
+                            };
+                            output_queue.push(response)?;
+                        }
+                        "TransferPlane" | "SharePlane" => {
+                            let name = call.name.clone();
+                            let r = match (name.as_str(), call.args) {
+                                ("TransferPlane", Some(Args::TransferPlane(request))) => {
+                                    transfer_plane(
+                                        &clients,
+                                        ip,
+                                        role,
+                                        &request.plane_id,
+                                        &request.owner,
+                                    )
+                                    .await
+                                }
+                                ("SharePlane", Some(Args::SharePlane(request))) => {
+                                    share_plane(
+                                        &clients,
+                                        ip,
+                                        role,
+                                        &request.plane_id,
+                                        &request.pilot,
+                                        request.revoke,
+                                    )
+                                    .await
+                                }
+                                _ => Err((ErrorCode::InvalidArgs, "Invalid RPC args".to_string())),
+                            };
+                            let info = match r {
+                                Ok(info) => info,
+                                Err((code, message)) => {
+                                    send_error(
+                                        &output_queue,
+                                        ip,
+                                        &name,
+                                        request_id,
+                                        code,
+                                        message,
+                                    )?;
+                                    continue;
+                                }
+                            };
+                            let response = match name.as_str() {
+                                "TransferPlane" => {
+                                    let _ = broadcast_channel_sender.send(Broadcast {
+                                        owner: info.owner.parse().unwrap_or(ip),
+                                        response: owner_changed(info.plane_id.clone()),
+                                    });
+                                    Response::TransferPlane(info)
+                                }
+                                _ => Response::SharePlane(info),
+                            };
+                            let response = ServiceCallResponse {
+                                name,
+                                request_id,
+                                response: Some(response),
+                            };
+                            output_queue.push(response)?;
                         }
                         "Tick" => {
                             tick_notify.notify_one();
                         }
+                        "Ping" => {
+                            if request_id != 0 {
+                                let response = ServiceCallResponse {
+                                    name: "Ping".to_string(),
+                                    request_id,
+                                    response: Some(Response::Pong),
+                                };
+                                output_queue.push(response)?;
+                            }
+                        }
+                        "GetStatus" => {
+                            // admins see every client of the room, the others only themselves
+                            let statuses = if role.includes(ClientRole::Admin) {
+                                queues
+                                    .lock()
+                                    .unwrap()
+                                    .iter()
+                                    .filter_map(|(addr, queue)| {
+                                        queue.upgrade().map(|queue| queue.status(*addr))
+                                    })
+                                    .collect()
+                            } else {
+                                vec![output_queue.status(ip)]
+                            };
+                            let response = ServiceCallResponse {
+                                name: "GetStatus".to_string(),
+                                request_id,
+                                response: Some(Response::GetStatus(GetStatusResponse {
+                                    clients: statuses,
+                                })),
+                            };
+                            output_queue.push(response)?;
+                        }
                         "Disconnect" => {
                             group_cancellation_token.cancel();
+                            // the planes of a client leaving on purpose are not kept
+                            release_client(&clients, ip, &planes, &broadcast_channel_sender).await;
                             return Err(anyhow!("Client {} request `Disconnect`", ip));
                         }
                         other => {
-                            let err = ServiceCallResponse {
-                                name: other.to_string(),
-                                response: Some(Response::Error(format!(
-                                    "Invalid RPC command: {}",
-                                    other
-                                ))),
-                            };
-                            private_channel_sender.send(err).await?;
-                            event!(Level::WARN, "Invalid RPC command from client: {}", ip);
+                            send_error(
+                                &output_queue,
+                                ip,
+                                other,
+                                request_id,
+                                ErrorCode::InvalidRequest,
+                                format!("Invalid RPC command: {}", other),
+                            )?;
                         }
                     }
                 }
                 None => {
-                    break;
+                    return Err(anyhow!("Client {} closed the connection", ip));
                 }
             }
         }
-        tokio::task::yield_now().await;
+    }
+    Ok(())
+}
+
+/// match the lifecycle command of a service call
+pub(crate) fn plane_command_args(
+    name: &str,
+    args: Option<Args>,
+) -> Option<(Option<String>, PlaneCommand)> {
+    match (name, args) {
+        ("RemovePlane", Some(Args::RemovePlane(r))) => Some((r.plane_id, PlaneCommand::Remove)),
+        ("PausePlane", Some(Args::PausePlane(r))) => Some((r.plane_id, PlaneCommand::Pause)),
+        ("ResumePlane", Some(Args::ResumePlane(r))) => Some((r.plane_id, PlaneCommand::Resume)),
+        ("ResetPlane", Some(Args::ResetPlane(r))) => Some((r.plane_id, PlaneCommand::Reset)),
+        ("SetTimeScale", Some(Args::SetTimeScale(r))) => PlaneCommand::set_time_scale(r.time_scale)
+            .ok()
+            .map(|command| (r.plane_id, command)),
+        _ => None,
+    }
+}
+
+/// collect the planes of all connected clients
+pub(crate) async fn list_planes(clients: &ClientRegistry) -> Vec<PlaneInfo> {
+    let mut infos = Vec::new();
+    for planes in clients.lock().await.values() {
+        let planes = match planes.upgrade() {
+            Some(planes) => planes,
+            None => continue,
+        };
+        for (id, plane) in planes.lock().await.iter() {
+            infos.push(plane.info(id));
+        }
+    }
+    infos
+}
+
+/// latest output of a plane owned by any client
+pub(crate) async fn plane_state(clients: &ClientRegistry, plane_id: &str) -> Option<PlaneMessage> {
+    for planes in clients.lock().await.values() {
+        let planes = match planes.upgrade() {
+            Some(planes) => planes,
+            None => continue,
+        };
+        let state = planes
+            .lock()
+            .await
+            .get(plane_id)
+            .map(|plane| plane.viewer.get());
+        if let Some((time, output)) = state {
+            return Some(PlaneMessage {
+                id: plane_id.to_string(),
+                time,
+                output: Some(output),
+            });
+        }
+    }
+    None
+}
+
+/// send a lifecycle command to one plane or to all planes of the client
+/// return the id of planes which received the command, `None` if the plane is not found
+pub(crate) async fn plane_command_handler(
+    planes: &PlaneMap,
+    plane_id: Option<String>,
+    command: PlaneCommand,
+) -> Option<Vec<String>> {
+    let mut planes = planes.lock().await;
+    let targets: Vec<String> = match plane_id {
+        Some(plane_id) => {
+            if !planes.contains_key(&plane_id) {
+                return None;
+            }
+            vec![plane_id]
+        }
+        None => planes.keys().cloned().collect(),
+    };
+
+    let mut plane_ids = Vec::with_capacity(targets.len());
+    for plane_id in targets {
+        let plane = match command {
+            PlaneCommand::Remove => planes.remove(&plane_id),
+            _ => planes.get(&plane_id).cloned(),
+        };
+        if let Some(plane) = plane {
+            match plane.commander.send(command).await {
+                Ok(()) => plane_ids.push(plane_id.clone()),
+                Err(e) => {
+                    event!(Level::WARN, "Plane {} command failed, {}", plane_id, e);
+                    continue;
+                }
+            }
+        }
+        let status = match command {
+            PlaneCommand::Pause => PlaneStatus::Paused,
+            PlaneCommand::Resume => PlaneStatus::Running,
+            _ => continue,
+        };
+        if let Some(plane) = planes.get_mut(&plane_id) {
+            plane.status = status;
+        }
+    }
+    Some(plane_ids)
+}
+
+/// send the grouped outputs in one frame
+async fn flush_group(
+    client_writer: &mut impl ResponseWriter,
+    group: &mut Vec<PlaneMessage>,
+) -> Result<()> {
+    if group.is_empty() {
+        return Ok(());
+    }
+    let response = ServiceCallResponse {
+        name: "OutputGroup".to_string(),
+        request_id: 0,
+        response: Some(Response::OutputGroup(PlaneMessageGroup {
+            msg: std::mem::take(group),
+        })),
+    };
+    client_writer.send(response).await?;
+    Ok(())
+}
+
+/// apply the subscription of a client to a broadcast message before it is encoded
+pub(crate) fn filter_broadcast(
+    filter: &mut OutputFilter,
+    msg: Broadcast,
+) -> Option<ServiceCallResponse> {
+    let Broadcast {
+        owner,
+        mut response,
+    } = msg;
+    match response.response.as_mut() {
+        Some(Response::Output(output)) => {
+            if !filter.contains(&owner, &output.id) || !filter.sample(&output.id) {
+                return None;
+            }
+            if let Some(output) = output.output.as_mut() {
+                filter.mask(output);
+            }
+        }
+        Some(Response::Diagnostic(diagnostic)) if !filter.contains(&owner, &diagnostic.id) => {
+            return None;
+        }
+        Some(Response::LostPlane(plane_id)) => filter.remove_plane(plane_id),
+        Some(Response::PlaneEvent(event)) if event.kind == PlaneEventKind::Removed => {
+            filter.remove_plane(&event.plane_id)
+        }
+        _ => {}
+    }
+    Some(response)
+}
+
+/// broadcast message of a lifecycle command
+pub(crate) fn plane_event(plane_id: String, command: PlaneCommand) -> ServiceCallResponse {
+    let (kind, time_scale) = match command {
+        PlaneCommand::Remove => (PlaneEventKind::Removed, None),
+        PlaneCommand::Pause => (PlaneEventKind::Paused, None),
+        PlaneCommand::Resume => (PlaneEventKind::Resumed, None),
+        PlaneCommand::Reset => (PlaneEventKind::Reset, None),
+        PlaneCommand::SetTimeScale(s) => (PlaneEventKind::TimeScaleChanged, Some(s)),
+    };
+    ServiceCallResponse {
+        name: "PlaneEvent".to_string(),
+        request_id: 0,
+        response: Some(Response::PlaneEvent(PlaneEvent {
+            plane_id,
+            kind,
+            time_scale,
+        })),
+    }
+}
+
+/// reply a failed request, the connection and planes of the client are kept
+fn send_error(
+    output_queue: &OutputQueue,
+    ip: SocketAddr,
+    request: &str,
+    request_id: u64,
+    code: ErrorCode,
+    message: impl ToString,
+) -> Result<()> {
+    let message = message.to_string();
+    event!(
+        Level::WARN,
+        "{} from client: {} failed, {}",
+        request,
+        ip,
+        message
+    );
+    let err = ServiceCallResponse {
+        name: request.to_string(),
+        request_id,
+        response: Some(Response::Error(ErrorResponse {
+            code,
+            message,
+            request: request.to_string(),
+            request_id,
+        })),
+    };
+    output_queue.push(err)
+}
+
+/// tell the client why its frame is rejected, the connection is closed anyway
+async fn malformed_frame(client_writer: &mut impl ResponseWriter, e: &FrError) {
+    let err = ServiceCallResponse {
+        name: String::new(),
+        request_id: 0,
+        response: Some(Response::Error(ErrorResponse {
+            code: ErrorCode::MalformedFrame,
+            message: e.to_string(),
+            request: String::new(),
+            request_id: 0,
+        })),
+    };
+    let _ = client_writer.send(err).await;
+}
+
+/// error code of a failed system call
+pub(crate) fn error_code(e: &SysError) -> ErrorCode {
+    match e {
+        SysError::ModelNotAvailable => ErrorCode::ModelNotAvailable,
+        SysError::Fr(FrError::Cfg(_)) => ErrorCode::InvalidArgs,
+        SysError::Fr(FrError::Core(_)) => ErrorCode::TrimFailed,
+        _ => ErrorCode::Internal,
+    }
+}
+
+#[instrument(skip(
+    alive_notify,
+    group_cancellation_token,
+    global_cancellation_token,
+),level = Level::INFO)]
+async fn liveness_handler(
+    ip: SocketAddr,
+    liveness_timeout: u64,
+    alive_notify: Arc<Notify>,
+    global_cancellation_token: CancellationToken,
+    group_cancellation_token: CancellationToken,
+) -> Result<()> {
+    if liveness_timeout == 0 {
+        tokio::select! {
+            _ = global_cancellation_token.cancelled() => {}
+            _ = group_cancellation_token.cancelled() => {}
+        }
+        return Ok(());
+    }
+    loop {
+        tokio::select! {
+            alive = tokio::time::timeout(
+                Duration::from_millis(liveness_timeout),
+                alive_notify.notified(),
+            ) => alive.map_err(|_| anyhow!("Client {} liveness timeout", ip))?,
+            _ = global_cancellation_token.cancelled() => break,
+            _ = group_cancellation_token.cancelled() => break,
+        }
     }
     Ok(())
 }
@@ -473,12 +2079,14 @@ async fn tick_handler(
     group_cancellation_token: CancellationToken,
 ) -> Result<()> {
     loop {
-        if global_cancellation_token.is_cancelled() || group_cancellation_token.is_cancelled() {
-            break;
-        }
-        let err = tokio::time::timeout(Duration::from_millis(tick_timeout), tick_notify.notified())
-            .await
-            .map_err(|_| anyhow!("Client {} tick timeout", ip));
+        let err = tokio::select! {
+            tick = tokio::time::timeout(
+                Duration::from_millis(tick_timeout),
+                tick_notify.notified(),
+            ) => tick.map_err(|_| anyhow!("Client {} tick timeout", ip)),
+            _ = global_cancellation_token.cancelled() => break,
+            _ = group_cancellation_token.cancelled() => break,
+        };
         if run_signal.available() {
             return err;
         }
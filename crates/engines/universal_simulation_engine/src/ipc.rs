@@ -0,0 +1,144 @@
+use crate::handler::{list_planes, Broadcast, ClientRegistry};
+#[cfg(unix)]
+use crate::{
+    handler::{framed_client_handler, ServerCfg},
+    system::System,
+    udp::UdpChannel,
+};
+use anyhow::Result;
+use fly_ruler_codec::{PlaneEventKind, Response, ShmWriter};
+#[cfg(unix)]
+use fly_ruler_core::core::PlaneInitCfg;
+use fly_ruler_utils::CancellationToken;
+use std::collections::HashSet;
+#[cfg(unix)]
+use std::{net::SocketAddr, sync::Arc};
+use tokio::sync::broadcast::{self, error::RecvError};
+use tracing::{event, Level};
+use uuid::Uuid;
+
+/// accept local clients on the unix domain socket `path`, they speak the framed protocol of the tcp clients
+#[cfg(unix)]
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn uds_handler(
+    path: String,
+    server_cfg: ServerCfg,
+    init_cfg: PlaneInitCfg,
+    system: Arc<std::sync::Mutex<System>>,
+    controller_buffer: usize,
+    udp: Option<Arc<UdpChannel>>,
+    cancellation_token: CancellationToken,
+) -> Result<()> {
+    use std::os::unix::fs::FileTypeExt;
+    use tokio::net::UnixListener;
+
+    // the socket of a previous run is left behind, other files are kept
+    if let Ok(metadata) = std::fs::symlink_metadata(&path) {
+        if metadata.file_type().is_socket() {
+            std::fs::remove_file(&path)?;
+        }
+    }
+    let listener = UnixListener::bind(&path)?;
+    event!(Level::INFO, "Unix socket server started on {}", path);
+
+    let mut next = 0u16;
+    loop {
+        let (stream, _) = tokio::select! {
+            accepted = listener.accept() => accepted?,
+            _ = cancellation_token.cancelled() => break,
+        };
+        // local clients have no address, a unique one names them in the registry and the logs
+        let client_addr = loop {
+            next = next.wrapping_add(1);
+            let addr = SocketAddr::from(([0, 0, 0, 0], next));
+            if !server_cfg.rooms.is_connected(&addr).await {
+                break addr;
+            }
+        };
+        event!(
+            Level::INFO,
+            "Accepted unix socket connection as {}",
+            client_addr
+        );
+
+        let (reader, writer) = stream.into_split();
+        tokio::spawn(framed_client_handler(
+            client_addr,
+            reader,
+            writer,
+            server_cfg.clone(),
+            init_cfg,
+            system.clone(),
+            controller_buffer,
+            udp.clone(),
+            cancellation_token.clone(),
+        ));
+    }
+    let _ = std::fs::remove_file(&path);
+    Ok(())
+}
+
+/// publish the latest output of every plane in the shared memory file `path`,
+/// local clients read them with `ShmReader` without a connection
+pub(crate) async fn shm_handler(
+    path: String,
+    capacity: u32,
+    mut receiver: broadcast::Receiver<Broadcast>,
+    clients: ClientRegistry,
+    cancellation_token: CancellationToken,
+) -> Result<()> {
+    let mut writer = ShmWriter::create(&path, capacity)?;
+    event!(Level::INFO, "Shared memory outputs started on {}", path);
+
+    loop {
+        let broadcast = tokio::select! {
+            received = receiver.recv() => received,
+            _ = cancellation_token.cancelled() => break,
+        };
+        let response = match broadcast {
+            Ok(broadcast) => broadcast.response.response,
+            // the skipped removals are found against the live planes, only the latest output matters
+            Err(RecvError::Lagged(skipped)) => {
+                event!(
+                    Level::DEBUG,
+                    "Shared memory outputs lagged, {} messages skipped",
+                    skipped
+                );
+                let live: HashSet<String> = list_planes(&clients)
+                    .await
+                    .into_iter()
+                    .map(|plane| plane.plane_id)
+                    .collect();
+                writer.retain(|id| live.contains(&id.to_string()));
+                continue;
+            }
+            Err(RecvError::Closed) => break,
+        };
+        match response {
+            Some(Response::Output(msg)) => {
+                let (Ok(id), Some(output)) = (Uuid::parse_str(&msg.id), msg.output) else {
+                    continue;
+                };
+                if !writer.write(&id, msg.time, &output) {
+                    event!(
+                        Level::DEBUG,
+                        "Plane {} output dropped, shared memory is full",
+                        id
+                    );
+                }
+            }
+            Some(Response::LostPlane(id)) => {
+                if let Ok(id) = Uuid::parse_str(&id) {
+                    writer.remove(&id);
+                }
+            }
+            Some(Response::PlaneEvent(e)) if e.kind == PlaneEventKind::Removed => {
+                if let Ok(id) = Uuid::parse_str(&e.plane_id) {
+                    writer.remove(&id);
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
@@ -1,5 +1,16 @@
 pub mod args;
+pub mod auth;
+pub mod grpc;
 pub mod handler;
+pub mod ipc;
 pub mod lua;
 pub mod manager;
+pub mod ownership;
+pub mod queue;
+pub mod room;
+pub mod session;
+pub mod subscription;
 pub mod system;
+pub mod tls;
+pub mod udp;
+pub mod websocket;
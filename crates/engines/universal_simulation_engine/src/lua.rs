@@ -1,7 +1,13 @@
+use crate::{
+    auth::Authenticator,
+    queue::Backpressure,
+    room::{RoomCfg, Rooms},
+};
+use fly_ruler_codec::DEFAULT_MAX_FRAME_SIZE;
 use fly_ruler_core::core::{CoreInitCfg, PlaneInitCfg};
 use mlua::prelude::*;
 use serde::de::DeserializeOwned;
-use std::path::Path;
+use std::{collections::HashMap, path::Path, time::Duration};
 
 pub struct LuaManager {
     lua: Lua,
@@ -77,12 +83,117 @@ impl LuaManager {
         tick_timeout
     }
 
+    pub fn liveness_timeout(&self) -> u64 {
+        let liveness_timeout: Option<u64> = self.load_table_data("server", "liveness_timeout");
+        liveness_timeout.unwrap_or(0)
+    }
+
     pub fn read_rate(&self) -> u64 {
         let mut read_rate: u64 = self.load_table_data("server", "read_rate");
         read_rate = read_rate.max(1);
         read_rate
     }
 
+    pub fn batch_interval(&self) -> u64 {
+        let batch_interval: Option<u64> = self.load_table_data("server", "batch_interval");
+        batch_interval.unwrap_or(0)
+    }
+
+    pub fn output_queue(&self) -> usize {
+        let output_queue: Option<usize> = self.load_table_data("server", "output_queue");
+        output_queue.unwrap_or(256)
+    }
+
+    pub fn backpressure(&self) -> Backpressure {
+        let backpressure: Option<String> = self.load_table_data("server", "backpressure");
+        match backpressure {
+            Some(backpressure) => backpressure
+                .parse()
+                .map_err(|e| {
+                    panic!("{}", e);
+                })
+                .unwrap(),
+            None => Backpressure::default(),
+        }
+    }
+
+    pub fn compression_threshold(&self) -> u32 {
+        let compression_threshold: Option<u32> =
+            self.load_table_data("server", "compression_threshold");
+        compression_threshold.unwrap_or(1024)
+    }
+
+    pub fn max_frame_size(&self) -> usize {
+        let max_frame_size: Option<usize> = self.load_table_data("server", "max_frame_size");
+        max_frame_size.unwrap_or(DEFAULT_MAX_FRAME_SIZE)
+    }
+
+    pub fn grpc_addr(&self) -> Option<String> {
+        let grpc_addr: Option<String> = self.load_table_data("server", "grpc_addr");
+        grpc_addr
+    }
+
+    pub fn ws_addr(&self) -> Option<String> {
+        let ws_addr: Option<String> = self.load_table_data("server", "ws_addr");
+        ws_addr
+    }
+
+    pub fn udp_addr(&self) -> Option<String> {
+        let udp_addr: Option<String> = self.load_table_data("server", "udp_addr");
+        udp_addr
+    }
+
+    pub fn uds_path(&self) -> Option<String> {
+        let uds_path: Option<String> = self.load_table_data("server", "uds_path");
+        uds_path
+    }
+
+    pub fn shm_path(&self) -> Option<String> {
+        let shm_path: Option<String> = self.load_table_data("server", "shm_path");
+        shm_path
+    }
+
+    pub fn shm_capacity(&self) -> u32 {
+        let shm_capacity: Option<u32> = self.load_table_data("server", "shm_capacity");
+        shm_capacity.unwrap_or(64)
+    }
+
+    pub fn tls_cert_path(&self) -> Option<String> {
+        let tls_cert_path: Option<String> = self.load_table_data("server", "tls_cert_path");
+        tls_cert_path
+    }
+
+    pub fn tls_key_path(&self) -> Option<String> {
+        let tls_key_path: Option<String> = self.load_table_data("server", "tls_key_path");
+        tls_key_path
+    }
+
+    pub fn authenticator(&self) -> Authenticator {
+        let auth_token: Option<String> = self.load_table_data("server", "auth_token");
+        let auth_token_file: Option<String> = self.load_table_data("server", "auth_token_file");
+        Authenticator::load(auth_token, auth_token_file)
+            .map_err(|e| {
+                panic!("{}", e);
+            })
+            .unwrap()
+    }
+
+    pub fn rooms(&self) -> Rooms {
+        let session_grace: Option<u64> = self.load_table_data("server", "session_grace");
+        let rooms: Option<LuaValue> = self.load_table_data("server", "rooms");
+        let rooms: HashMap<String, RoomCfg> = match rooms {
+            Some(rooms) => self
+                .lua
+                .from_value(rooms)
+                .map_err(|e| {
+                    panic!("{}", e);
+                })
+                .unwrap(),
+            None => HashMap::new(),
+        };
+        Rooms::new(Duration::from_millis(session_grace.unwrap_or(0)), rooms)
+    }
+
     pub fn core_init_cfg(&self) -> CoreInitCfg {
         let cfg: CoreInitCfg = self.load_ser_data("core_init_cfg");
         cfg
@@ -9,7 +9,10 @@ use tracing_subscriber::{
     filter::EnvFilter, fmt, layer::SubscriberExt, util::SubscriberInitExt, Registry,
 };
 use universal_simulation_engine::{
-    args::Args, handler::server_handler, lua::LuaManager, system::System,
+    args::Args,
+    handler::{server_handler, ServerCfg},
+    lua::LuaManager,
+    system::System,
 };
 use uuid::Uuid;
 
@@ -18,9 +21,27 @@ static ARGS: Lazy<std::sync::Mutex<Args>> = Lazy::new(|| std::sync::Mutex::new(A
 fn main() {
     let lua = LuaManager::new(&ARGS.lock().unwrap().config_path);
 
-    let server_addr = lua.server_addr();
-    let tick_timeout = lua.tick_timeout();
-    let read_rate = lua.read_rate();
+    let server_cfg = ServerCfg {
+        addr: lua.server_addr(),
+        tick_timeout: lua.tick_timeout(),
+        liveness_timeout: lua.liveness_timeout(),
+        read_rate: lua.read_rate(),
+        batch_interval: lua.batch_interval(),
+        output_queue: lua.output_queue(),
+        backpressure: lua.backpressure(),
+        compression_threshold: lua.compression_threshold(),
+        max_frame_size: lua.max_frame_size(),
+        grpc_addr: lua.grpc_addr(),
+        ws_addr: lua.ws_addr(),
+        udp_addr: lua.udp_addr(),
+        uds_path: lua.uds_path(),
+        shm_path: lua.shm_path(),
+        shm_capacity: lua.shm_capacity(),
+        tls_cert_path: lua.tls_cert_path(),
+        tls_key_path: lua.tls_key_path(),
+        auth: lua.authenticator(),
+        rooms: lua.rooms(),
+    };
     let model_root_path = lua.model_root_path();
     let controller_buffer = lua.controller_buffer();
     let core_init_cfg = lua.core_init_cfg();
@@ -92,9 +113,7 @@ fn main() {
         let global_cancellation_token = CancellationToken::new();
 
         server_handler(
-            &server_addr,
-            tick_timeout,
-            read_rate,
+            server_cfg,
             plane_init_cfg,
             system.clone(),
             controller_buffer,
@@ -0,0 +1,248 @@
+use crate::handler::{Broadcast, ClientRegistry, PlaneHandle, PlaneMap};
+use fly_ruler_codec::{
+    ClientRole, ErrorCode, PlaneEvent, PlaneEventKind, PlaneInfo, Response, ServiceCallResponse,
+};
+use fly_ruler_utils::InputSender;
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    sync::{Arc, Weak},
+};
+use tokio::sync::broadcast;
+
+/// address of the client owning a plane, shared with the tasks which broadcast its outputs
+#[derive(Debug, Clone)]
+pub(crate) struct PlaneOwner(Arc<std::sync::Mutex<SocketAddr>>);
+
+impl PlaneOwner {
+    pub(crate) fn new(owner: SocketAddr) -> Self {
+        Self(Arc::new(std::sync::Mutex::new(owner)))
+    }
+
+    pub(crate) fn get(&self) -> SocketAddr {
+        *self.0.lock().unwrap()
+    }
+
+    pub(crate) fn set(&self, owner: SocketAddr) {
+        *self.0.lock().unwrap() = owner;
+    }
+}
+
+/// error of a request on a plane, replied to the client
+pub(crate) type OwnershipError = (ErrorCode, String);
+
+// the registry is always locked before the planes of a client,
+// so that a plane is never moved to a client which is being released
+
+/// the owner and the planes holding `plane_id`
+async fn find_plane(
+    clients: &HashMap<SocketAddr, Weak<PlaneMap>>,
+    plane_id: &str,
+) -> Option<(SocketAddr, Arc<PlaneMap>)> {
+    for (owner, planes) in clients.iter() {
+        let Some(planes) = planes.upgrade() else {
+            continue;
+        };
+        if planes.lock().await.contains_key(plane_id) {
+            return Some((*owner, planes));
+        }
+    }
+    None
+}
+
+fn not_found(plane_id: &str) -> OwnershipError {
+    (
+        ErrorCode::PlaneNotFound,
+        format!("Plane {} not found", plane_id),
+    )
+}
+
+/// the planes of a connected client
+fn connected(
+    clients: &HashMap<SocketAddr, Weak<PlaneMap>>,
+    client: &str,
+) -> Result<(SocketAddr, Arc<PlaneMap>), OwnershipError> {
+    let addr: SocketAddr = client
+        .parse()
+        .map_err(|e| (ErrorCode::InvalidArgs, format!("{}, {}", client, e)))?;
+    match clients.get(&addr).and_then(|planes| planes.upgrade()) {
+        Some(planes) => Ok((addr, planes)),
+        None => Err((
+            ErrorCode::InvalidArgs,
+            format!("Client {} is not connected", client),
+        )),
+    }
+}
+
+/// the planes holding `plane_id`, if `ip` owns the plane, is one of its pilots or is an admin,
+/// `owner_only` leaves the pilots out
+pub(crate) async fn authorize_plane(
+    clients: &ClientRegistry,
+    ip: SocketAddr,
+    role: ClientRole,
+    plane_id: &str,
+    owner_only: bool,
+) -> Result<(SocketAddr, Arc<PlaneMap>), OwnershipError> {
+    let clients = clients.lock().await;
+    let (owner, planes) = find_plane(&clients, plane_id)
+        .await
+        .ok_or_else(|| not_found(plane_id))?;
+    let allowed = owner == ip
+        || role.includes(ClientRole::Admin)
+        || (!owner_only
+            && planes
+                .lock()
+                .await
+                .get(plane_id)
+                .is_some_and(|plane| plane.pilots.contains(&ip)));
+    if allowed {
+        Ok((owner, planes))
+    } else {
+        Err((
+            ErrorCode::NotPlaneOwner,
+            format!("Plane {} is owned by {}", plane_id, owner),
+        ))
+    }
+}
+
+/// the controller of a plane `ip` may control, its own planes are looked up first
+pub(crate) async fn plane_controller(
+    clients: &ClientRegistry,
+    planes: &PlaneMap,
+    ip: SocketAddr,
+    role: ClientRole,
+    plane_id: &str,
+) -> Result<InputSender, OwnershipError> {
+    if let Some(plane) = planes.lock().await.get(plane_id) {
+        return Ok(plane.controller.clone());
+    }
+    let (_, planes) = authorize_plane(clients, ip, role, plane_id, false).await?;
+    let controller = planes
+        .lock()
+        .await
+        .get(plane_id)
+        .map(|plane| plane.controller.clone());
+    controller.ok_or_else(|| not_found(plane_id))
+}
+
+/// give a plane to the client `new_owner`, only the owner and the admins may
+pub(crate) async fn transfer_plane(
+    clients: &ClientRegistry,
+    ip: SocketAddr,
+    role: ClientRole,
+    plane_id: &str,
+    new_owner: &str,
+) -> Result<PlaneInfo, OwnershipError> {
+    let clients = clients.lock().await;
+    let (new_owner, target) = connected(&clients, new_owner)?;
+    let (owner, planes) = find_plane(&clients, plane_id)
+        .await
+        .ok_or_else(|| not_found(plane_id))?;
+    if owner != ip && !role.includes(ClientRole::Admin) {
+        return Err((
+            ErrorCode::NotPlaneOwner,
+            format!("Plane {} is owned by {}", plane_id, owner),
+        ));
+    }
+    if owner == new_owner {
+        let planes = planes.lock().await;
+        let plane = planes.get(plane_id).ok_or_else(|| not_found(plane_id))?;
+        return Ok(plane.info(plane_id));
+    }
+
+    let mut plane = planes
+        .lock()
+        .await
+        .remove(plane_id)
+        .ok_or_else(|| not_found(plane_id))?;
+    plane.owner.set(new_owner);
+    plane.pilots.remove(&new_owner);
+    let info = plane.info(plane_id);
+    target.lock().await.insert(plane_id.to_string(), plane);
+    Ok(info)
+}
+
+/// let the client `pilot` control a plane, or take the permission back,
+/// only the owner and the admins may
+pub(crate) async fn share_plane(
+    clients: &ClientRegistry,
+    ip: SocketAddr,
+    role: ClientRole,
+    plane_id: &str,
+    pilot: &str,
+    revoke: bool,
+) -> Result<PlaneInfo, OwnershipError> {
+    let (_, planes) = authorize_plane(clients, ip, role, plane_id, true).await?;
+    let pilot = if revoke {
+        // a disconnected pilot may be revoked
+        pilot
+            .parse()
+            .map_err(|e| (ErrorCode::InvalidArgs, format!("{}, {}", pilot, e)))?
+    } else {
+        connected(&*clients.lock().await, pilot)?.0
+    };
+    let mut planes = planes.lock().await;
+    let plane = planes
+        .get_mut(plane_id)
+        .ok_or_else(|| not_found(plane_id))?;
+    if revoke {
+        plane.pilots.remove(&pilot);
+    } else if pilot != plane.owner.get() {
+        plane.pilots.insert(pilot);
+    }
+    Ok(plane.info(plane_id))
+}
+
+/// broadcast message of a plane given to another client, tagged with the new owner
+pub(crate) fn owner_changed(plane_id: String) -> ServiceCallResponse {
+    ServiceCallResponse {
+        name: "PlaneEvent".to_string(),
+        request_id: 0,
+        response: Some(Response::PlaneEvent(PlaneEvent {
+            plane_id,
+            kind: PlaneEventKind::OwnerChanged,
+            time_scale: None,
+        })),
+    }
+}
+
+/// remove a plane whose outputs stopped, wherever it is owned
+pub(crate) async fn take_plane(
+    clients: &ClientRegistry,
+    plane_id: &str,
+) -> Option<(SocketAddr, PlaneHandle)> {
+    let clients = clients.lock().await;
+    let (owner, planes) = find_plane(&clients, plane_id).await?;
+    let plane = planes.lock().await.remove(plane_id)?;
+    Some((owner, plane))
+}
+
+/// forget a client which is dropped, its planes are stopped and it no longer pilots the other planes
+pub(crate) async fn release_client(
+    clients: &ClientRegistry,
+    ip: SocketAddr,
+    planes: &PlaneMap,
+    broadcast_channel_sender: &broadcast::Sender<Broadcast>,
+) {
+    let mut clients = clients.lock().await;
+    clients.remove(&ip);
+    for (id, plane) in planes.lock().await.drain() {
+        plane.cancellation_token.cancel();
+        let _ = broadcast_channel_sender.send(Broadcast {
+            owner: ip,
+            response: ServiceCallResponse {
+                name: "LostPlane".to_string(),
+                request_id: 0,
+                response: Some(Response::LostPlane(id)),
+            },
+        });
+    }
+    for planes in clients.values() {
+        let Some(planes) = planes.upgrade() else {
+            continue;
+        };
+        for plane in planes.lock().await.values_mut() {
+            plane.pilots.remove(&ip);
+        }
+    }
+}
@@ -0,0 +1,302 @@
+use anyhow::{anyhow, Result};
+use fly_ruler_codec::{ClientStatus, Response, ServiceCallResponse};
+use std::{
+    collections::{HashMap, VecDeque},
+    mem::{discriminant, Discriminant},
+    net::SocketAddr,
+    str::FromStr,
+    sync::{Arc, Mutex, Weak},
+};
+use tokio::sync::Notify;
+
+/// what happens to the streaming messages of a client whose queue is full
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Backpressure {
+    /// the oldest queued streaming message makes room for the new one
+    #[default]
+    DropOldest,
+    /// only the latest output and diagnostic of every plane are queued, then the oldest is dropped
+    Conflate,
+    /// the client is dropped
+    Disconnect,
+}
+
+impl FromStr for Backpressure {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "drop_oldest" => Ok(Self::DropOldest),
+            "conflate" => Ok(Self::Conflate),
+            "disconnect" => Ok(Self::Disconnect),
+            _ => Err(format!("unknown backpressure policy {}", s)),
+        }
+    }
+}
+
+/// the output queues of the clients of a room, by address
+pub(crate) type QueueRegistry = Arc<Mutex<HashMap<SocketAddr, Weak<OutputQueue>>>>;
+
+#[derive(Default)]
+struct Queue {
+    messages: VecDeque<ServiceCallResponse>,
+    dropped: u64,
+    conflated: u64,
+    lagged: u64,
+}
+
+/// a message the client did not ask for, such as an output or an event, may be dropped
+fn is_streaming(msg: &ServiceCallResponse) -> bool {
+    msg.request_id == 0
+}
+
+/// the kind and the plane of a streaming message superseded by the next one of its plane
+fn conflation_key(msg: &ServiceCallResponse) -> Option<(Discriminant<Response>, &str)> {
+    match &msg.response {
+        Some(response @ Response::Output(output)) if is_streaming(msg) => {
+            Some((discriminant(response), &output.id))
+        }
+        Some(response @ Response::Diagnostic(diagnostic)) if is_streaming(msg) => {
+            Some((discriminant(response), &diagnostic.id))
+        }
+        _ => None,
+    }
+}
+
+/// the messages waiting to be written to one client,
+/// the policy only drops the streaming messages, the replies are always kept
+pub(crate) struct OutputQueue {
+    capacity: usize,
+    policy: Backpressure,
+    queue: Mutex<Queue>,
+    notify: Notify,
+}
+
+impl OutputQueue {
+    pub(crate) fn new(capacity: usize, policy: Backpressure) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            policy,
+            queue: Mutex::new(Queue::default()),
+            notify: Notify::new(),
+        }
+    }
+
+    /// queue a message without waiting, fails if the client has to be dropped
+    pub(crate) fn push(&self, msg: ServiceCallResponse) -> Result<()> {
+        let mut queue = self.queue.lock().unwrap();
+        if let (Backpressure::Conflate, Some(key)) = (self.policy, conflation_key(&msg)) {
+            let queued = queue
+                .messages
+                .iter_mut()
+                .find(|queued| conflation_key(queued) == Some(key));
+            if let Some(queued) = queued {
+                *queued = msg;
+                queue.conflated += 1;
+                return Ok(());
+            }
+        }
+        if queue.messages.len() >= self.capacity {
+            if self.policy == Backpressure::Disconnect {
+                return Err(anyhow!(
+                    "output queue is full, {} messages are waiting",
+                    queue.messages.len()
+                ));
+            }
+            let oldest = queue.messages.iter().position(is_streaming);
+            match (oldest, is_streaming(&msg)) {
+                (Some(oldest), _) => {
+                    queue.messages.remove(oldest);
+                    queue.dropped += 1;
+                }
+                (None, true) => {
+                    queue.dropped += 1;
+                    return Ok(());
+                }
+                // the client does not even read its replies
+                (None, false) => {
+                    return Err(anyhow!(
+                        "output queue is full, {} replies are not read",
+                        queue.messages.len()
+                    ));
+                }
+            }
+        }
+        queue.messages.push_back(msg);
+        drop(queue);
+        self.notify.notify_one();
+        Ok(())
+    }
+
+    /// count the broadcast messages the client missed
+    pub(crate) fn lagged(&self, skipped: u64) {
+        self.queue.lock().unwrap().lagged += skipped;
+    }
+
+    /// wait for the next message
+    pub(crate) async fn pop(&self) -> ServiceCallResponse {
+        loop {
+            if let Some(msg) = self.try_pop() {
+                return msg;
+            }
+            self.notify.notified().await;
+        }
+    }
+
+    pub(crate) fn try_pop(&self) -> Option<ServiceCallResponse> {
+        self.queue.lock().unwrap().messages.pop_front()
+    }
+
+    pub(crate) fn status(&self, addr: SocketAddr) -> ClientStatus {
+        let queue = self.queue.lock().unwrap();
+        ClientStatus {
+            addr: addr.to_string(),
+            queued: queue.messages.len() as u64,
+            dropped: queue.dropped,
+            conflated: queue.conflated,
+            lagged: queue.lagged,
+        }
+    }
+}
+
+#[cfg(test)]
+mod use_queue_tests {
+    use super::*;
+    use fly_ruler_codec::{PlaneDiagnostic, PlaneMessage};
+
+    fn output(plane_id: &str, time: f64) -> ServiceCallResponse {
+        ServiceCallResponse {
+            name: "Output".to_string(),
+            request_id: 0,
+            response: Some(Response::Output(PlaneMessage {
+                id: plane_id.to_string(),
+                time,
+                output: None,
+            })),
+        }
+    }
+
+    fn diagnostic(plane_id: &str, time: f64) -> ServiceCallResponse {
+        ServiceCallResponse {
+            name: "Diagnostic".to_string(),
+            request_id: 0,
+            response: Some(Response::Diagnostic(PlaneDiagnostic {
+                id: plane_id.to_string(),
+                time,
+                diagnostic: None,
+            })),
+        }
+    }
+
+    fn reply(request_id: u64) -> ServiceCallResponse {
+        ServiceCallResponse {
+            name: "ListPlanes".to_string(),
+            request_id,
+            response: None,
+        }
+    }
+
+    fn drain(queue: &OutputQueue) -> Vec<(String, u64, Option<f64>)> {
+        std::iter::from_fn(|| queue.try_pop())
+            .map(|msg| {
+                let time = match &msg.response {
+                    Some(Response::Output(output)) => Some(output.time),
+                    Some(Response::Diagnostic(diagnostic)) => Some(diagnostic.time),
+                    _ => None,
+                };
+                (msg.name, msg.request_id, time)
+            })
+            .collect()
+    }
+
+    fn addr() -> SocketAddr {
+        "127.0.0.1:2350".parse().unwrap()
+    }
+
+    #[test]
+    fn test_drop_oldest() {
+        let queue = OutputQueue::new(3, Backpressure::DropOldest);
+        queue.push(reply(1)).unwrap();
+        queue.push(diagnostic("a", 0.0)).unwrap();
+        queue.push(output("a", 0.0)).unwrap();
+        queue.push(output("a", 1.0)).unwrap();
+        queue.push(reply(2)).unwrap();
+
+        let status = queue.status(addr());
+        assert_eq!(status.queued, 3);
+        assert_eq!(status.dropped, 2);
+        assert_eq!(status.conflated, 0);
+        assert_eq!(
+            drain(&queue),
+            vec![
+                ("ListPlanes".to_string(), 1, None),
+                ("Output".to_string(), 0, Some(1.0)),
+                ("ListPlanes".to_string(), 2, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_drop_new_streaming_message() {
+        let queue = OutputQueue::new(2, Backpressure::DropOldest);
+        queue.push(reply(1)).unwrap();
+        queue.push(reply(2)).unwrap();
+        queue.push(diagnostic("a", 0.0)).unwrap();
+        assert!(queue.push(reply(3)).is_err());
+
+        assert_eq!(queue.status(addr()).dropped, 1);
+        assert_eq!(drain(&queue).len(), 2);
+    }
+
+    #[test]
+    fn test_conflate() {
+        let queue = OutputQueue::new(4, Backpressure::Conflate);
+        queue.push(output("a", 0.0)).unwrap();
+        queue.push(diagnostic("a", 0.0)).unwrap();
+        queue.push(output("b", 0.0)).unwrap();
+        queue.push(reply(1)).unwrap();
+        queue.push(output("a", 1.0)).unwrap();
+        queue.push(diagnostic("a", 1.0)).unwrap();
+        queue.push(output("c", 0.0)).unwrap();
+
+        let status = queue.status(addr());
+        assert_eq!(status.queued, 4);
+        assert_eq!(status.conflated, 2);
+        assert_eq!(status.dropped, 1);
+        assert_eq!(
+            drain(&queue),
+            vec![
+                ("Diagnostic".to_string(), 0, Some(1.0)),
+                ("Output".to_string(), 0, Some(0.0)),
+                ("ListPlanes".to_string(), 1, None),
+                ("Output".to_string(), 0, Some(0.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_disconnect() {
+        let queue = OutputQueue::new(2, Backpressure::Disconnect);
+        queue.push(output("a", 0.0)).unwrap();
+        queue.push(diagnostic("a", 0.0)).unwrap();
+        assert!(queue.push(output("a", 1.0)).is_err());
+
+        let status = queue.status(addr());
+        assert_eq!(status.queued, 2);
+        assert_eq!(status.dropped, 0);
+        assert_eq!(status.conflated, 0);
+    }
+
+    #[test]
+    fn test_status() {
+        let queue = OutputQueue::new(1, Backpressure::DropOldest);
+        queue.push(output("a", 0.0)).unwrap();
+        queue.lagged(3);
+        queue.lagged(2);
+
+        let status = queue.status(addr());
+        assert_eq!(status.addr, "127.0.0.1:2350");
+        assert_eq!(status.queued, 1);
+        assert_eq!(status.lagged, 5);
+    }
+}
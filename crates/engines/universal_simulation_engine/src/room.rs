@@ -0,0 +1,162 @@
+use crate::{
+    handler::{Broadcast, ClientRegistry},
+    queue::QueueRegistry,
+    session::SessionStore,
+};
+use fly_ruler_codec::ClientRole;
+use fly_ruler_core::core::ClockMode;
+use serde::Deserialize;
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    net::SocketAddr,
+    sync::Arc,
+    time::Duration,
+};
+use tokio::sync::{broadcast, Mutex};
+use tracing::{event, Level};
+
+/// options of a room, loaded from the `rooms` table of the server config
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RoomCfg {
+    /// clock of the planes of the room, the clock of the core if missing
+    pub clock_mode: Option<ClockMode>,
+    /// users of the token file allowed in the room, every user if empty
+    #[serde(default)]
+    pub access: Vec<String>,
+}
+
+/// a world of its own, its clients only see and control the planes of the room
+#[derive(Clone)]
+pub(crate) struct Room {
+    pub(crate) name: String,
+    pub(crate) clock_mode: Option<ClockMode>,
+    access: Arc<HashSet<String>>,
+    /// loaded from the config, kept even without clients
+    configured: bool,
+    pub(crate) broadcast_channel_sender: broadcast::Sender<Broadcast>,
+    pub(crate) clients: ClientRegistry,
+    pub(crate) sessions: SessionStore,
+    pub(crate) queues: QueueRegistry,
+}
+
+impl Room {
+    fn new(name: &str, cfg: RoomCfg, configured: bool, session_grace: Duration) -> Self {
+        let (broadcast_channel_sender, _) = broadcast::channel::<Broadcast>(1024);
+        Self {
+            name: name.to_string(),
+            clock_mode: cfg.clock_mode,
+            access: Arc::new(cfg.access.into_iter().collect()),
+            configured,
+            broadcast_channel_sender,
+            clients: Arc::new(Mutex::new(HashMap::new())),
+            sessions: SessionStore::new(session_grace),
+            queues: Arc::new(std::sync::Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// every client, session and plane of the room holds its registry,
+    /// a room only held by the rooms is empty
+    fn is_empty(&self) -> bool {
+        Arc::strong_count(&self.clients) == 1
+    }
+
+    /// admins enter every room, the other clients the rooms open to their user
+    fn admits(&self, user: Option<&str>, role: ClientRole) -> bool {
+        self.access.is_empty()
+            || role.includes(ClientRole::Admin)
+            || user.is_some_and(|user| self.access.contains(user))
+    }
+}
+
+/// the rooms of the server, the default one is named by an empty string
+#[derive(Clone)]
+pub struct Rooms {
+    session_grace: Duration,
+    rooms: Arc<std::sync::Mutex<HashMap<String, Room>>>,
+}
+
+impl fmt::Debug for Rooms {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rooms = self.rooms.lock().unwrap();
+        f.debug_struct("Rooms")
+            .field("session_grace", &self.session_grace)
+            .field("rooms", &rooms.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl Rooms {
+    /// `session_grace` is the grace period of the sessions of every room
+    pub fn new(session_grace: Duration, cfgs: HashMap<String, RoomCfg>) -> Self {
+        let mut rooms: HashMap<String, Room> = cfgs
+            .into_iter()
+            .map(|(name, cfg)| (name.clone(), Room::new(&name, cfg, true, session_grace)))
+            .collect();
+        rooms
+            .entry(String::new())
+            .or_insert_with(|| Room::new("", RoomCfg::default(), true, session_grace));
+        Self {
+            session_grace,
+            rooms: Arc::new(std::sync::Mutex::new(rooms)),
+        }
+    }
+
+    /// the room of the clients which do not name one
+    pub(crate) fn default_room(&self) -> Room {
+        self.rooms.lock().unwrap()[""].clone()
+    }
+
+    /// the room `name` joined by a client of `user` with `role`, or why it is rejected,
+    /// a missing room is created if `create` is set and the client is an admin,
+    /// the rooms created by the clients are dropped once they are empty
+    pub(crate) fn join(
+        &self,
+        name: &str,
+        create: bool,
+        user: Option<&str>,
+        role: ClientRole,
+    ) -> Result<Room, String> {
+        let mut rooms = self.rooms.lock().unwrap();
+        rooms.retain(|room_name, room| {
+            let keep = room.configured || !room.is_empty();
+            if !keep {
+                event!(Level::INFO, "Room {} removed", room_name);
+            }
+            keep
+        });
+        let room = match rooms.get(name) {
+            Some(room) => room.clone(),
+            None if create && role.includes(ClientRole::Admin) => {
+                let room = Room::new(name, RoomCfg::default(), false, self.session_grace);
+                rooms.insert(name.to_string(), room.clone());
+                event!(Level::INFO, "Room {} created", name);
+                room
+            }
+            None if create => return Err(format!("{:?} can not create a room", role)),
+            None => return Err(format!("room {} does not exist", name)),
+        };
+        if room.admits(user, role) {
+            Ok(room)
+        } else {
+            Err(format!("room {} is not open to this client", name))
+        }
+    }
+
+    /// whether `addr` names a client of any room
+    pub(crate) async fn is_connected(&self, addr: &SocketAddr) -> bool {
+        let registries: Vec<ClientRegistry> = self
+            .rooms
+            .lock()
+            .unwrap()
+            .values()
+            .map(|room| room.clients.clone())
+            .collect();
+        for clients in registries {
+            if clients.lock().await.contains_key(addr) {
+                return true;
+            }
+        }
+        false
+    }
+}
@@ -0,0 +1,215 @@
+use crate::{
+    handler::{Broadcast, ClientRegistry, PlaneMap},
+    ownership::{owner_changed, release_client},
+};
+use fly_ruler_utils::CancellationToken;
+use std::{collections::HashMap, fmt, net::SocketAddr, sync::Arc, time::Duration};
+use tokio::{
+    sync::{broadcast, Mutex},
+    time::MissedTickBehavior,
+};
+use tracing::{event, Level};
+use uuid::Uuid;
+
+/// period at which the planes of a detached session are stepped with their last control
+const HOLD_PERIOD: Duration = Duration::from_millis(10);
+
+/// planes of a dropped client, still registered under its address
+struct DetachedSession {
+    addr: SocketAddr,
+    planes: Arc<PlaneMap>,
+    /// stops holding the controls of the planes
+    hold: CancellationToken,
+}
+
+/// sessions of the clients, the planes of a dropped client keep flying under their last control
+/// during the grace period, and are reclaimed by the client reconnecting with its session
+#[derive(Clone, Default)]
+pub struct SessionStore {
+    /// 0 releases the planes as soon as the client is dropped
+    grace: Duration,
+    detached: Arc<Mutex<HashMap<String, DetachedSession>>>,
+}
+
+impl fmt::Debug for SessionStore {
+    // the sessions are kept out of the logs
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SessionStore")
+            .field("grace", &self.grace)
+            .finish_non_exhaustive()
+    }
+}
+
+impl SessionStore {
+    pub fn new(grace: Duration) -> Self {
+        Self {
+            grace,
+            detached: Default::default(),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.grace.is_zero()
+    }
+
+    /// the session of a new client and the planes waiting for it,
+    /// the session asked for is kept if it is still detached, otherwise a new one is given
+    pub(crate) async fn open(&self, session: &str) -> (String, Vec<String>) {
+        if !self.is_enabled() {
+            return (String::new(), Vec::new());
+        }
+        match self.detached.lock().await.get(session) {
+            Some(detached) => {
+                let plane_ids = detached.planes.lock().await.keys().cloned().collect();
+                (session.to_string(), plane_ids)
+            }
+            None => (Uuid::new_v4().simple().to_string(), Vec::new()),
+        }
+    }
+
+    /// keep the planes of a dropped client for the grace period, they are released
+    /// if the client does not reconnect in time
+    pub(crate) async fn detach(
+        &self,
+        session: &str,
+        ip: SocketAddr,
+        planes: &Arc<PlaneMap>,
+        clients: &ClientRegistry,
+        broadcast_channel_sender: &broadcast::Sender<Broadcast>,
+    ) {
+        if !self.is_enabled() || session.is_empty() || planes.lock().await.is_empty() {
+            release_client(clients, ip, planes, broadcast_channel_sender).await;
+            return;
+        }
+        let hold = CancellationToken::new();
+        {
+            let mut detached = self.detached.lock().await;
+            // every task of the client detaches it when it stops
+            if detached.get(session).is_some_and(|d| d.addr == ip) {
+                return;
+            }
+            let replaced = detached.insert(
+                session.to_string(),
+                DetachedSession {
+                    addr: ip,
+                    planes: planes.clone(),
+                    hold: hold.clone(),
+                },
+            );
+            if let Some(replaced) = replaced {
+                replaced.hold.cancel();
+            }
+        }
+        event!(
+            Level::INFO,
+            "Client: {} detached, its planes are kept for {:?}",
+            ip,
+            self.grace
+        );
+
+        // a plane only steps on a control, so the last one is sent again until the planes are
+        // reclaimed or released
+        tokio::spawn({
+            let planes = planes.clone();
+            async move {
+                let mut interval = tokio::time::interval(HOLD_PERIOD);
+                interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+                loop {
+                    tokio::select! {
+                        _ = hold.cancelled() => break,
+                        _ = interval.tick() => {}
+                    }
+                    let controllers: Vec<_> = planes
+                        .lock()
+                        .await
+                        .values()
+                        .map(|plane| plane.controller.clone())
+                        .collect();
+                    for controller in controllers {
+                        if let Some(control) = controller.last() {
+                            let _ = controller.send(&control).await;
+                        }
+                    }
+                }
+            }
+        });
+
+        tokio::spawn({
+            let store = self.clone();
+            let session = session.to_string();
+            let clients = clients.clone();
+            let broadcast_channel_sender = broadcast_channel_sender.clone();
+            async move {
+                tokio::time::sleep(store.grace).await;
+                let expired = {
+                    let mut detached = store.detached.lock().await;
+                    match detached.get(&session) {
+                        Some(d) if d.addr == ip => detached.remove(&session),
+                        _ => None,
+                    }
+                };
+                if let Some(expired) = expired {
+                    expired.hold.cancel();
+                    event!(Level::INFO, "Client: {} session expired", ip);
+                    release_client(&clients, ip, &expired.planes, &broadcast_channel_sender).await;
+                }
+            }
+        });
+    }
+
+    /// move the planes of a detached session to the reconnected client `ip`,
+    /// return the id of the reclaimed planes
+    pub(crate) async fn reclaim(
+        &self,
+        session: &str,
+        ip: SocketAddr,
+        planes: &PlaneMap,
+        clients: &ClientRegistry,
+        broadcast_channel_sender: &broadcast::Sender<Broadcast>,
+    ) -> Vec<String> {
+        let Some(detached) = self.detached.lock().await.remove(session) else {
+            return Vec::new();
+        };
+        detached.hold.cancel();
+        let mut clients = clients.lock().await;
+        clients.remove(&detached.addr);
+
+        let mut plane_ids = Vec::new();
+        {
+            let mut own = planes.lock().await;
+            for (id, mut plane) in detached.planes.lock().await.drain() {
+                plane.owner.set(ip);
+                plane.pilots.remove(&ip);
+                own.insert(id.clone(), plane);
+                plane_ids.push(id);
+            }
+        }
+        // the client pilots the planes of the others under its new address
+        for planes in clients.values() {
+            let Some(planes) = planes.upgrade() else {
+                continue;
+            };
+            for plane in planes.lock().await.values_mut() {
+                if plane.pilots.remove(&detached.addr) {
+                    plane.pilots.insert(ip);
+                }
+            }
+        }
+        drop(clients);
+
+        for plane_id in &plane_ids {
+            let _ = broadcast_channel_sender.send(Broadcast {
+                owner: ip,
+                response: owner_changed(plane_id.clone()),
+            });
+        }
+        event!(
+            Level::INFO,
+            "Client: {} reclaimed {} planes of {}",
+            ip,
+            plane_ids.len(),
+            detached.addr
+        );
+        plane_ids
+    }
+}
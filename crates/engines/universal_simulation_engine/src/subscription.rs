@@ -0,0 +1,135 @@
+use fly_ruler_codec::{OutputField, Subscription};
+use fly_ruler_utils::plane_model::{Control, CoreOutput, State, StateExtend};
+use std::{
+    collections::{HashMap, HashSet},
+    net::SocketAddr,
+    time::Duration,
+};
+
+/// the outputs forwarded to one client, every plane at full rate by default
+#[derive(Debug, Clone)]
+pub struct OutputFilter {
+    all: bool,
+    plane_ids: HashSet<String>,
+    owners: HashSet<SocketAddr>,
+    decimation: u32,
+    fields: Vec<OutputField>,
+    counters: HashMap<String, u32>,
+    batch_interval: Option<Duration>,
+}
+
+impl OutputFilter {
+    /// outputs are grouped by `batch_interval` if it is set
+    pub fn new(batch_interval: Option<Duration>) -> Self {
+        Self {
+            all: true,
+            plane_ids: HashSet::new(),
+            owners: HashSet::new(),
+            decimation: 1,
+            fields: vec![
+                OutputField::State,
+                OutputField::Control,
+                OutputField::StateExtend,
+            ],
+            counters: HashMap::new(),
+            batch_interval,
+        }
+    }
+
+    pub fn batch_interval(&self) -> Option<Duration> {
+        self.batch_interval
+    }
+
+    /// add the planes of the subscription, and replace the rate and mask if they are set
+    pub fn subscribe(&mut self, subscription: Subscription) -> Result<(), String> {
+        let owners = parse_owners(&subscription.owners)?;
+        self.all |= subscription.all;
+        self.plane_ids.extend(subscription.plane_ids);
+        self.owners.extend(owners);
+        if subscription.decimation > 0 {
+            self.decimation = subscription.decimation;
+        }
+        if !subscription.fields.is_empty() {
+            self.fields = subscription.fields;
+        }
+        Ok(())
+    }
+
+    /// remove the planes of the subscription, `all` removes every plane
+    pub fn unsubscribe(&mut self, subscription: Subscription) -> Result<(), String> {
+        let owners = parse_owners(&subscription.owners)?;
+        if subscription.all {
+            self.all = false;
+            self.plane_ids.clear();
+            self.owners.clear();
+            return Ok(());
+        }
+        for plane_id in &subscription.plane_ids {
+            self.plane_ids.remove(plane_id);
+        }
+        for owner in &owners {
+            self.owners.remove(owner);
+        }
+        Ok(())
+    }
+
+    pub fn subscription(&self) -> Subscription {
+        Subscription {
+            all: self.all,
+            plane_ids: self.plane_ids.iter().cloned().collect(),
+            owners: self.owners.iter().map(|o| o.to_string()).collect(),
+            decimation: self.decimation,
+            fields: self.fields.clone(),
+        }
+    }
+
+    /// whether the plane of the owner is subscribed
+    pub fn contains(&self, owner: &SocketAddr, plane_id: &str) -> bool {
+        self.all || self.plane_ids.contains(plane_id) || self.owners.contains(owner)
+    }
+
+    /// whether the next output of the plane is kept by the decimation
+    pub fn sample(&mut self, plane_id: &str) -> bool {
+        if self.decimation <= 1 {
+            return true;
+        }
+        let count = self.counters.entry(plane_id.to_string()).or_insert(0);
+        let keep = *count == 0;
+        *count = (*count + 1) % self.decimation;
+        keep
+    }
+
+    /// zero the parts of the output which are not subscribed,
+    /// zero values are skipped by the encoder
+    pub fn mask(&self, output: &mut CoreOutput) {
+        if !self.fields.contains(&OutputField::State) {
+            output.state = State::default();
+        }
+        if !self.fields.contains(&OutputField::Control) {
+            output.control = Control {
+                thrust: 0.0,
+                elevator: 0.0,
+                aileron: 0.0,
+                rudder: 0.0,
+            };
+        }
+        if !self.fields.contains(&OutputField::StateExtend) {
+            output.state_extend = StateExtend::default();
+        }
+    }
+
+    /// forget the decimation counter of a lost plane
+    pub fn remove_plane(&mut self, plane_id: &str) {
+        self.counters.remove(plane_id);
+    }
+}
+
+fn parse_owners(owners: &[String]) -> Result<Vec<SocketAddr>, String> {
+    owners
+        .iter()
+        .map(|o| {
+            o.parse()
+                .map_err(|_| format!("Invalid owner address: {}", o))
+        })
+        .collect()
+}
@@ -1,19 +1,19 @@
 use crate::manager::{AsPluginManager, ModelManager};
 use fly_ruler_core::{
-    core::{Core, CoreInitCfg, PlaneInitCfg},
+    core::{ClockMode, Core, CoreInitCfg, PlaneInitCfg, PushedPlane},
     parts::trim::TrimOutput,
 };
-use fly_ruler_plugin::{PluginInfo, PluginState};
+use fly_ruler_plugin::{AsPlugin, PluginInfo, PluginState};
 use fly_ruler_utils::{
-    error::{FrError, FrResult},
-    CancellationToken, InputSender, OutputReceiver,
+    error::FrError,
+    plane_model::{ControlLimit, PlaneConstants},
+    CancellationToken,
 };
 use std::{
     collections::HashMap,
     path::{Path, PathBuf},
 };
 use thiserror::Error;
-use tokio::task::JoinHandle;
 use tracing::{event, instrument, Level};
 use uuid::Uuid;
 
@@ -84,6 +84,27 @@ impl System {
         }
     }
 
+    /// get the info, constants and control limits of an enabled model
+    #[instrument(skip(self), level = Level::INFO, err)]
+    pub fn get_model_details(
+        &self,
+        model_id: Uuid,
+    ) -> Result<(PluginInfo, PluginState, PlaneConstants, ControlLimit), SysError> {
+        let model = if let Some(manager) = &self.model_manager {
+            manager.get_model(model_id)
+        } else {
+            return Err(SysError::ManagerNotInit);
+        };
+        match model {
+            Some(model) => {
+                let constants = model.load_constants().map_err(FrError::Plugin)?;
+                let ctrl_limits = model.load_ctrl_limits().map_err(FrError::Plugin)?;
+                Ok((model.info(), model.state(), constants, ctrl_limits))
+            }
+            None => Err(SysError::ModelNotAvailable),
+        }
+    }
+
     #[instrument(skip_all, level = Level::INFO)]
     pub fn init(&mut self, init_cfg: CoreInitCfg) {
         self.model_manager = Some(ModelManager::new(&self.model_root));
@@ -91,23 +112,16 @@ impl System {
         self.core = Some(core);
     }
 
+    /// `clock_mode` overrides the clock of the core
     #[instrument(skip(self, init_cfg, cancellation_token), level = Level::INFO, err)]
     pub fn push_plane(
         &mut self,
         model_id: Uuid,
         controller_buffer: usize,
         init_cfg: PlaneInitCfg,
+        clock_mode: Option<ClockMode>,
         cancellation_token: CancellationToken,
-    ) -> Result<
-        (
-            Uuid,
-            OutputReceiver,
-            InputSender,
-            JoinHandle<FrResult<()>>,
-            TrimOutput,
-        ),
-        SysError,
-    > {
+    ) -> Result<PushedPlane, SysError> {
         let model = if let Some(manager) = &mut self.model_manager {
             manager.get_model(model_id)
         } else {
@@ -115,9 +129,37 @@ impl System {
         };
         match model {
             Some(model) => match &mut self.core {
-                Some(core) => {
-                    Ok(core.push_plane(model, controller_buffer, init_cfg, cancellation_token)?)
-                }
+                Some(core) => match clock_mode {
+                    Some(clock_mode) => Ok(core.push_plane_with_clock(
+                        model,
+                        controller_buffer,
+                        init_cfg,
+                        clock_mode,
+                        cancellation_token,
+                    )?),
+                    None => Ok(core.push_plane(
+                        model,
+                        controller_buffer,
+                        init_cfg,
+                        cancellation_token,
+                    )?),
+                },
+                None => Err(SysError::CoreNotInit),
+            },
+            None => Err(SysError::ModelNotAvailable),
+        }
+    }
+
+    #[instrument(skip(self, init_cfg), level = Level::INFO, err)]
+    pub fn trim(&mut self, model_id: Uuid, init_cfg: PlaneInitCfg) -> Result<TrimOutput, SysError> {
+        let model = if let Some(manager) = &mut self.model_manager {
+            manager.get_model(model_id)
+        } else {
+            return Err(SysError::ManagerNotInit);
+        };
+        match model {
+            Some(model) => match &self.core {
+                Some(core) => Ok(core.trim(model, init_cfg)?),
                 None => Err(SysError::CoreNotInit),
             },
             None => Err(SysError::ModelNotAvailable),
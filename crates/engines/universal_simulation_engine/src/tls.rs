@@ -0,0 +1,37 @@
+use anyhow::{anyhow, Result};
+use std::sync::Arc;
+use tokio_rustls::{rustls::ServerConfig, TlsAcceptor};
+use tonic::transport::{Identity, ServerTlsConfig};
+
+/// certificate chain and private key of the server, in pem
+#[derive(Clone)]
+pub(crate) struct TlsIdentity {
+    cert: Vec<u8>,
+    key: Vec<u8>,
+}
+
+impl TlsIdentity {
+    pub(crate) fn load(cert_path: &str, key_path: &str) -> Result<Self> {
+        let cert = std::fs::read(cert_path)
+            .map_err(|e| anyhow!("certificate {} can not be read, {}", cert_path, e))?;
+        let key = std::fs::read(key_path)
+            .map_err(|e| anyhow!("private key {} can not be read, {}", key_path, e))?;
+        Ok(Self { cert, key })
+    }
+
+    /// acceptor of the tcp and websocket clients
+    pub(crate) fn acceptor(&self) -> Result<TlsAcceptor> {
+        let certs =
+            rustls_pemfile::certs(&mut self.cert.as_slice()).collect::<Result<Vec<_>, _>>()?;
+        let key = rustls_pemfile::private_key(&mut self.key.as_slice())?
+            .ok_or_else(|| anyhow!("no private key found"))?;
+        let config = ServerConfig::builder()
+            .with_no_client_auth()
+            .with_single_cert(certs, key)?;
+        Ok(TlsAcceptor::from(Arc::new(config)))
+    }
+
+    pub(crate) fn grpc(&self) -> ServerTlsConfig {
+        ServerTlsConfig::new().identity(Identity::from_pem(&self.cert, &self.key))
+    }
+}
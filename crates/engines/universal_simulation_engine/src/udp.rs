@@ -0,0 +1,218 @@
+use crate::{
+    auth::required_role,
+    handler::{ClientRegistry, PlaneMap},
+    ownership::plane_controller,
+};
+use anyhow::Result;
+use fly_ruler_codec::{ClientRole, Decoder, Encoder, PlaneMessage, UdpDatagram, UdpPayload};
+use fly_ruler_utils::CancellationToken;
+use std::{
+    collections::HashMap,
+    io::ErrorKind,
+    net::SocketAddr,
+    sync::{Arc, Weak},
+};
+use tokio::{net::UdpSocket, sync::Mutex};
+use tracing::{event, Level};
+use uuid::Uuid;
+
+/// the udp side channel shared by the clients, the session stays on the client connection
+pub(crate) struct UdpChannel {
+    socket: UdpSocket,
+    port: u16,
+    sessions: Mutex<HashMap<u64, UdpSession>>,
+}
+
+/// udp state of one client, keyed by the token given by `OpenUdp`
+struct UdpSession {
+    owner: SocketAddr,
+    /// the controls are authorized as the `SendControl` of the connection
+    role: ClientRole,
+    planes: Weak<PlaneMap>,
+    clients: ClientRegistry,
+    /// address of the last datagram of the client
+    peer: Option<SocketAddr>,
+    /// sequence of the last control applied to every plane
+    sequences: HashMap<String, u64>,
+    /// sequence of the last output sent to the client
+    output_sequence: u64,
+}
+
+/// the udp session of a client connection
+#[derive(Clone)]
+pub(crate) struct UdpLink {
+    pub(crate) channel: Arc<UdpChannel>,
+    pub(crate) token: u64,
+    /// the subscribed outputs are sent over udp instead of the connection
+    pub(crate) outputs: bool,
+}
+
+impl UdpChannel {
+    pub(crate) async fn bind(addr: &str) -> Result<Arc<Self>> {
+        let socket = UdpSocket::bind(addr).await?;
+        let port = socket.local_addr()?.port();
+        Ok(Arc::new(Self {
+            socket,
+            port,
+            sessions: Mutex::new(HashMap::new()),
+        }))
+    }
+
+    pub(crate) fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// open the session of a client, a client has one session at most
+    pub(crate) async fn open(
+        &self,
+        owner: SocketAddr,
+        role: ClientRole,
+        planes: &Arc<PlaneMap>,
+        clients: &ClientRegistry,
+    ) -> u64 {
+        let mut sessions = self.sessions.lock().await;
+        sessions.retain(|_, s| s.owner != owner && s.planes.strong_count() > 0);
+        let token = Uuid::new_v4().as_u64_pair().0;
+        sessions.insert(
+            token,
+            UdpSession {
+                owner,
+                role,
+                planes: Arc::downgrade(planes),
+                clients: clients.clone(),
+                peer: None,
+                sequences: HashMap::new(),
+                output_sequence: 0,
+            },
+        );
+        token
+    }
+
+    /// apply a datagram of a client, datagrams of unknown sessions, stale controls
+    /// and controls of planes the client may not fly are dropped
+    async fn receive(&self, datagram: UdpDatagram, peer: SocketAddr) {
+        let (owner, role, planes, clients, request) = {
+            let mut sessions = self.sessions.lock().await;
+            let Some(session) = sessions.get_mut(&datagram.token) else {
+                event!(Level::DEBUG, "Datagram from {} has an unknown token", peer);
+                return;
+            };
+            let Some(planes) = session.planes.upgrade() else {
+                sessions.remove(&datagram.token);
+                return;
+            };
+            session.peer = Some(peer);
+            let request = match datagram.payload {
+                Some(UdpPayload::Control(request)) => request,
+                _ => return,
+            };
+            let last = session.sequences.get(&request.plane_id);
+            if last.is_some_and(|last| datagram.sequence <= *last) {
+                event!(
+                    Level::TRACE,
+                    "Stale control {} of plane {} dropped",
+                    datagram.sequence,
+                    request.plane_id
+                );
+                return;
+            }
+            session
+                .sequences
+                .insert(request.plane_id.clone(), datagram.sequence);
+            (
+                session.owner,
+                session.role,
+                planes,
+                session.clients.clone(),
+                request,
+            )
+        };
+
+        if !role.includes(required_role("SendControl")) {
+            event!(Level::DEBUG, "Control from {} dropped, {:?}", peer, role);
+            return;
+        }
+        let controller =
+            match plane_controller(&clients, &planes, owner, role, &request.plane_id).await {
+                Ok(controller) => controller,
+                Err((_, message)) => {
+                    event!(Level::DEBUG, "Control from {} dropped, {}", peer, message);
+                    return;
+                }
+            };
+        let control = request.control.unwrap_or_default();
+        if let Err(e) = controller.send(&control).await {
+            event!(
+                Level::WARN,
+                "Plane {} control failed, {}",
+                request.plane_id,
+                e
+            );
+        }
+    }
+}
+
+impl UdpLink {
+    /// send an output to the client, outputs are dropped before the first datagram of the client
+    /// and while the socket is busy
+    pub(crate) async fn send_output(&self, output: PlaneMessage) {
+        let (peer, sequence) = {
+            let mut sessions = self.channel.sessions.lock().await;
+            let Some(session) = sessions.get_mut(&self.token) else {
+                return;
+            };
+            let Some(peer) = session.peer else {
+                return;
+            };
+            session.output_sequence += 1;
+            (peer, session.output_sequence)
+        };
+        let datagram = UdpDatagram {
+            token: self.token,
+            sequence,
+            payload: Some(UdpPayload::Output(output)),
+        };
+        let data = match datagram.encode() {
+            Ok(data) => data,
+            Err(e) => {
+                event!(Level::WARN, "Output to {} failed, {}", peer, e);
+                return;
+            }
+        };
+        match self.channel.socket.try_send_to(&data, peer) {
+            Ok(_) => {}
+            Err(e) if e.kind() == ErrorKind::WouldBlock => {
+                event!(Level::TRACE, "Output to {} dropped, socket is busy", peer);
+            }
+            Err(e) => event!(Level::WARN, "Output to {} failed, {}", peer, e),
+        }
+    }
+}
+
+/// receive the datagrams of every client until the server is cancelled
+pub(crate) async fn udp_handler(channel: Arc<UdpChannel>, cancellation_token: CancellationToken) {
+    event!(Level::INFO, "UDP side channel started on {}", channel.port);
+    let mut buf = vec![0u8; u16::MAX as usize];
+    loop {
+        let received = tokio::select! {
+            received = channel.socket.recv_from(&mut buf) => received,
+            _ = cancellation_token.cancelled() => break,
+        };
+        let (len, peer) = match received {
+            Ok(received) => received,
+            Err(e) => {
+                // such as the icmp error of a datagram sent to a closed client
+                event!(Level::WARN, "UDP receive failed, {}", e);
+                continue;
+            }
+        };
+        let datagram = match UdpDatagram::decode(&buf[..len]) {
+            Ok(datagram) => datagram,
+            Err(e) => {
+                event!(Level::DEBUG, "Datagram from {} dropped, due to {}", peer, e);
+                continue;
+            }
+        };
+        channel.receive(datagram, peer).await;
+    }
+}
@@ -0,0 +1,225 @@
+use crate::{
+    handler::{client_handler, handshake, ServerCfg},
+    system::System,
+};
+use anyhow::Result;
+use fly_ruler_codec::{Decoder, Encoder, Feature, ServiceCall, ServiceCallResponse};
+use fly_ruler_core::core::PlaneInitCfg;
+use fly_ruler_utils::{error::FrError, CancellationToken};
+use futures_util::{future, SinkExt, StreamExt};
+use std::{net::SocketAddr, sync::Arc};
+use tokio::{
+    io::{AsyncRead, AsyncWrite},
+    net::TcpListener,
+};
+use tokio_rustls::TlsAcceptor;
+use tokio_tungstenite::tungstenite::{
+    handshake::server::{Request, Response},
+    http::HeaderValue,
+    protocol::WebSocketConfig,
+    Error as WsError, Message,
+};
+use tracing::{event, Level};
+
+/// subprotocol of the clients which want json responses, such as javascript dashboards
+pub const JSON_PROTOCOL: &str = "fly_ruler.json";
+
+/// websocket messages are already delimited and checked, so only these features are negotiated
+const WS_FEATURES: &[Feature] = &[Feature::Batching, Feature::Subscription];
+
+/// accept websocket clients on `addr`, every message carries one `ServiceCall` or `ServiceCallResponse`
+/// binary messages are protobuf encoded and text messages json encoded,
+/// the responses are json encoded if the client asked for the `fly_ruler.json` subprotocol
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn websocket_handler(
+    addr: String,
+    acceptor: Option<TlsAcceptor>,
+    server_cfg: ServerCfg,
+    init_cfg: PlaneInitCfg,
+    system: Arc<std::sync::Mutex<System>>,
+    controller_buffer: usize,
+    cancellation_token: CancellationToken,
+) -> Result<()> {
+    let listener = TcpListener::bind(&addr).await?;
+    event!(Level::INFO, "WebSocket server started on {}", addr);
+
+    loop {
+        let (stream, client_addr) = tokio::select! {
+            accepted = listener.accept() => accepted?,
+            _ = cancellation_token.cancelled() => break,
+        };
+        event!(
+            Level::INFO,
+            "Accepted websocket connection from {}",
+            client_addr
+        );
+
+        let acceptor = acceptor.clone();
+        let server_cfg = server_cfg.clone();
+        let system = system.clone();
+        let cancellation_token = cancellation_token.clone();
+        tokio::spawn(async move {
+            let Some(acceptor) = acceptor else {
+                websocket_client_handler(
+                    stream,
+                    client_addr,
+                    server_cfg,
+                    init_cfg,
+                    system,
+                    controller_buffer,
+                    cancellation_token,
+                )
+                .await;
+                return;
+            };
+            match acceptor.accept(stream).await {
+                Ok(stream) => {
+                    websocket_client_handler(
+                        stream,
+                        client_addr,
+                        server_cfg,
+                        init_cfg,
+                        system,
+                        controller_buffer,
+                        cancellation_token,
+                    )
+                    .await
+                }
+                Err(e) => event!(
+                    Level::WARN,
+                    "Client: {} tls handshake failed, due to {}",
+                    client_addr,
+                    e
+                ),
+            }
+        });
+    }
+    Ok(())
+}
+
+/// upgrade a connection to websocket and serve the client until it disconnects
+#[allow(clippy::too_many_arguments)]
+async fn websocket_client_handler<S>(
+    stream: S,
+    client_addr: SocketAddr,
+    server_cfg: ServerCfg,
+    init_cfg: PlaneInitCfg,
+    system: Arc<std::sync::Mutex<System>>,
+    controller_buffer: usize,
+    cancellation_token: CancellationToken,
+) where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    let mut json = false;
+    let config = WebSocketConfig {
+        max_message_size: Some(server_cfg.max_frame_size),
+        max_frame_size: Some(server_cfg.max_frame_size),
+        ..Default::default()
+    };
+    // the error response is defined by tungstenite
+    #[allow(clippy::result_large_err)]
+    let select_protocol = |request: &Request, mut response: Response| {
+        let protocols = request
+            .headers()
+            .get("Sec-WebSocket-Protocol")
+            .and_then(|p| p.to_str().ok())
+            .unwrap_or_default();
+        if protocols.split(',').any(|p| p.trim() == JSON_PROTOCOL) {
+            json = true;
+            response.headers_mut().insert(
+                "Sec-WebSocket-Protocol",
+                HeaderValue::from_static(JSON_PROTOCOL),
+            );
+        }
+        Ok(response)
+    };
+    let ws = tokio_tungstenite::accept_hdr_async_with_config(stream, select_protocol, Some(config))
+        .await;
+    let ws = match ws {
+        Ok(ws) => ws,
+        Err(e) => {
+            event!(
+                Level::WARN,
+                "Client: {} websocket upgrade failed, due to {}",
+                client_addr,
+                e
+            );
+            return;
+        }
+    };
+
+    let (writer, reader) = ws.split();
+    let mut reader = reader
+        .take_while(|msg| {
+            future::ready(!matches!(
+                msg,
+                Ok(Message::Close(_)) | Err(WsError::ConnectionClosed | WsError::AlreadyClosed)
+            ))
+        })
+        .filter_map(|msg| future::ready(decode(msg)));
+    let mut writer = writer
+        .sink_map_err(|e| FrError::Codec(e.to_string()))
+        .with(move |response| future::ready(encode(response, json)));
+
+    let hello = handshake(
+        client_addr,
+        server_cfg.tick_timeout,
+        0,
+        WS_FEATURES,
+        &server_cfg.auth,
+        &server_cfg.rooms,
+        &mut reader,
+        &mut writer,
+    )
+    .await;
+    let (hello, _, room) = match hello {
+        Ok(hello) => hello,
+        Err(e) => {
+            event!(
+                Level::WARN,
+                "Client: {} rejected, due to {}",
+                client_addr,
+                e
+            );
+            return;
+        }
+    };
+
+    client_handler(
+        client_addr,
+        hello,
+        room,
+        reader,
+        writer,
+        server_cfg,
+        init_cfg,
+        system,
+        controller_buffer,
+        // browsers can not open udp sockets
+        None,
+        cancellation_token,
+    )
+    .await;
+}
+
+/// a call of a binary or text message, control messages are skipped
+fn decode(msg: Result<Message, WsError>) -> Option<Result<ServiceCall, FrError>> {
+    match msg {
+        Ok(Message::Binary(data)) => Some(ServiceCall::decode(&data)),
+        Ok(Message::Text(text)) => {
+            Some(serde_json::from_str(&text).map_err(|e| FrError::Codec(e.to_string())))
+        }
+        Ok(_) => None,
+        Err(e) => Some(Err(FrError::Codec(e.to_string()))),
+    }
+}
+
+fn encode(response: ServiceCallResponse, json: bool) -> Result<Message, FrError> {
+    if json {
+        serde_json::to_string(&response)
+            .map(Message::Text)
+            .map_err(|e| FrError::Codec(e.to_string()))
+    } else {
+        response.encode().map(Message::Binary)
+    }
+}
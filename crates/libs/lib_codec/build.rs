@@ -5,8 +5,16 @@ fn main() -> Result<()> {
     config
         .protoc_arg("--proto_path")
         .protoc_arg("../../../proto")
+        // outputs are much larger than controls, both are sent one by one anyway
+        .enum_attribute(
+            ".service.UdpDatagram.payload",
+            "#[allow(clippy::large_enum_variant)]",
+        );
+    tonic_build::configure()
+        .build_client(false)
         .out_dir("src/generated")
-        .compile_protos(
+        .compile_with_config(
+            config,
             &[
                 "service.proto",
                 "id.proto",
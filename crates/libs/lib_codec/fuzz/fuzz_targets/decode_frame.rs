@@ -0,0 +1,16 @@
+#![no_main]
+
+use fly_ruler_codec::{RequestFrame, ResponseFrame};
+use libfuzzer_sys::fuzz_target;
+use tokio_util::{bytes::BytesMut, codec::Decoder};
+
+// arbitrary bytes must never panic the decoders nor allocate more than the limit
+fuzz_target!(|data: &[u8]| {
+    let mut src = BytesMut::from(data);
+    let mut frame = RequestFrame::new(64 * 1024);
+    while let Ok(Some(_)) = frame.decode(&mut src) {}
+
+    let mut src = BytesMut::from(data);
+    let mut frame = ResponseFrame::new(64 * 1024);
+    while let Ok(Some(_)) = frame.decode(&mut src) {}
+});
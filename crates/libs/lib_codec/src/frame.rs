@@ -1,44 +1,100 @@
-use crate::{Decoder, Encoder, ServiceCall, ServiceCallResponse};
+use crate::{Compression, Decoder, Encoder, ServiceCall, ServiceCallResponse};
 use fly_ruler_utils::error::{FrError, FrResult};
 use prost::bytes::BufMut;
+use std::io::Read;
 use tokio_util::bytes::BytesMut;
 
+/// the largest frame the head can describe
+pub const MAX_FRAME_SIZE: usize = Frame::LEN_MASK as usize;
+
+/// the largest frame accepted if no limit is set
+pub const DEFAULT_MAX_FRAME_SIZE: usize = 16 * 1024 * 1024; // 16MB
+
+/// a frame is a 4 bytes big endian head, the payload and an optional crc32 trailer,
+/// the 2 high bits of the head are the codec of the payload, the next one tells if the trailer follows,
+/// the others are the length of the payload
 #[derive(Debug, Clone)]
-struct Frame;
+struct Frame {
+    compression: Compression,
+    threshold: usize,
+    checksum: bool,
+    max_size: usize,
+}
 
-impl Frame {
-    const MAX_SIZE: usize = 1024 * 1024 * 1024; // 1GB
+impl Default for Frame {
+    fn default() -> Self {
+        Self {
+            compression: Compression::None,
+            threshold: 0,
+            checksum: false,
+            max_size: DEFAULT_MAX_FRAME_SIZE,
+        }
+    }
 }
 
-#[derive(Debug, Clone)]
-pub struct RequestFrame;
+impl Frame {
+    const LEN_MASK: u32 = (1 << 29) - 1;
+    const CHECKSUM_FLAG: u32 = 1 << 29;
+    const ZSTD_LEVEL: i32 = 3;
 
-#[derive(Debug, Clone)]
-pub struct ResponseFrame;
+    fn flag(compression: Compression) -> u32 {
+        match compression {
+            Compression::None => 0,
+            Compression::Zstd => 1,
+            Compression::Lz4 => 2,
+        }
+    }
 
-impl tokio_util::codec::Encoder<ServiceCall> for RequestFrame {
-    type Error = FrError;
+    fn compression(flag: u32) -> FrResult<Compression> {
+        match flag {
+            0 => Ok(Compression::None),
+            1 => Ok(Compression::Zstd),
+            2 => Ok(Compression::Lz4),
+            _ => Err(FrError::Codec(format!("unknown frame codec {}", flag))),
+        }
+    }
 
-    fn encode(&mut self, item: ServiceCall, dst: &mut BytesMut) -> FrResult<()> {
-        let data = item.encode()?;
-        let data = data.as_slice();
+    fn too_large(&self, len: usize) -> FrError {
+        FrError::Codec(format!(
+            "frame of {} bytes exceeds the limit of {} bytes",
+            len, self.max_size
+        ))
+    }
+
+    fn encode(&self, data: Vec<u8>, dst: &mut BytesMut) -> FrResult<()> {
+        if data.len() > self.max_size {
+            return Err(self.too_large(data.len()));
+        }
+
+        let compression = if data.len() < self.threshold {
+            Compression::None
+        } else {
+            self.compression
+        };
+        let data = match compression {
+            Compression::None => data,
+            Compression::Zstd => zstd::bulk::compress(&data, Frame::ZSTD_LEVEL)?,
+            Compression::Lz4 => lz4_flex::compress_prepend_size(&data),
+        };
         let data_len = data.len();
-        if data_len > Frame::MAX_SIZE {
-            return Err(FrError::Io(std::io::ErrorKind::InvalidData.into()));
+        if data_len > self.max_size {
+            return Err(self.too_large(data_len));
         }
 
-        dst.reserve(data_len + 4);
-        dst.put_u32(data_len as u32);
-        dst.extend_from_slice(data);
+        let mut head = (Frame::flag(compression) << 30) | data_len as u32;
+        if self.checksum {
+            head |= Frame::CHECKSUM_FLAG;
+        }
+        dst.reserve(data_len + 8);
+        dst.put_u32(head);
+        dst.extend_from_slice(&data);
+        if self.checksum {
+            dst.put_u32(crc32fast::hash(&data));
+        }
         Ok(())
     }
-}
-
-impl tokio_util::codec::Decoder for RequestFrame {
-    type Item = ServiceCall;
-    type Error = FrError;
 
-    fn decode(&mut self, src: &mut BytesMut) -> FrResult<Option<Self::Item>> {
+    fn decode(&self, src: &mut BytesMut) -> FrResult<Option<BytesMut>> {
         let buf_len = src.len();
         if buf_len < 4 {
             return Ok(None);
@@ -46,41 +102,143 @@ impl tokio_util::codec::Decoder for RequestFrame {
 
         let mut head = [0u8; 4];
         head.copy_from_slice(&src[..4]);
-        let data_len = u32::from_be_bytes(head) as usize;
+        let head = u32::from_be_bytes(head);
+        let compression = Frame::compression(head >> 30)?;
+        let checksum = head & Frame::CHECKSUM_FLAG != 0;
+        let data_len = (head & Frame::LEN_MASK) as usize;
 
-        if data_len > Frame::MAX_SIZE {
-            return Err(FrError::Io(std::io::ErrorKind::InvalidData.into()));
+        // checked before reserving, so that a corrupt head can not allocate more than the limit
+        if data_len > self.max_size {
+            return Err(self.too_large(data_len));
         }
 
-        let frame_len = data_len + 4;
+        let frame_len = data_len + if checksum { 8 } else { 4 };
 
         if buf_len < frame_len {
             src.reserve(frame_len - buf_len);
             return Ok(None);
         }
 
-        let body = src.split_to(data_len as usize + 4);
-        let item = ServiceCall::decode(&body[4..])?;
-        Ok(Some(item))
+        let mut data = src.split_to(frame_len).split_off(4);
+        if checksum {
+            let trailer = data.split_off(data_len);
+            let expected = u32::from_be_bytes([trailer[0], trailer[1], trailer[2], trailer[3]]);
+            let actual = crc32fast::hash(&data);
+            if actual != expected {
+                return Err(FrError::Codec(format!(
+                    "frame checksum mismatch, expected {:08x}, got {:08x}",
+                    expected, actual
+                )));
+            }
+        }
+
+        let data = match compression {
+            Compression::None => return Ok(Some(data)),
+            Compression::Zstd => {
+                let mut out = Vec::new();
+                zstd::stream::read::Decoder::new(&data[..])?
+                    .take(self.max_size as u64 + 1)
+                    .read_to_end(&mut out)
+                    .map_err(|e| FrError::Codec(format!("invalid zstd frame, {}", e)))?;
+                out
+            }
+            Compression::Lz4 => {
+                // the size is prepended in little endian, check it before allocating
+                if data.len() < 4 {
+                    return Err(FrError::Codec("truncated lz4 frame".to_string()));
+                }
+                let size = u32::from_le_bytes([data[0], data[1], data[2], data[3]]) as usize;
+                if size > self.max_size {
+                    return Err(self.too_large(size));
+                }
+                lz4_flex::decompress_size_prepended(&data)
+                    .map_err(|e| FrError::Codec(format!("invalid lz4 frame, {}", e)))?
+            }
+        };
+        if data.len() > self.max_size {
+            return Err(self.too_large(data.len()));
+        }
+        Ok(Some(BytesMut::from(&data[..])))
     }
 }
 
-impl tokio_util::codec::Encoder<ServiceCallResponse> for ResponseFrame {
+/// frames of calls, compressed frames and checksums are always accepted
+#[derive(Debug, Clone, Default)]
+pub struct RequestFrame(Frame);
+
+/// frames of responses, compressed frames and checksums are always accepted
+#[derive(Debug, Clone, Default)]
+pub struct ResponseFrame(Frame);
+
+impl RequestFrame {
+    /// frames larger than `max_size` bytes are rejected both ways, at most `MAX_FRAME_SIZE`
+    pub fn new(max_size: usize) -> Self {
+        Self(Frame {
+            max_size: max_size.min(MAX_FRAME_SIZE),
+            ..Default::default()
+        })
+    }
+
+    /// compress the frames which are not smaller than `threshold` bytes
+    pub fn set_compression(&mut self, compression: Compression, threshold: usize) {
+        self.0.compression = compression;
+        self.0.threshold = threshold;
+    }
+
+    /// append a crc32 trailer to the frames
+    pub fn set_checksum(&mut self, checksum: bool) {
+        self.0.checksum = checksum;
+    }
+}
+
+impl ResponseFrame {
+    /// frames larger than `max_size` bytes are rejected both ways, at most `MAX_FRAME_SIZE`
+    pub fn new(max_size: usize) -> Self {
+        Self(Frame {
+            max_size: max_size.min(MAX_FRAME_SIZE),
+            ..Default::default()
+        })
+    }
+
+    /// compress the frames which are not smaller than `threshold` bytes
+    pub fn set_compression(&mut self, compression: Compression, threshold: usize) {
+        self.0.compression = compression;
+        self.0.threshold = threshold;
+    }
+
+    /// append a crc32 trailer to the frames
+    pub fn set_checksum(&mut self, checksum: bool) {
+        self.0.checksum = checksum;
+    }
+}
+
+impl tokio_util::codec::Encoder<ServiceCall> for RequestFrame {
     type Error = FrError;
 
-    fn encode(&mut self, item: ServiceCallResponse, dst: &mut BytesMut) -> FrResult<()> {
+    fn encode(&mut self, item: ServiceCall, dst: &mut BytesMut) -> FrResult<()> {
         let data = item.encode()?;
-        let data = data.as_slice();
+        self.0.encode(data, dst)
+    }
+}
 
-        let data_len = data.len();
-        if data_len > Frame::MAX_SIZE {
-            return Err(FrError::Io(std::io::ErrorKind::InvalidData.into()));
+impl tokio_util::codec::Decoder for RequestFrame {
+    type Item = ServiceCall;
+    type Error = FrError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> FrResult<Option<Self::Item>> {
+        match self.0.decode(src)? {
+            Some(data) => Ok(Some(ServiceCall::decode(&data)?)),
+            None => Ok(None),
         }
+    }
+}
 
-        dst.reserve(data_len + 4);
-        dst.put_u32(data_len as u32);
-        dst.extend_from_slice(data);
-        Ok(())
+impl tokio_util::codec::Encoder<ServiceCallResponse> for ResponseFrame {
+    type Error = FrError;
+
+    fn encode(&mut self, item: ServiceCallResponse, dst: &mut BytesMut) -> FrResult<()> {
+        let data = item.encode()?;
+        self.0.encode(data, dst)
     }
 }
 
@@ -89,29 +247,131 @@ impl tokio_util::codec::Decoder for ResponseFrame {
     type Error = FrError;
 
     fn decode(&mut self, src: &mut BytesMut) -> FrResult<Option<Self::Item>> {
-        let buf_len = src.len();
+        match self.0.decode(src)? {
+            Some(data) => Ok(Some(ServiceCallResponse::decode(&data)?)),
+            None => Ok(None),
+        }
+    }
+}
 
-        if buf_len < 4 {
-            return Ok(None);
+#[cfg(test)]
+mod codec_frame_tests {
+    use super::*;
+    use crate::{Args, PlaneRequest, Response};
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+    use tokio_util::codec::{Decoder as _, Encoder as _};
+
+    fn call(len: usize) -> ServiceCall {
+        ServiceCall {
+            name: "RemovePlane".to_string(),
+            request_id: 42,
+            args: Some(Args::RemovePlane(PlaneRequest {
+                plane_id: Some("p".repeat(len)),
+            })),
         }
+    }
 
-        let mut head = [0u8; 4];
-        head.copy_from_slice(&src[..4]);
-        let data_len = u32::from_be_bytes(head) as usize;
+    fn frames() -> Vec<RequestFrame> {
+        let mut frames = Vec::new();
+        for compression in [Compression::None, Compression::Zstd, Compression::Lz4] {
+            for checksum in [false, true] {
+                let mut frame = RequestFrame::new(4096);
+                frame.set_compression(compression, 64);
+                frame.set_checksum(checksum);
+                frames.push(frame);
+            }
+        }
+        frames
+    }
 
-        if data_len > Frame::MAX_SIZE {
-            return Err(FrError::Io(std::io::ErrorKind::InvalidData.into()));
+    #[test]
+    fn test_round_trip() {
+        for mut frame in frames() {
+            for len in [0, 10, 1000] {
+                let mut buf = BytesMut::new();
+                frame.encode(call(len), &mut buf).unwrap();
+                // fed byte by byte, the call is only decoded once complete
+                let mut src = BytesMut::new();
+                let mut decoded = None;
+                for b in buf.iter() {
+                    assert!(decoded.is_none());
+                    src.put_u8(*b);
+                    decoded = frame.decode(&mut src).unwrap();
+                }
+                let decoded = decoded.unwrap();
+                assert_eq!(decoded.request_id, 42);
+                assert!(src.is_empty());
+                match decoded.args {
+                    Some(Args::RemovePlane(r)) => assert_eq!(r.plane_id.unwrap().len(), len),
+                    _ => panic!("unexpected args"),
+                }
+            }
         }
+    }
 
-        let frame_len = data_len + 4;
+    #[test]
+    fn test_max_size() {
+        let mut frame = RequestFrame::new(1024);
+        let mut buf = BytesMut::new();
+        assert!(frame.encode(call(2048), &mut buf).is_err());
 
-        if buf_len < frame_len {
-            src.reserve(frame_len - buf_len);
-            return Ok(None);
+        // a huge length is rejected before anything is reserved
+        let mut src = BytesMut::new();
+        src.put_u32(Frame::LEN_MASK);
+        assert!(frame.decode(&mut src).is_err());
+        assert!(src.capacity() < 1024);
+
+        // a compressed frame which inflates over the limit is rejected
+        let mut large = ResponseFrame::new(1024 * 1024);
+        large.set_compression(Compression::Zstd, 0);
+        let mut buf = BytesMut::new();
+        let response = ServiceCallResponse {
+            name: "LostPlane".to_string(),
+            request_id: 0,
+            response: Some(Response::LostPlane("p".repeat(4096))),
+        };
+        large.encode(response, &mut buf).unwrap();
+        assert!(buf.len() < 1024);
+        assert!(ResponseFrame::new(1024).decode(&mut buf).is_err());
+    }
+
+    #[test]
+    fn test_checksum() {
+        let mut frame = RequestFrame::default();
+        frame.set_checksum(true);
+        let mut buf = BytesMut::new();
+        frame.encode(call(100), &mut buf).unwrap();
+        let last = buf.len() - 1;
+        buf[last] ^= 0xff;
+        assert!(frame.decode(&mut buf).is_err());
+    }
+
+    #[test]
+    fn test_fuzz_random_input() {
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        for _ in 0..2000 {
+            let len = rng.gen_range(0..256);
+            let mut src: BytesMut = (0..len).map(|_| rng.gen::<u8>()).collect();
+            let mut frame = RequestFrame::new(4096);
+            // never panics and never reserves more than the limit
+            while let Ok(Some(_)) = frame.decode(&mut src) {}
+            assert!(src.capacity() <= 2 * (4096 + 8));
         }
+    }
 
-        let body = src.split_to(data_len as usize + 4);
-        let item = ServiceCallResponse::decode(&body[4..])?;
-        Ok(Some(item))
+    #[test]
+    fn test_fuzz_mutated_frames() {
+        let mut rng = StdRng::seed_from_u64(0xf1a5);
+        for mut frame in frames() {
+            for _ in 0..300 {
+                let mut buf = BytesMut::new();
+                frame.encode(call(rng.gen_range(0..512)), &mut buf).unwrap();
+                for _ in 0..rng.gen_range(1..4) {
+                    let i = rng.gen_range(0..buf.len());
+                    buf[i] ^= rng.gen_range(1..=255u8);
+                }
+                let _ = frame.decode(&mut buf);
+            }
+        }
     }
 }
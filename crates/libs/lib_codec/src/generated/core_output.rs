@@ -24,3 +24,47 @@ pub struct CoreOutput {
     #[prost(message, optional, tag = "4")]
     pub state_extend: ::core::option::Option<super::state_extend::StateExtend>,
 }
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PlaneDiagnostic {
+    #[prost(message, optional, tag = "1")]
+    pub id: ::core::option::Option<super::id::Id>,
+    #[prost(double, tag = "2")]
+    pub time: f64,
+    #[prost(message, optional, tag = "3")]
+    pub diagnostic: ::core::option::Option<Diagnostic>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Diagnostic {
+    #[prost(message, optional, tag = "1")]
+    pub c: ::core::option::Option<Coefficient>,
+    #[prost(double, repeated, tag = "2")]
+    pub aero_force: ::prost::alloc::vec::Vec<f64>,
+    #[prost(double, repeated, tag = "3")]
+    pub thrust_force: ::prost::alloc::vec::Vec<f64>,
+    #[prost(double, repeated, tag = "4")]
+    pub gravity_force: ::prost::alloc::vec::Vec<f64>,
+    #[prost(double, repeated, tag = "5")]
+    pub aero_moment: ::prost::alloc::vec::Vec<f64>,
+    #[prost(double, repeated, tag = "6")]
+    pub thrust_moment: ::prost::alloc::vec::Vec<f64>,
+    #[prost(double, repeated, tag = "7")]
+    pub angular_accel: ::prost::alloc::vec::Vec<f64>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Coefficient {
+    #[prost(double, tag = "1")]
+    pub c_x: f64,
+    #[prost(double, tag = "2")]
+    pub c_z: f64,
+    #[prost(double, tag = "3")]
+    pub c_m: f64,
+    #[prost(double, tag = "4")]
+    pub c_y: f64,
+    #[prost(double, tag = "5")]
+    pub c_n: f64,
+    #[prost(double, tag = "6")]
+    pub c_l: f64,
+}
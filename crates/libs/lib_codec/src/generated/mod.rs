@@ -1,8 +1,8 @@
-pub(crate) mod control;
-pub(crate) mod core_output;
-pub(crate) mod id;
-pub(crate) mod plane_init_cfg;
-pub(crate) mod plugin;
-pub(crate) mod service;
-pub(crate) mod state;
-pub(crate) mod state_extend;
+pub mod control;
+pub mod core_output;
+pub mod id;
+pub mod plane_init_cfg;
+pub mod plugin;
+pub mod service;
+pub mod state;
+pub mod state_extend;
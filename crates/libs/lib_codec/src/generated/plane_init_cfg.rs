@@ -11,6 +11,14 @@ pub struct PlaneInitCfg {
     pub flight_condition: ::core::option::Option<i32>,
     #[prost(message, optional, tag = "5")]
     pub optim_options: ::core::option::Option<NelderMeadOptions>,
+    #[prost(bool, optional, tag = "6")]
+    pub diagnostic: ::core::option::Option<bool>,
+    #[prost(message, optional, tag = "7")]
+    pub constants: ::core::option::Option<PlaneConstantsOverride>,
+    #[prost(message, optional, tag = "8")]
+    pub ctrl_limit: ::core::option::Option<ControlLimitOverride>,
+    #[prost(message, optional, tag = "9")]
+    pub init_state: ::core::option::Option<InitState>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -44,6 +52,14 @@ pub struct TrimInit {
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct InitState {
+    #[prost(message, optional, tag = "1")]
+    pub state: ::core::option::Option<super::state::State>,
+    #[prost(message, optional, tag = "2")]
+    pub control: ::core::option::Option<super::control::Control>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct NelderMeadOptions {
     #[prost(uint32, tag = "1")]
     pub max_fun_evals: u32,
@@ -54,6 +70,154 @@ pub struct NelderMeadOptions {
     #[prost(double, tag = "4")]
     pub tol_x: f64,
 }
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PlaneConstantsOverride {
+    #[prost(double, optional, tag = "1")]
+    pub m: ::core::option::Option<f64>,
+    #[prost(double, optional, tag = "2")]
+    pub b: ::core::option::Option<f64>,
+    #[prost(double, optional, tag = "3")]
+    pub s: ::core::option::Option<f64>,
+    #[prost(double, optional, tag = "4")]
+    pub c_bar: ::core::option::Option<f64>,
+    #[prost(double, optional, tag = "5")]
+    pub x_cg_r: ::core::option::Option<f64>,
+    #[prost(double, optional, tag = "6")]
+    pub x_cg: ::core::option::Option<f64>,
+    #[prost(double, optional, tag = "7")]
+    pub h_eng: ::core::option::Option<f64>,
+    #[prost(double, optional, tag = "8")]
+    pub j_y: ::core::option::Option<f64>,
+    #[prost(double, optional, tag = "9")]
+    pub j_xz: ::core::option::Option<f64>,
+    #[prost(double, optional, tag = "10")]
+    pub j_z: ::core::option::Option<f64>,
+    #[prost(double, optional, tag = "11")]
+    pub j_x: ::core::option::Option<f64>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ControlLimitOverride {
+    #[prost(double, optional, tag = "1")]
+    pub thrust_cmd_limit_top: ::core::option::Option<f64>,
+    #[prost(double, optional, tag = "2")]
+    pub thrust_cmd_limit_bottom: ::core::option::Option<f64>,
+    #[prost(double, optional, tag = "3")]
+    pub thrust_rate_limit: ::core::option::Option<f64>,
+    #[prost(double, optional, tag = "4")]
+    pub ele_cmd_limit_top: ::core::option::Option<f64>,
+    #[prost(double, optional, tag = "5")]
+    pub ele_cmd_limit_bottom: ::core::option::Option<f64>,
+    #[prost(double, optional, tag = "6")]
+    pub ele_rate_limit: ::core::option::Option<f64>,
+    #[prost(double, optional, tag = "7")]
+    pub ail_cmd_limit_top: ::core::option::Option<f64>,
+    #[prost(double, optional, tag = "8")]
+    pub ail_cmd_limit_bottom: ::core::option::Option<f64>,
+    #[prost(double, optional, tag = "9")]
+    pub ail_rate_limit: ::core::option::Option<f64>,
+    #[prost(double, optional, tag = "10")]
+    pub rud_cmd_limit_top: ::core::option::Option<f64>,
+    #[prost(double, optional, tag = "11")]
+    pub rud_cmd_limit_bottom: ::core::option::Option<f64>,
+    #[prost(double, optional, tag = "12")]
+    pub rud_rate_limit: ::core::option::Option<f64>,
+    #[prost(double, optional, tag = "13")]
+    pub alpha_limit_top: ::core::option::Option<f64>,
+    #[prost(double, optional, tag = "14")]
+    pub alpha_limit_bottom: ::core::option::Option<f64>,
+    #[prost(double, optional, tag = "15")]
+    pub beta_limit_top: ::core::option::Option<f64>,
+    #[prost(double, optional, tag = "16")]
+    pub beta_limit_bottom: ::core::option::Option<f64>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PlaneConstants {
+    #[prost(double, tag = "1")]
+    pub m: f64,
+    #[prost(double, tag = "2")]
+    pub b: f64,
+    #[prost(double, tag = "3")]
+    pub s: f64,
+    #[prost(double, tag = "4")]
+    pub c_bar: f64,
+    #[prost(double, tag = "5")]
+    pub x_cg_r: f64,
+    #[prost(double, tag = "6")]
+    pub x_cg: f64,
+    #[prost(double, tag = "7")]
+    pub h_eng: f64,
+    #[prost(double, tag = "8")]
+    pub j_y: f64,
+    #[prost(double, tag = "9")]
+    pub j_xz: f64,
+    #[prost(double, tag = "10")]
+    pub j_z: f64,
+    #[prost(double, tag = "11")]
+    pub j_x: f64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ControlLimit {
+    #[prost(double, tag = "1")]
+    pub thrust_cmd_limit_top: f64,
+    #[prost(double, tag = "2")]
+    pub thrust_cmd_limit_bottom: f64,
+    #[prost(double, tag = "3")]
+    pub thrust_rate_limit: f64,
+    #[prost(double, tag = "4")]
+    pub ele_cmd_limit_top: f64,
+    #[prost(double, tag = "5")]
+    pub ele_cmd_limit_bottom: f64,
+    #[prost(double, tag = "6")]
+    pub ele_rate_limit: f64,
+    #[prost(double, tag = "7")]
+    pub ail_cmd_limit_top: f64,
+    #[prost(double, tag = "8")]
+    pub ail_cmd_limit_bottom: f64,
+    #[prost(double, tag = "9")]
+    pub ail_rate_limit: f64,
+    #[prost(double, tag = "10")]
+    pub rud_cmd_limit_top: f64,
+    #[prost(double, tag = "11")]
+    pub rud_cmd_limit_bottom: f64,
+    #[prost(double, tag = "12")]
+    pub rud_rate_limit: f64,
+    #[prost(double, tag = "13")]
+    pub alpha_limit_top: f64,
+    #[prost(double, tag = "14")]
+    pub alpha_limit_bottom: f64,
+    #[prost(double, tag = "15")]
+    pub beta_limit_top: f64,
+    #[prost(double, tag = "16")]
+    pub beta_limit_bottom: f64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct NelderMeadResult {
+    #[prost(double, repeated, tag = "1")]
+    pub x: ::prost::alloc::vec::Vec<f64>,
+    #[prost(double, tag = "2")]
+    pub fval: f64,
+    #[prost(uint32, tag = "3")]
+    pub iter: u32,
+    #[prost(uint32, tag = "4")]
+    pub fun_evals: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TrimOutput {
+    #[prost(message, optional, tag = "1")]
+    pub state: ::core::option::Option<super::state::State>,
+    #[prost(message, optional, tag = "2")]
+    pub control: ::core::option::Option<super::control::Control>,
+    #[prost(message, optional, tag = "3")]
+    pub state_extend: ::core::option::Option<super::state_extend::StateExtend>,
+    #[prost(message, optional, tag = "4")]
+    pub nelder_mead_result: ::core::option::Option<NelderMeadResult>,
+}
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
 pub enum FlightCondition {
@@ -25,13 +25,310 @@ pub struct SendControlRequest {
 pub struct PushPlaneResponse {
     #[prost(message, optional, tag = "1")]
     pub plane_id: ::core::option::Option<super::id::Id>,
+    #[prost(message, optional, tag = "2")]
+    pub trim_output: ::core::option::Option<super::plane_init_cfg::TrimOutput>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TrimRequest {
+    #[prost(message, optional, tag = "1")]
+    pub model_id: ::core::option::Option<super::id::Id>,
+    #[prost(message, optional, tag = "2")]
+    pub plane_init_cfg: ::core::option::Option<super::plane_init_cfg::PlaneInitCfg>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TrimResponse {
+    #[prost(message, optional, tag = "1")]
+    pub model_id: ::core::option::Option<super::id::Id>,
+    #[prost(message, optional, tag = "2")]
+    pub trim_output: ::core::option::Option<super::plane_init_cfg::TrimOutput>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PlaneRequest {
+    #[prost(message, optional, tag = "1")]
+    pub plane_id: ::core::option::Option<super::id::Id>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetTimeScaleRequest {
+    #[prost(message, optional, tag = "1")]
+    pub plane_id: ::core::option::Option<super::id::Id>,
+    #[prost(double, tag = "2")]
+    pub time_scale: f64,
+}
+/// planes affected by the request
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PlaneResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub plane_ids: ::prost::alloc::vec::Vec<super::id::Id>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PlaneEvent {
+    #[prost(message, optional, tag = "1")]
+    pub plane_id: ::core::option::Option<super::id::Id>,
+    #[prost(enumeration = "PlaneEventKind", tag = "2")]
+    pub kind: i32,
+    #[prost(double, optional, tag = "3")]
+    pub time_scale: ::core::option::Option<f64>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PlaneInfo {
+    #[prost(message, optional, tag = "1")]
+    pub plane_id: ::core::option::Option<super::id::Id>,
+    #[prost(message, optional, tag = "2")]
+    pub model_id: ::core::option::Option<super::id::Id>,
+    /// address of the client which owns the plane
+    #[prost(string, tag = "3")]
+    pub owner: ::prost::alloc::string::String,
+    #[prost(enumeration = "PlaneStatus", tag = "4")]
+    pub status: i32,
+    #[prost(double, tag = "5")]
+    pub time: f64,
+    /// clients allowed to control the plane besides the owner
+    #[prost(string, repeated, tag = "6")]
+    pub pilots: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListPlanesResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub planes: ::prost::alloc::vec::Vec<PlaneInfo>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetPlaneStateRequest {
+    #[prost(message, optional, tag = "1")]
+    pub plane_id: ::core::option::Option<super::id::Id>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetModelDetailsRequest {
+    #[prost(message, optional, tag = "1")]
+    pub model_id: ::core::option::Option<super::id::Id>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetModelDetailsResponse {
+    #[prost(message, optional, tag = "1")]
+    pub model_info: ::core::option::Option<super::plugin::PluginInfoTuple>,
+    #[prost(message, optional, tag = "2")]
+    pub constants: ::core::option::Option<super::plane_init_cfg::PlaneConstants>,
+    #[prost(message, optional, tag = "3")]
+    pub control_limit: ::core::option::Option<super::plane_init_cfg::ControlLimit>,
+}
+/// a failed request, the connection and planes of the client are kept
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ErrorResponse {
+    #[prost(enumeration = "ErrorCode", tag = "1")]
+    pub code: i32,
+    #[prost(string, tag = "2")]
+    pub message: ::prost::alloc::string::String,
+    /// name of the request which failed
+    #[prost(string, tag = "3")]
+    pub request: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "4")]
+    pub request_id: u64,
+}
+/// first call of every connection
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Hello {
+    #[prost(uint32, tag = "1")]
+    pub protocol_version: u32,
+    #[prost(string, tag = "2")]
+    pub client_name: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub client_version: ::prost::alloc::string::String,
+    #[prost(enumeration = "ClientRole", tag = "4")]
+    pub role: i32,
+    /// features supported by the client
+    #[prost(enumeration = "Feature", repeated, tag = "5")]
+    pub features: ::prost::alloc::vec::Vec<i32>,
+    /// codecs supported by the client, the preferred one first
+    #[prost(enumeration = "Compression", repeated, tag = "6")]
+    pub compressions: ::prost::alloc::vec::Vec<i32>,
+    /// checked against the tokens of the server, if the server asks for one
+    #[prost(string, tag = "7")]
+    pub token: ::prost::alloc::string::String,
+    /// session of a previous connection, its planes are reclaimed if the session
+    /// has not expired
+    #[prost(string, tag = "8")]
+    pub session: ::prost::alloc::string::String,
+    /// world joined by the client, empty for the default one
+    #[prost(string, tag = "9")]
+    pub room: ::prost::alloc::string::String,
+    /// create the room if it does not exist, only an admin may
+    #[prost(bool, tag = "10")]
+    pub create_room: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HelloAck {
+    #[prost(bool, tag = "1")]
+    pub accepted: bool,
+    /// why the client is rejected
+    #[prost(string, tag = "2")]
+    pub reason: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "3")]
+    pub protocol_version: u32,
+    #[prost(string, tag = "4")]
+    pub server_name: ::prost::alloc::string::String,
+    #[prost(string, tag = "5")]
+    pub server_version: ::prost::alloc::string::String,
+    /// features supported by both sides
+    #[prost(enumeration = "Feature", repeated, tag = "6")]
+    pub features: ::prost::alloc::vec::Vec<i32>,
+    /// codec used by both sides if `COMPRESSION` is negotiated
+    #[prost(enumeration = "Compression", tag = "7")]
+    pub compression: i32,
+    /// frames smaller than the threshold are sent uncompressed, in bytes
+    #[prost(uint32, tag = "8")]
+    pub compression_threshold: u32,
+    /// role granted to the client
+    #[prost(enumeration = "ClientRole", tag = "9")]
+    pub role: i32,
+    /// session of the client, empty if the server keeps no session
+    #[prost(string, tag = "10")]
+    pub session: ::prost::alloc::string::String,
+    /// planes reclaimed from the previous connection
+    #[prost(message, repeated, tag = "11")]
+    pub resumed_planes: ::prost::alloc::vec::Vec<super::id::Id>,
+    /// world joined by the client, empty for the default one
+    #[prost(string, tag = "12")]
+    pub room: ::prost::alloc::string::String,
+}
+/// planes whose outputs are forwarded to a client
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Subscription {
+    /// every plane, planes pushed later included
+    #[prost(bool, tag = "1")]
+    pub all: bool,
+    #[prost(message, repeated, tag = "2")]
+    pub plane_ids: ::prost::alloc::vec::Vec<super::id::Id>,
+    /// address of the clients whose planes are subscribed
+    #[prost(string, repeated, tag = "3")]
+    pub owners: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// forward one of every `decimation` outputs of a plane, 0 keeps the current
+    /// rate
+    #[prost(uint32, tag = "4")]
+    pub decimation: u32,
+    /// parts of the output to send, empty keeps the current mask
+    #[prost(enumeration = "OutputField", repeated, tag = "5")]
+    pub fields: ::prost::alloc::vec::Vec<i32>,
+}
+/// open the udp side channel of the client
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OpenUdpRequest {
+    /// send the subscribed outputs over udp instead of the session
+    #[prost(bool, tag = "1")]
+    pub outputs: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OpenUdpResponse {
+    /// udp port of the server
+    #[prost(uint32, tag = "1")]
+    pub port: u32,
+    /// identifies the client in every datagram
+    #[prost(uint64, tag = "2")]
+    pub token: u64,
+}
+/// give a plane to another connected client, only the owner may transfer it
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TransferPlaneRequest {
+    #[prost(message, optional, tag = "1")]
+    pub plane_id: ::core::option::Option<super::id::Id>,
+    /// address of the new owner, as listed by `ListPlanes`
+    #[prost(string, tag = "2")]
+    pub owner: ::prost::alloc::string::String,
+}
+/// let another client control a plane, or take the permission back
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SharePlaneRequest {
+    #[prost(message, optional, tag = "1")]
+    pub plane_id: ::core::option::Option<super::id::Id>,
+    /// address of the client
+    #[prost(string, tag = "2")]
+    pub pilot: ::prost::alloc::string::String,
+    #[prost(bool, tag = "3")]
+    pub revoke: bool,
+}
+/// one datagram of the udp side channel, the first datagram of the client
+/// registers its address and may carry no payload
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UdpDatagram {
+    #[prost(uint64, tag = "1")]
+    pub token: u64,
+    /// increasing for every datagram of a sender, stale controls are dropped
+    #[prost(uint64, tag = "2")]
+    pub sequence: u64,
+    #[prost(oneof = "udp_datagram::Payload", tags = "3, 4")]
+    pub payload: ::core::option::Option<udp_datagram::Payload>,
+}
+/// Nested message and enum types in `UdpDatagram`.
+pub mod udp_datagram {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    #[allow(clippy::large_enum_variant)]
+    pub enum Payload {
+        #[prost(message, tag = "3")]
+        Control(super::SendControlRequest),
+        #[prost(message, tag = "4")]
+        Output(super::super::core_output::PlaneMessage),
+    }
+}
+/// the output queue of a client, filled according to the backpressure policy of
+/// the server
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ClientStatus {
+    /// address of the client
+    #[prost(string, tag = "1")]
+    pub addr: ::prost::alloc::string::String,
+    /// messages waiting to be sent
+    #[prost(uint64, tag = "2")]
+    pub queued: u64,
+    /// outputs dropped because the queue was full
+    #[prost(uint64, tag = "3")]
+    pub dropped: u64,
+    /// outputs replaced by a newer output of the same plane
+    #[prost(uint64, tag = "4")]
+    pub conflated: u64,
+    /// broadcast messages missed while the client lagged behind
+    #[prost(uint64, tag = "5")]
+    pub lagged: u64,
+}
+/// the calling client, and every client of the room for an admin
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetStatusResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub clients: ::prost::alloc::vec::Vec<ClientStatus>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ServiceCall {
     #[prost(string, tag = "1")]
     pub name: ::prost::alloc::string::String,
-    #[prost(oneof = "service_call::Args", tags = "3, 4, 5, 6, 7")]
+    /// chosen by the client and echoed back in the responses, 0 means no response
+    /// is expected
+    #[prost(uint64, tag = "2")]
+    pub request_id: u64,
+    #[prost(
+        oneof = "service_call::Args",
+        tags = "3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24"
+    )]
     pub args: ::core::option::Option<service_call::Args>,
 }
 /// Nested message and enum types in `ServiceCall`.
@@ -49,6 +346,41 @@ pub mod service_call {
         Tick(()),
         #[prost(message, tag = "7")]
         Disconnect(()),
+        #[prost(message, tag = "8")]
+        Trim(super::TrimRequest),
+        #[prost(message, tag = "9")]
+        RemovePlane(super::PlaneRequest),
+        #[prost(message, tag = "10")]
+        PausePlane(super::PlaneRequest),
+        #[prost(message, tag = "11")]
+        ResumePlane(super::PlaneRequest),
+        #[prost(message, tag = "12")]
+        ResetPlane(super::PlaneRequest),
+        #[prost(message, tag = "13")]
+        SetTimeScale(super::SetTimeScaleRequest),
+        #[prost(message, tag = "14")]
+        ListPlanes(()),
+        #[prost(message, tag = "15")]
+        GetPlaneState(super::GetPlaneStateRequest),
+        #[prost(message, tag = "16")]
+        GetModelDetails(super::GetModelDetailsRequest),
+        #[prost(message, tag = "17")]
+        Hello(super::Hello),
+        #[prost(message, tag = "18")]
+        Subscribe(super::Subscription),
+        #[prost(message, tag = "19")]
+        Unsubscribe(super::Subscription),
+        #[prost(message, tag = "20")]
+        OpenUdp(super::OpenUdpRequest),
+        #[prost(message, tag = "21")]
+        TransferPlane(super::TransferPlaneRequest),
+        #[prost(message, tag = "22")]
+        SharePlane(super::SharePlaneRequest),
+        /// keeps the client alive without ticking, answered with `pong`
+        #[prost(message, tag = "23")]
+        Ping(()),
+        #[prost(message, tag = "24")]
+        GetStatus(()),
     }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -56,7 +388,13 @@ pub mod service_call {
 pub struct ServiceCallResponse {
     #[prost(string, tag = "1")]
     pub name: ::prost::alloc::string::String,
-    #[prost(oneof = "service_call_response::Response", tags = "3, 4, 5, 6, 7, 8, 9")]
+    /// id of the request answered, 0 for broadcast messages
+    #[prost(uint64, tag = "2")]
+    pub request_id: u64,
+    #[prost(
+        oneof = "service_call_response::Response",
+        tags = "3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29"
+    )]
     pub response: ::core::option::Option<service_call_response::Response>,
 }
 /// Nested message and enum types in `ServiceCallResponse`.
@@ -76,7 +414,1247 @@ pub mod service_call_response {
         LostPlane(super::super::id::Id),
         #[prost(message, tag = "8")]
         NewPlane(super::super::id::Id),
-        #[prost(string, tag = "9")]
-        Error(::prost::alloc::string::String),
+        #[prost(message, tag = "9")]
+        Error(super::ErrorResponse),
+        #[prost(message, tag = "10")]
+        Diagnostic(super::super::core_output::PlaneDiagnostic),
+        #[prost(message, tag = "11")]
+        Trim(super::TrimResponse),
+        #[prost(message, tag = "12")]
+        RemovePlane(super::PlaneResponse),
+        #[prost(message, tag = "13")]
+        PausePlane(super::PlaneResponse),
+        #[prost(message, tag = "14")]
+        ResumePlane(super::PlaneResponse),
+        #[prost(message, tag = "15")]
+        ResetPlane(super::PlaneResponse),
+        #[prost(message, tag = "16")]
+        SetTimeScale(super::PlaneResponse),
+        #[prost(message, tag = "17")]
+        PlaneEvent(super::PlaneEvent),
+        #[prost(message, tag = "18")]
+        ListPlanes(super::ListPlanesResponse),
+        #[prost(message, tag = "19")]
+        GetPlaneState(super::super::core_output::PlaneMessage),
+        #[prost(message, tag = "20")]
+        GetModelDetails(super::GetModelDetailsResponse),
+        #[prost(message, tag = "21")]
+        Hello(super::HelloAck),
+        #[prost(message, tag = "22")]
+        Subscribe(super::Subscription),
+        #[prost(message, tag = "23")]
+        Unsubscribe(super::Subscription),
+        /// outputs sent within the batch interval, only if `BATCHING` is negotiated
+        #[prost(message, tag = "24")]
+        OutputGroup(super::super::core_output::PlaneMessageGroup),
+        #[prost(message, tag = "25")]
+        OpenUdp(super::OpenUdpResponse),
+        #[prost(message, tag = "26")]
+        TransferPlane(super::PlaneInfo),
+        #[prost(message, tag = "27")]
+        SharePlane(super::PlaneInfo),
+        #[prost(message, tag = "28")]
+        Pong(()),
+        #[prost(message, tag = "29")]
+        GetStatus(super::GetStatusResponse),
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum PlaneEventKind {
+    Removed = 0,
+    Paused = 1,
+    Resumed = 2,
+    Reset = 3,
+    TimeScaleChanged = 4,
+    /// the plane is transferred, the event is tagged with the new owner
+    OwnerChanged = 5,
+}
+impl PlaneEventKind {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            PlaneEventKind::Removed => "REMOVED",
+            PlaneEventKind::Paused => "PAUSED",
+            PlaneEventKind::Resumed => "RESUMED",
+            PlaneEventKind::Reset => "RESET",
+            PlaneEventKind::TimeScaleChanged => "TIME_SCALE_CHANGED",
+            PlaneEventKind::OwnerChanged => "OWNER_CHANGED",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "REMOVED" => Some(Self::Removed),
+            "PAUSED" => Some(Self::Paused),
+            "RESUMED" => Some(Self::Resumed),
+            "RESET" => Some(Self::Reset),
+            "TIME_SCALE_CHANGED" => Some(Self::TimeScaleChanged),
+            "OWNER_CHANGED" => Some(Self::OwnerChanged),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum PlaneStatus {
+    Running = 0,
+    Paused = 1,
+}
+impl PlaneStatus {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            PlaneStatus::Running => "RUNNING",
+            PlaneStatus::Paused => "PAUSED",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "RUNNING" => Some(Self::Running),
+            "PAUSED" => Some(Self::Paused),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum ErrorCode {
+    Internal = 0,
+    InvalidRequest = 1,
+    InvalidArgs = 2,
+    InvalidId = 3,
+    ModelNotAvailable = 4,
+    PlaneNotFound = 5,
+    TrimFailed = 6,
+    HandshakeRequired = 7,
+    /// the frame can not be decoded, the connection is closed after the error
+    MalformedFrame = 8,
+    /// the role of the client does not allow the request
+    PermissionDenied = 9,
+    /// the plane is owned by another client, which did not share it
+    NotPlaneOwner = 10,
+}
+impl ErrorCode {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            ErrorCode::Internal => "INTERNAL",
+            ErrorCode::InvalidRequest => "INVALID_REQUEST",
+            ErrorCode::InvalidArgs => "INVALID_ARGS",
+            ErrorCode::InvalidId => "INVALID_ID",
+            ErrorCode::ModelNotAvailable => "MODEL_NOT_AVAILABLE",
+            ErrorCode::PlaneNotFound => "PLANE_NOT_FOUND",
+            ErrorCode::TrimFailed => "TRIM_FAILED",
+            ErrorCode::HandshakeRequired => "HANDSHAKE_REQUIRED",
+            ErrorCode::MalformedFrame => "MALFORMED_FRAME",
+            ErrorCode::PermissionDenied => "PERMISSION_DENIED",
+            ErrorCode::NotPlaneOwner => "NOT_PLANE_OWNER",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "INTERNAL" => Some(Self::Internal),
+            "INVALID_REQUEST" => Some(Self::InvalidRequest),
+            "INVALID_ARGS" => Some(Self::InvalidArgs),
+            "INVALID_ID" => Some(Self::InvalidId),
+            "MODEL_NOT_AVAILABLE" => Some(Self::ModelNotAvailable),
+            "PLANE_NOT_FOUND" => Some(Self::PlaneNotFound),
+            "TRIM_FAILED" => Some(Self::TrimFailed),
+            "HANDSHAKE_REQUIRED" => Some(Self::HandshakeRequired),
+            "MALFORMED_FRAME" => Some(Self::MalformedFrame),
+            "PERMISSION_DENIED" => Some(Self::PermissionDenied),
+            "NOT_PLANE_OWNER" => Some(Self::NotPlaneOwner),
+            _ => None,
+        }
+    }
+}
+/// what a client may do, every role may do what the roles below it do
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum ClientRole {
+    /// push and command planes
+    Pilot = 0,
+    /// read the models and the outputs
+    Observer = 1,
+    /// every request
+    Admin = 2,
+}
+impl ClientRole {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            ClientRole::Pilot => "PILOT",
+            ClientRole::Observer => "OBSERVER",
+            ClientRole::Admin => "ADMIN",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "PILOT" => Some(Self::Pilot),
+            "OBSERVER" => Some(Self::Observer),
+            "ADMIN" => Some(Self::Admin),
+            _ => None,
+        }
+    }
+}
+/// optional protocol features negotiated during the handshake
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum Feature {
+    Compression = 0,
+    Batching = 1,
+    Subscription = 2,
+    /// a crc32 of the payload follows every frame
+    Checksum = 3,
+}
+impl Feature {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Feature::Compression => "COMPRESSION",
+            Feature::Batching => "BATCHING",
+            Feature::Subscription => "SUBSCRIPTION",
+            Feature::Checksum => "CHECKSUM",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "COMPRESSION" => Some(Self::Compression),
+            "BATCHING" => Some(Self::Batching),
+            "SUBSCRIPTION" => Some(Self::Subscription),
+            "CHECKSUM" => Some(Self::Checksum),
+            _ => None,
+        }
+    }
+}
+/// codec of the frames larger than the compression threshold
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum Compression {
+    None = 0,
+    Zstd = 1,
+    Lz4 = 2,
+}
+impl Compression {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            Compression::None => "NONE",
+            Compression::Zstd => "ZSTD",
+            Compression::Lz4 => "LZ4",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "NONE" => Some(Self::None),
+            "ZSTD" => Some(Self::Zstd),
+            "LZ4" => Some(Self::Lz4),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum OutputField {
+    State = 0,
+    Control = 1,
+    StateExtend = 2,
+}
+impl OutputField {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            OutputField::State => "STATE",
+            OutputField::Control => "CONTROL",
+            OutputField::StateExtend => "STATE_EXTEND",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "STATE" => Some(Self::State),
+            "CONTROL" => Some(Self::Control),
+            "STATE_EXTEND" => Some(Self::StateExtend),
+            _ => None,
+        }
+    }
+}
+/// Generated server implementations.
+pub mod fly_ruler_service_server {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    /// Generated trait containing gRPC methods that should be implemented for use with FlyRulerServiceServer.
+    #[async_trait]
+    pub trait FlyRulerService: Send + Sync + 'static {
+        async fn get_model_infos(
+            &self,
+            request: tonic::Request<()>,
+        ) -> std::result::Result<tonic::Response<super::GetModelInfosResponse>, tonic::Status>;
+        async fn push_plane(
+            &self,
+            request: tonic::Request<super::PushPlaneRequest>,
+        ) -> std::result::Result<tonic::Response<super::PushPlaneResponse>, tonic::Status>;
+        async fn send_control(
+            &self,
+            request: tonic::Request<super::SendControlRequest>,
+        ) -> std::result::Result<tonic::Response<()>, tonic::Status>;
+        async fn trim(
+            &self,
+            request: tonic::Request<super::TrimRequest>,
+        ) -> std::result::Result<tonic::Response<super::TrimResponse>, tonic::Status>;
+        async fn remove_plane(
+            &self,
+            request: tonic::Request<super::PlaneRequest>,
+        ) -> std::result::Result<tonic::Response<super::PlaneResponse>, tonic::Status>;
+        async fn pause_plane(
+            &self,
+            request: tonic::Request<super::PlaneRequest>,
+        ) -> std::result::Result<tonic::Response<super::PlaneResponse>, tonic::Status>;
+        async fn resume_plane(
+            &self,
+            request: tonic::Request<super::PlaneRequest>,
+        ) -> std::result::Result<tonic::Response<super::PlaneResponse>, tonic::Status>;
+        async fn reset_plane(
+            &self,
+            request: tonic::Request<super::PlaneRequest>,
+        ) -> std::result::Result<tonic::Response<super::PlaneResponse>, tonic::Status>;
+        async fn set_time_scale(
+            &self,
+            request: tonic::Request<super::SetTimeScaleRequest>,
+        ) -> std::result::Result<tonic::Response<super::PlaneResponse>, tonic::Status>;
+        async fn list_planes(
+            &self,
+            request: tonic::Request<()>,
+        ) -> std::result::Result<tonic::Response<super::ListPlanesResponse>, tonic::Status>;
+        async fn get_plane_state(
+            &self,
+            request: tonic::Request<super::GetPlaneStateRequest>,
+        ) -> std::result::Result<tonic::Response<super::super::core_output::PlaneMessage>, tonic::Status>;
+        async fn get_model_details(
+            &self,
+            request: tonic::Request<super::GetModelDetailsRequest>,
+        ) -> std::result::Result<tonic::Response<super::GetModelDetailsResponse>, tonic::Status>;
+        async fn subscribe(
+            &self,
+            request: tonic::Request<super::Subscription>,
+        ) -> std::result::Result<tonic::Response<super::Subscription>, tonic::Status>;
+        async fn unsubscribe(
+            &self,
+            request: tonic::Request<super::Subscription>,
+        ) -> std::result::Result<tonic::Response<super::Subscription>, tonic::Status>;
+        /// Server streaming response type for the StreamOutputs method.
+        type StreamOutputsStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<
+                    super::super::core_output::PlaneMessage,
+                    tonic::Status,
+                >,
+            >
+            + Send
+            + 'static;
+        /// outputs of the subscribed planes, every plane if neither plane nor owner is
+        /// given, ends with DATA_LOSS when the client falls behind
+        async fn stream_outputs(
+            &self,
+            request: tonic::Request<super::Subscription>,
+        ) -> std::result::Result<tonic::Response<Self::StreamOutputsStream>, tonic::Status>;
+        /// Server streaming response type for the ControlStream method.
+        type ControlStreamStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<
+                    super::super::core_output::PlaneMessage,
+                    tonic::Status,
+                >,
+            >
+            + Send
+            + 'static;
+        /// controls of the planes pushed over grpc, answered by the outputs of the
+        /// controlled planes, ends with DATA_LOSS when the client falls behind
+        async fn control_stream(
+            &self,
+            request: tonic::Request<tonic::Streaming<super::SendControlRequest>>,
+        ) -> std::result::Result<tonic::Response<Self::ControlStreamStream>, tonic::Status>;
+    }
+    #[derive(Debug)]
+    pub struct FlyRulerServiceServer<T: FlyRulerService> {
+        inner: _Inner<T>,
+        accept_compression_encodings: EnabledCompressionEncodings,
+        send_compression_encodings: EnabledCompressionEncodings,
+        max_decoding_message_size: Option<usize>,
+        max_encoding_message_size: Option<usize>,
+    }
+    struct _Inner<T>(Arc<T>);
+    impl<T: FlyRulerService> FlyRulerServiceServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            let inner = _Inner(inner);
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+                max_decoding_message_size: None,
+                max_encoding_message_size: None,
+            }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+        /// Enable decompressing requests with the given encoding.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.accept_compression_encodings.enable(encoding);
+            self
+        }
+        /// Compress responses with the given encoding, if the client supports it.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.send_compression_encodings.enable(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.max_decoding_message_size = Some(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.max_encoding_message_size = Some(limit);
+            self
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for FlyRulerServiceServer<T>
+    where
+        T: FlyRulerService,
+        B: Body + Send + 'static,
+        B::Error: Into<StdError> + Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<std::result::Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            let inner = self.inner.clone();
+            match req.uri().path() {
+                "/service.FlyRulerService/GetModelInfos" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetModelInfosSvc<T: FlyRulerService>(pub Arc<T>);
+                    impl<
+                        T: FlyRulerService,
+                    > tonic::server::UnaryService<()>
+                    for GetModelInfosSvc<T> {
+                        type Response = super::GetModelInfosResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<()>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as FlyRulerService>::get_model_infos(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetModelInfosSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/service.FlyRulerService/PushPlane" => {
+                    #[allow(non_camel_case_types)]
+                    struct PushPlaneSvc<T: FlyRulerService>(pub Arc<T>);
+                    impl<
+                        T: FlyRulerService,
+                    > tonic::server::UnaryService<super::PushPlaneRequest>
+                    for PushPlaneSvc<T> {
+                        type Response = super::PushPlaneResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::PushPlaneRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as FlyRulerService>::push_plane(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = PushPlaneSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/service.FlyRulerService/SendControl" => {
+                    #[allow(non_camel_case_types)]
+                    struct SendControlSvc<T: FlyRulerService>(pub Arc<T>);
+                    impl<
+                        T: FlyRulerService,
+                    > tonic::server::UnaryService<super::SendControlRequest>
+                    for SendControlSvc<T> {
+                        type Response = ();
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SendControlRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as FlyRulerService>::send_control(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = SendControlSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/service.FlyRulerService/Trim" => {
+                    #[allow(non_camel_case_types)]
+                    struct TrimSvc<T: FlyRulerService>(pub Arc<T>);
+                    impl<
+                        T: FlyRulerService,
+                    > tonic::server::UnaryService<super::TrimRequest>
+                    for TrimSvc<T> {
+                        type Response = super::TrimResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::TrimRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as FlyRulerService>::trim(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = TrimSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/service.FlyRulerService/RemovePlane" => {
+                    #[allow(non_camel_case_types)]
+                    struct RemovePlaneSvc<T: FlyRulerService>(pub Arc<T>);
+                    impl<
+                        T: FlyRulerService,
+                    > tonic::server::UnaryService<super::PlaneRequest>
+                    for RemovePlaneSvc<T> {
+                        type Response = super::PlaneResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::PlaneRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as FlyRulerService>::remove_plane(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = RemovePlaneSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/service.FlyRulerService/PausePlane" => {
+                    #[allow(non_camel_case_types)]
+                    struct PausePlaneSvc<T: FlyRulerService>(pub Arc<T>);
+                    impl<
+                        T: FlyRulerService,
+                    > tonic::server::UnaryService<super::PlaneRequest>
+                    for PausePlaneSvc<T> {
+                        type Response = super::PlaneResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::PlaneRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as FlyRulerService>::pause_plane(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = PausePlaneSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/service.FlyRulerService/ResumePlane" => {
+                    #[allow(non_camel_case_types)]
+                    struct ResumePlaneSvc<T: FlyRulerService>(pub Arc<T>);
+                    impl<
+                        T: FlyRulerService,
+                    > tonic::server::UnaryService<super::PlaneRequest>
+                    for ResumePlaneSvc<T> {
+                        type Response = super::PlaneResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::PlaneRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as FlyRulerService>::resume_plane(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ResumePlaneSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/service.FlyRulerService/ResetPlane" => {
+                    #[allow(non_camel_case_types)]
+                    struct ResetPlaneSvc<T: FlyRulerService>(pub Arc<T>);
+                    impl<
+                        T: FlyRulerService,
+                    > tonic::server::UnaryService<super::PlaneRequest>
+                    for ResetPlaneSvc<T> {
+                        type Response = super::PlaneResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::PlaneRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as FlyRulerService>::reset_plane(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ResetPlaneSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/service.FlyRulerService/SetTimeScale" => {
+                    #[allow(non_camel_case_types)]
+                    struct SetTimeScaleSvc<T: FlyRulerService>(pub Arc<T>);
+                    impl<
+                        T: FlyRulerService,
+                    > tonic::server::UnaryService<super::SetTimeScaleRequest>
+                    for SetTimeScaleSvc<T> {
+                        type Response = super::PlaneResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SetTimeScaleRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as FlyRulerService>::set_time_scale(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = SetTimeScaleSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/service.FlyRulerService/ListPlanes" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListPlanesSvc<T: FlyRulerService>(pub Arc<T>);
+                    impl<
+                        T: FlyRulerService,
+                    > tonic::server::UnaryService<()>
+                    for ListPlanesSvc<T> {
+                        type Response = super::ListPlanesResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<()>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as FlyRulerService>::list_planes(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ListPlanesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/service.FlyRulerService/GetPlaneState" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetPlaneStateSvc<T: FlyRulerService>(pub Arc<T>);
+                    impl<
+                        T: FlyRulerService,
+                    > tonic::server::UnaryService<super::GetPlaneStateRequest>
+                    for GetPlaneStateSvc<T> {
+                        type Response = super::super::core_output::PlaneMessage;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetPlaneStateRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as FlyRulerService>::get_plane_state(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetPlaneStateSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/service.FlyRulerService/GetModelDetails" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetModelDetailsSvc<T: FlyRulerService>(pub Arc<T>);
+                    impl<
+                        T: FlyRulerService,
+                    > tonic::server::UnaryService<super::GetModelDetailsRequest>
+                    for GetModelDetailsSvc<T> {
+                        type Response = super::GetModelDetailsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetModelDetailsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as FlyRulerService>::get_model_details(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetModelDetailsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/service.FlyRulerService/Subscribe" => {
+                    #[allow(non_camel_case_types)]
+                    struct SubscribeSvc<T: FlyRulerService>(pub Arc<T>);
+                    impl<
+                        T: FlyRulerService,
+                    > tonic::server::UnaryService<super::Subscription>
+                    for SubscribeSvc<T> {
+                        type Response = super::Subscription;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::Subscription>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as FlyRulerService>::subscribe(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = SubscribeSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/service.FlyRulerService/Unsubscribe" => {
+                    #[allow(non_camel_case_types)]
+                    struct UnsubscribeSvc<T: FlyRulerService>(pub Arc<T>);
+                    impl<
+                        T: FlyRulerService,
+                    > tonic::server::UnaryService<super::Subscription>
+                    for UnsubscribeSvc<T> {
+                        type Response = super::Subscription;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::Subscription>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as FlyRulerService>::unsubscribe(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = UnsubscribeSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/service.FlyRulerService/StreamOutputs" => {
+                    #[allow(non_camel_case_types)]
+                    struct StreamOutputsSvc<T: FlyRulerService>(pub Arc<T>);
+                    impl<
+                        T: FlyRulerService,
+                    > tonic::server::ServerStreamingService<super::Subscription>
+                    for StreamOutputsSvc<T> {
+                        type Response = super::super::core_output::PlaneMessage;
+                        type ResponseStream = T::StreamOutputsStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::Subscription>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as FlyRulerService>::stream_outputs(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = StreamOutputsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/service.FlyRulerService/ControlStream" => {
+                    #[allow(non_camel_case_types)]
+                    struct ControlStreamSvc<T: FlyRulerService>(pub Arc<T>);
+                    impl<
+                        T: FlyRulerService,
+                    > tonic::server::StreamingService<super::SendControlRequest>
+                    for ControlStreamSvc<T> {
+                        type Response = super::super::core_output::PlaneMessage;
+                        type ResponseStream = T::ControlStreamStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<tonic::Streaming<super::SendControlRequest>>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as FlyRulerService>::control_stream(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ControlStreamSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => {
+                    Box::pin(async move {
+                        Ok(
+                            http::Response::builder()
+                                .status(200)
+                                .header("grpc-status", "12")
+                                .header("content-type", "application/grpc")
+                                .body(empty_body())
+                                .unwrap(),
+                        )
+                    })
+                }
+            }
+        }
+    }
+    impl<T: FlyRulerService> Clone for FlyRulerServiceServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self {
+                inner,
+                accept_compression_encodings: self.accept_compression_encodings,
+                send_compression_encodings: self.send_compression_encodings,
+                max_decoding_message_size: self.max_decoding_message_size,
+                max_encoding_message_size: self.max_encoding_message_size,
+            }
+        }
+    }
+    impl<T: FlyRulerService> Clone for _Inner<T> {
+        fn clone(&self) -> Self {
+            Self(Arc::clone(&self.0))
+        }
+    }
+    impl<T: std::fmt::Debug> std::fmt::Debug for _Inner<T> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{:?}", self.0)
+        }
+    }
+    impl<T: FlyRulerService> tonic::server::NamedService for FlyRulerServiceServer<T> {
+        const NAME: &'static str = "service.FlyRulerService";
     }
 }
@@ -13,4 +13,36 @@ pub struct StateExtend {
     pub qbar: f64,
     #[prost(double, tag = "6")]
     pub ps: f64,
+    #[prost(double, tag = "7")]
+    pub u: f64,
+    #[prost(double, tag = "8")]
+    pub v: f64,
+    #[prost(double, tag = "9")]
+    pub w: f64,
+    #[prost(double, tag = "10")]
+    pub v_north: f64,
+    #[prost(double, tag = "11")]
+    pub v_east: f64,
+    #[prost(double, tag = "12")]
+    pub v_down: f64,
+    #[prost(double, tag = "13")]
+    pub gamma: f64,
+    #[prost(double, tag = "14")]
+    pub track: f64,
+    #[prost(double, tag = "15")]
+    pub ground_speed: f64,
+    #[prost(double, tag = "16")]
+    pub climb_rate: f64,
+    #[prost(double, tag = "17")]
+    pub tas: f64,
+    #[prost(double, tag = "18")]
+    pub cas: f64,
+    #[prost(double, tag = "19")]
+    pub eas: f64,
+    #[prost(double, tag = "20")]
+    pub load_factor: f64,
+    #[prost(double, tag = "21")]
+    pub specific_energy: f64,
+    #[prost(double, tag = "22")]
+    pub specific_excess_power: f64,
 }
@@ -1,11 +1,17 @@
 mod frame;
-mod generated;
+/// protobuf types and the server of the `FlyRulerService` grpc service
+pub mod generated;
 mod model;
 mod proto;
+mod shm;
 
 use fly_ruler_utils::error::FrResult;
 pub use frame::*;
 pub use model::*;
+pub use shm::*;
+
+/// version of the framed service protocol, checked by the `Hello` handshake
+pub const PROTOCOL_VERSION: u32 = 1;
 
 pub trait Encoder
 where
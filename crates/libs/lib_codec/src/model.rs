@@ -1,6 +1,6 @@
-use fly_ruler_core::core::PlaneInitCfg;
+use fly_ruler_core::{core::PlaneInitCfg, parts::trim::TrimOutput};
 use fly_ruler_plugin::{PluginInfo, PluginState};
-use fly_ruler_utils::plane_model::{Control, CoreOutput};
+use fly_ruler_utils::plane_model::{Control, ControlLimit, CoreOutput, Diagnostic, PlaneConstants};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -15,6 +15,13 @@ pub struct PlaneMessage {
     pub output: Option<CoreOutput>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PlaneDiagnostic {
+    pub id: String,
+    pub time: f64,
+    pub diagnostic: Option<Diagnostic>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct PluginInfoTuple {
     pub id: String,
@@ -27,15 +34,28 @@ pub struct GetModelInfosResponse {
     pub model_infos: Vec<PluginInfoTuple>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct PushPlaneRequest {
     pub model_id: String,
     pub plane_init_cfg: Option<PlaneInitCfg>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct PushPlaneResponse {
     pub plane_id: String,
+    pub trim_output: Option<TrimOutput>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TrimRequest {
+    pub model_id: String,
+    pub plane_init_cfg: Option<PlaneInitCfg>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TrimResponse {
+    pub model_id: String,
+    pub trim_output: Option<TrimOutput>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -44,28 +64,339 @@ pub struct SendControlRequest {
     pub control: Option<Control>,
 }
 
-#[derive(Debug, Clone)]
+/// `plane_id` is `None` means all planes of the client
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PlaneRequest {
+    pub plane_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SetTimeScaleRequest {
+    pub plane_id: Option<String>,
+    pub time_scale: f64,
+}
+
+/// planes affected by the request
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PlaneResponse {
+    pub plane_ids: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum PlaneEventKind {
+    Removed,
+    Paused,
+    Resumed,
+    Reset,
+    TimeScaleChanged,
+    /// the plane is transferred, the event is tagged with the new owner
+    OwnerChanged,
+}
+
+/// broadcast after the lifecycle of a plane changed
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PlaneEvent {
+    pub plane_id: String,
+    pub kind: PlaneEventKind,
+    pub time_scale: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum PlaneStatus {
+    Running,
+    Paused,
+}
+
+/// summary of a plane running on the server
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PlaneInfo {
+    pub plane_id: String,
+    pub model_id: String,
+    /// address of the client which owns the plane
+    pub owner: String,
+    pub status: PlaneStatus,
+    pub time: f64,
+    /// clients allowed to control the plane besides the owner
+    pub pilots: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ListPlanesResponse {
+    pub planes: Vec<PlaneInfo>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GetPlaneStateRequest {
+    pub plane_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GetModelDetailsRequest {
+    pub model_id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GetModelDetailsResponse {
+    pub model_info: Option<PluginInfoTuple>,
+    pub constants: Option<PlaneConstants>,
+    pub control_limit: Option<ControlLimit>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum ErrorCode {
+    Internal,
+    InvalidRequest,
+    InvalidArgs,
+    InvalidId,
+    ModelNotAvailable,
+    PlaneNotFound,
+    TrimFailed,
+    HandshakeRequired,
+    /// the frame can not be decoded, the connection is closed after the error
+    MalformedFrame,
+    /// the role of the client does not allow the request
+    PermissionDenied,
+    /// the plane is owned by another client, which did not share it
+    NotPlaneOwner,
+}
+
+/// a failed request, the connection and planes of the client are kept
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ErrorResponse {
+    pub code: ErrorCode,
+    pub message: String,
+    /// name of the request which failed
+    pub request: String,
+    pub request_id: u64,
+}
+
+/// what a client may do, every role may do what the roles below it do
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum ClientRole {
+    /// push and command planes
+    Pilot,
+    /// read the models and the outputs
+    Observer,
+    /// every request
+    Admin,
+}
+
+impl ClientRole {
+    fn rank(&self) -> u8 {
+        match self {
+            ClientRole::Observer => 0,
+            ClientRole::Pilot => 1,
+            ClientRole::Admin => 2,
+        }
+    }
+
+    /// whether the role may do what `other` does
+    pub fn includes(&self, other: ClientRole) -> bool {
+        self.rank() >= other.rank()
+    }
+}
+
+/// optional protocol features negotiated during the handshake
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum Feature {
+    Compression,
+    Batching,
+    Subscription,
+    /// a crc32 of the payload follows every frame
+    Checksum,
+}
+
+/// codec of the frames larger than the compression threshold
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+pub enum Compression {
+    #[default]
+    None,
+    Zstd,
+    Lz4,
+}
+
+/// first call of every connection
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Hello {
+    pub protocol_version: u32,
+    pub client_name: String,
+    pub client_version: String,
+    pub role: ClientRole,
+    /// features supported by the client
+    pub features: Vec<Feature>,
+    /// codecs supported by the client, the preferred one first
+    pub compressions: Vec<Compression>,
+    /// checked against the tokens of the server, if the server asks for one
+    pub token: String,
+    /// session of a previous connection, its planes are reclaimed if the session has not expired
+    pub session: String,
+    /// world joined by the client, empty for the default one
+    pub room: String,
+    /// create the room if it does not exist, only an admin may
+    pub create_room: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HelloAck {
+    pub accepted: bool,
+    /// why the client is rejected
+    pub reason: String,
+    pub protocol_version: u32,
+    pub server_name: String,
+    pub server_version: String,
+    /// features supported by both sides
+    pub features: Vec<Feature>,
+    /// codec used by both sides if `Compression` is negotiated
+    pub compression: Compression,
+    /// frames smaller than the threshold are sent uncompressed, in bytes
+    pub compression_threshold: u32,
+    /// role granted to the client
+    pub role: ClientRole,
+    /// session of the client, empty if the server keeps no session
+    pub session: String,
+    /// planes reclaimed from the previous connection
+    pub resumed_planes: Vec<String>,
+    /// world joined by the client, empty for the default one
+    pub room: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum OutputField {
+    State,
+    Control,
+    StateExtend,
+}
+
+/// planes whose outputs are forwarded to a client
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct Subscription {
+    /// every plane, planes pushed later included
+    pub all: bool,
+    pub plane_ids: Vec<String>,
+    /// address of the clients whose planes are subscribed
+    pub owners: Vec<String>,
+    /// forward one of every `decimation` outputs of a plane, 0 keeps the current rate
+    pub decimation: u32,
+    /// parts of the output to send, empty keeps the current mask
+    pub fields: Vec<OutputField>,
+}
+
+/// open the udp side channel of the client
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenUdpRequest {
+    /// send the subscribed outputs over udp instead of the session
+    pub outputs: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OpenUdpResponse {
+    /// udp port of the server
+    pub port: u16,
+    /// identifies the client in every datagram
+    pub token: u64,
+}
+
+/// give a plane to another connected client, only the owner may transfer it
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TransferPlaneRequest {
+    pub plane_id: String,
+    /// address of the new owner, as listed by `ListPlanes`
+    pub owner: String,
+}
+
+/// let another client control a plane, or take the permission back
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SharePlaneRequest {
+    pub plane_id: String,
+    /// address of the client
+    pub pilot: String,
+    pub revoke: bool,
+}
+
+/// the output queue of a client, filled according to the backpressure policy of the server
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ClientStatus {
+    /// address of the client
+    pub addr: String,
+    /// messages waiting to be sent
+    pub queued: u64,
+    /// outputs dropped because the queue was full
+    pub dropped: u64,
+    /// outputs replaced by a newer output of the same plane
+    pub conflated: u64,
+    /// broadcast messages missed while the client lagged behind
+    pub lagged: u64,
+}
+
+/// the calling client, and every client of the room for an admin
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GetStatusResponse {
+    pub clients: Vec<ClientStatus>,
+}
+
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub enum UdpPayload {
+    Control(SendControlRequest),
+    Output(PlaneMessage),
+}
+
+/// one datagram of the udp side channel, the first datagram of the client
+/// registers its address and may carry no payload
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UdpDatagram {
+    pub token: u64,
+    /// increasing for every datagram of a sender, stale controls are dropped
+    pub sequence: u64,
+    pub payload: Option<UdpPayload>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ServiceCall {
     pub name: String,
+    /// chosen by the client and echoed back in the responses, 0 means no response is expected
+    pub request_id: u64,
     pub args: Option<Args>,
 }
 
-#[derive(Debug, Clone)]
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum Args {
     GetModelInfos,
     PushPlane(PushPlaneRequest),
     SendControl(SendControlRequest),
     Tick,
     Disconnect,
+    Trim(TrimRequest),
+    RemovePlane(PlaneRequest),
+    PausePlane(PlaneRequest),
+    ResumePlane(PlaneRequest),
+    ResetPlane(PlaneRequest),
+    SetTimeScale(SetTimeScaleRequest),
+    ListPlanes,
+    GetPlaneState(GetPlaneStateRequest),
+    GetModelDetails(GetModelDetailsRequest),
+    Hello(Hello),
+    Subscribe(Subscription),
+    Unsubscribe(Subscription),
+    OpenUdp(OpenUdpRequest),
+    TransferPlane(TransferPlaneRequest),
+    SharePlane(SharePlaneRequest),
+    /// keeps the client alive without ticking, answered with `Pong`
+    Ping,
+    GetStatus,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ServiceCallResponse {
     pub name: String,
+    /// id of the request answered, 0 for broadcast messages
+    pub request_id: u64,
     pub response: Option<Response>,
 }
 
-#[derive(Debug, Clone)]
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum Response {
     GetModelInfos(GetModelInfosResponse),
     PushPlane(PushPlaneResponse),
@@ -73,5 +404,26 @@ pub enum Response {
     Output(PlaneMessage),
     LostPlane(String),
     NewPlane(String),
-    Error(String),
+    Error(ErrorResponse),
+    Diagnostic(PlaneDiagnostic),
+    Trim(TrimResponse),
+    RemovePlane(PlaneResponse),
+    PausePlane(PlaneResponse),
+    ResumePlane(PlaneResponse),
+    ResetPlane(PlaneResponse),
+    SetTimeScale(PlaneResponse),
+    PlaneEvent(PlaneEvent),
+    ListPlanes(ListPlanesResponse),
+    GetPlaneState(PlaneMessage),
+    GetModelDetails(GetModelDetailsResponse),
+    Hello(HelloAck),
+    Subscribe(Subscription),
+    Unsubscribe(Subscription),
+    /// outputs sent within the batch interval, only if `Batching` is negotiated
+    OutputGroup(PlaneMessageGroup),
+    OpenUdp(OpenUdpResponse),
+    TransferPlane(PlaneInfo),
+    SharePlane(PlaneInfo),
+    Pong,
+    GetStatus(GetStatusResponse),
 }
@@ -1,12 +1,17 @@
 use crate::generated::control::Control as ControlGen;
 use crate::generated::core_output::{
-    CoreOutput as CoreOutputGen, PlaneMessage as PlaneMessageGen,
+    Coefficient as CoefficientGen, CoreOutput as CoreOutputGen, Diagnostic as DiagnosticGen,
+    PlaneDiagnostic as PlaneDiagnosticGen, PlaneMessage as PlaneMessageGen,
     PlaneMessageGroup as PlaneMessageGroupGen,
 };
 use crate::generated::id::Id as IdGen;
 use crate::generated::plane_init_cfg::{
-    Deflection as DeflectionGen, NelderMeadOptions as NelderMeadOptionsGen,
-    PlaneInitCfg as PlaneInitCfgGen, TrimInit as TrimInitGen, TrimTarget as TrimTargetGen,
+    ControlLimit as ControlLimitGen, ControlLimitOverride as ControlLimitOverrideGen,
+    Deflection as DeflectionGen, InitState as InitStateGen,
+    NelderMeadOptions as NelderMeadOptionsGen, NelderMeadResult as NelderMeadResultGen,
+    PlaneConstants as PlaneConstantsGen, PlaneConstantsOverride as PlaneConstantsOverrideGen,
+    PlaneInitCfg as PlaneInitCfgGen, TrimInit as TrimInitGen, TrimOutput as TrimOutputGen,
+    TrimTarget as TrimTargetGen,
 };
 use crate::generated::plugin::{
     PluginInfo as PluginInfoGen, PluginInfoTuple as PluginInfoTupleGen,
@@ -14,22 +19,46 @@ use crate::generated::plugin::{
 };
 use crate::generated::service::{
     service_call::Args as ArgsGen, service_call_response::Response as ResponseGen,
-    GetModelInfosResponse as GetModelInfosResponseGen, PushPlaneRequest as PushPlaneRequestGen,
+    udp_datagram::Payload as UdpPayloadGen, ClientRole as ClientRoleGen,
+    ClientStatus as ClientStatusGen, Compression as CompressionGen, ErrorCode as ErrorCodeGen,
+    ErrorResponse as ErrorResponseGen, Feature as FeatureGen,
+    GetModelDetailsRequest as GetModelDetailsRequestGen,
+    GetModelDetailsResponse as GetModelDetailsResponseGen,
+    GetModelInfosResponse as GetModelInfosResponseGen,
+    GetPlaneStateRequest as GetPlaneStateRequestGen, GetStatusResponse as GetStatusResponseGen,
+    Hello as HelloGen, HelloAck as HelloAckGen, ListPlanesResponse as ListPlanesResponseGen,
+    OpenUdpRequest as OpenUdpRequestGen, OpenUdpResponse as OpenUdpResponseGen,
+    OutputField as OutputFieldGen, PlaneEvent as PlaneEventGen,
+    PlaneEventKind as PlaneEventKindGen, PlaneInfo as PlaneInfoGen,
+    PlaneRequest as PlaneRequestGen, PlaneResponse as PlaneResponseGen,
+    PlaneStatus as PlaneStatusGen, PushPlaneRequest as PushPlaneRequestGen,
     PushPlaneResponse as PushPlaneResponseGen, SendControlRequest as SendControlRequestGen,
     ServiceCall as ServiceCallGen, ServiceCallResponse as ServiceCallResponseGen,
+    SetTimeScaleRequest as SetTimeScaleRequestGen, SharePlaneRequest as SharePlaneRequestGen,
+    Subscription as SubscriptionGen, TransferPlaneRequest as TransferPlaneRequestGen,
+    TrimRequest as TrimRequestGen, TrimResponse as TrimResponseGen, UdpDatagram as UdpDatagramGen,
 };
 use crate::generated::state::State as StateGen;
 use crate::generated::state_extend::StateExtend as StateExtendGen;
 use crate::{
-    Args, Decoder, Encoder, GetModelInfosResponse, PlaneMessage, PlaneMessageGroup,
-    PluginInfoTuple, PushPlaneRequest, PushPlaneResponse, Response, SendControlRequest,
-    ServiceCall, ServiceCallResponse,
+    Args, ClientRole, ClientStatus, Compression, Decoder, Encoder, ErrorCode, ErrorResponse,
+    Feature, GetModelDetailsRequest, GetModelDetailsResponse, GetModelInfosResponse,
+    GetPlaneStateRequest, GetStatusResponse, Hello, HelloAck, ListPlanesResponse, OpenUdpRequest,
+    OpenUdpResponse, OutputField, PlaneDiagnostic, PlaneEvent, PlaneEventKind, PlaneInfo,
+    PlaneMessage, PlaneMessageGroup, PlaneRequest, PlaneResponse, PlaneStatus, PluginInfoTuple,
+    PushPlaneRequest, PushPlaneResponse, Response, SendControlRequest, ServiceCall,
+    ServiceCallResponse, SetTimeScaleRequest, SharePlaneRequest, Subscription,
+    TransferPlaneRequest, TrimRequest, TrimResponse, UdpDatagram, UdpPayload,
 };
-use fly_ruler_core::algorithm::nelder_mead::NelderMeadOptions;
+use fly_ruler_core::algorithm::nelder_mead::{NelderMeadOptions, NelderMeadResult};
 use fly_ruler_core::core::PlaneInitCfg;
-use fly_ruler_core::parts::trim::{TrimInit, TrimTarget};
+use fly_ruler_core::parts::trim::{InitState, TrimInit, TrimOutput, TrimTarget};
 use fly_ruler_plugin::{PluginInfo, PluginState};
-use fly_ruler_utils::plane_model::{Control, CoreOutput, FlightCondition, State, StateExtend};
+use fly_ruler_utils::plane_model::{
+    Control, ControlLimit, ControlLimitOverride, CoreOutput, Diagnostic, FlightCondition,
+    PlaneConstants, PlaneConstantsOverride, State, StateExtend, C,
+};
+use fly_ruler_utils::Vector;
 use prost::Message;
 use uuid::Uuid;
 
@@ -102,6 +131,22 @@ impl Into<StateExtendGen> for StateExtend {
             mach: self.mach,
             qbar: self.qbar,
             ps: self.ps,
+            u: self.u,
+            v: self.v,
+            w: self.w,
+            v_north: self.v_north,
+            v_east: self.v_east,
+            v_down: self.v_down,
+            gamma: self.gamma,
+            track: self.track,
+            ground_speed: self.ground_speed,
+            climb_rate: self.climb_rate,
+            tas: self.tas,
+            cas: self.cas,
+            eas: self.eas,
+            load_factor: self.load_factor,
+            specific_energy: self.specific_energy,
+            specific_excess_power: self.specific_excess_power,
         }
     }
 }
@@ -115,6 +160,22 @@ impl From<StateExtendGen> for StateExtend {
             mach: value.mach,
             qbar: value.qbar,
             ps: value.ps,
+            u: value.u,
+            v: value.v,
+            w: value.w,
+            v_north: value.v_north,
+            v_east: value.v_east,
+            v_down: value.v_down,
+            gamma: value.gamma,
+            track: value.track,
+            ground_speed: value.ground_speed,
+            climb_rate: value.climb_rate,
+            tas: value.tas,
+            cas: value.cas,
+            eas: value.eas,
+            load_factor: value.load_factor,
+            specific_energy: value.specific_energy,
+            specific_excess_power: value.specific_excess_power,
         }
     }
 }
@@ -183,6 +244,87 @@ impl From<PlaneMessageGroupGen> for PlaneMessageGroup {
     }
 }
 
+impl From<C> for CoefficientGen {
+    fn from(value: C) -> Self {
+        CoefficientGen {
+            c_x: value.c_x,
+            c_z: value.c_z,
+            c_m: value.c_m,
+            c_y: value.c_y,
+            c_n: value.c_n,
+            c_l: value.c_l,
+        }
+    }
+}
+
+impl From<CoefficientGen> for C {
+    fn from(value: CoefficientGen) -> Self {
+        C {
+            c_x: value.c_x,
+            c_z: value.c_z,
+            c_m: value.c_m,
+            c_y: value.c_y,
+            c_n: value.c_n,
+            c_l: value.c_l,
+        }
+    }
+}
+
+/// missing components of a repeated triple are read as zero
+fn triple(value: Vec<f64>) -> [f64; 3] {
+    let mut r = [0.0; 3];
+    r.iter_mut().zip(value).for_each(|(r, v)| *r = v);
+    r
+}
+
+impl From<Diagnostic> for DiagnosticGen {
+    fn from(value: Diagnostic) -> Self {
+        DiagnosticGen {
+            c: Some(value.c.into()),
+            aero_force: value.aero_force.to_vec(),
+            thrust_force: value.thrust_force.to_vec(),
+            gravity_force: value.gravity_force.to_vec(),
+            aero_moment: value.aero_moment.to_vec(),
+            thrust_moment: value.thrust_moment.to_vec(),
+            angular_accel: value.angular_accel.to_vec(),
+        }
+    }
+}
+
+impl From<DiagnosticGen> for Diagnostic {
+    fn from(value: DiagnosticGen) -> Self {
+        Diagnostic {
+            c: value.c.map(C::from).unwrap_or_default(),
+            aero_force: triple(value.aero_force),
+            thrust_force: triple(value.thrust_force),
+            gravity_force: triple(value.gravity_force),
+            aero_moment: triple(value.aero_moment),
+            thrust_moment: triple(value.thrust_moment),
+            angular_accel: triple(value.angular_accel),
+        }
+    }
+}
+
+impl From<PlaneDiagnostic> for PlaneDiagnosticGen {
+    fn from(value: PlaneDiagnostic) -> Self {
+        PlaneDiagnosticGen {
+            id: Some(Uuid::parse_str(&value.id).unwrap().into()),
+            time: value.time,
+            diagnostic: value.diagnostic.map(|a| a.into()),
+        }
+    }
+}
+
+impl From<PlaneDiagnosticGen> for PlaneDiagnostic {
+    fn from(value: PlaneDiagnosticGen) -> Self {
+        PlaneDiagnostic {
+            id: Uuid::from(value.id.unwrap()).into(),
+            time: value.time,
+            diagnostic: value.diagnostic.map(|a| a.into()),
+        }
+    }
+}
+
 impl From<Uuid> for IdGen {
     fn from(value: Uuid) -> Self {
         IdGen {
@@ -300,6 +442,188 @@ impl From<GetModelInfosResponse> for GetModelInfosResponseGen {
     }
 }
 
+impl From<PlaneConstants> for PlaneConstantsGen {
+    fn from(value: PlaneConstants) -> Self {
+        PlaneConstantsGen {
+            m: value.m,
+            b: value.b,
+            s: value.s,
+            c_bar: value.c_bar,
+            x_cg_r: value.x_cg_r,
+            x_cg: value.x_cg,
+            h_eng: value.h_eng,
+            j_y: value.j_y,
+            j_xz: value.j_xz,
+            j_z: value.j_z,
+            j_x: value.j_x,
+        }
+    }
+}
+
+impl From<PlaneConstantsGen> for PlaneConstants {
+    fn from(value: PlaneConstantsGen) -> Self {
+        PlaneConstants {
+            m: value.m,
+            b: value.b,
+            s: value.s,
+            c_bar: value.c_bar,
+            x_cg_r: value.x_cg_r,
+            x_cg: value.x_cg,
+            h_eng: value.h_eng,
+            j_y: value.j_y,
+            j_xz: value.j_xz,
+            j_z: value.j_z,
+            j_x: value.j_x,
+        }
+    }
+}
+
+impl From<ControlLimit> for ControlLimitGen {
+    fn from(value: ControlLimit) -> Self {
+        ControlLimitGen {
+            thrust_cmd_limit_top: value.thrust_cmd_limit_top,
+            thrust_cmd_limit_bottom: value.thrust_cmd_limit_bottom,
+            thrust_rate_limit: value.thrust_rate_limit,
+            ele_cmd_limit_top: value.ele_cmd_limit_top,
+            ele_cmd_limit_bottom: value.ele_cmd_limit_bottom,
+            ele_rate_limit: value.ele_rate_limit,
+            ail_cmd_limit_top: value.ail_cmd_limit_top,
+            ail_cmd_limit_bottom: value.ail_cmd_limit_bottom,
+            ail_rate_limit: value.ail_rate_limit,
+            rud_cmd_limit_top: value.rud_cmd_limit_top,
+            rud_cmd_limit_bottom: value.rud_cmd_limit_bottom,
+            rud_rate_limit: value.rud_rate_limit,
+            alpha_limit_top: value.alpha_limit_top,
+            alpha_limit_bottom: value.alpha_limit_bottom,
+            beta_limit_top: value.beta_limit_top,
+            beta_limit_bottom: value.beta_limit_bottom,
+        }
+    }
+}
+
+impl From<ControlLimitGen> for ControlLimit {
+    fn from(value: ControlLimitGen) -> Self {
+        ControlLimit {
+            thrust_cmd_limit_top: value.thrust_cmd_limit_top,
+            thrust_cmd_limit_bottom: value.thrust_cmd_limit_bottom,
+            thrust_rate_limit: value.thrust_rate_limit,
+            ele_cmd_limit_top: value.ele_cmd_limit_top,
+            ele_cmd_limit_bottom: value.ele_cmd_limit_bottom,
+            ele_rate_limit: value.ele_rate_limit,
+            ail_cmd_limit_top: value.ail_cmd_limit_top,
+            ail_cmd_limit_bottom: value.ail_cmd_limit_bottom,
+            ail_rate_limit: value.ail_rate_limit,
+            rud_cmd_limit_top: value.rud_cmd_limit_top,
+            rud_cmd_limit_bottom: value.rud_cmd_limit_bottom,
+            rud_rate_limit: value.rud_rate_limit,
+            alpha_limit_top: value.alpha_limit_top,
+            alpha_limit_bottom: value.alpha_limit_bottom,
+            beta_limit_top: value.beta_limit_top,
+            beta_limit_bottom: value.beta_limit_bottom,
+        }
+    }
+}
+
+impl From<PlaneConstantsOverride> for PlaneConstantsOverrideGen {
+    fn from(value: PlaneConstantsOverride) -> Self {
+        PlaneConstantsOverrideGen {
+            m: value.m,
+            b: value.b,
+            s: value.s,
+            c_bar: value.c_bar,
+            x_cg_r: value.x_cg_r,
+            x_cg: value.x_cg,
+            h_eng: value.h_eng,
+            j_y: value.j_y,
+            j_xz: value.j_xz,
+            j_z: value.j_z,
+            j_x: value.j_x,
+        }
+    }
+}
+
+impl From<PlaneConstantsOverrideGen> for PlaneConstantsOverride {
+    fn from(value: PlaneConstantsOverrideGen) -> Self {
+        PlaneConstantsOverride {
+            m: value.m,
+            b: value.b,
+            s: value.s,
+            c_bar: value.c_bar,
+            x_cg_r: value.x_cg_r,
+            x_cg: value.x_cg,
+            h_eng: value.h_eng,
+            j_y: value.j_y,
+            j_xz: value.j_xz,
+            j_z: value.j_z,
+            j_x: value.j_x,
+        }
+    }
+}
+
+impl From<ControlLimitOverride> for ControlLimitOverrideGen {
+    fn from(value: ControlLimitOverride) -> Self {
+        ControlLimitOverrideGen {
+            thrust_cmd_limit_top: value.thrust_cmd_limit_top,
+            thrust_cmd_limit_bottom: value.thrust_cmd_limit_bottom,
+            thrust_rate_limit: value.thrust_rate_limit,
+            ele_cmd_limit_top: value.ele_cmd_limit_top,
+            ele_cmd_limit_bottom: value.ele_cmd_limit_bottom,
+            ele_rate_limit: value.ele_rate_limit,
+            ail_cmd_limit_top: value.ail_cmd_limit_top,
+            ail_cmd_limit_bottom: value.ail_cmd_limit_bottom,
+            ail_rate_limit: value.ail_rate_limit,
+            rud_cmd_limit_top: value.rud_cmd_limit_top,
+            rud_cmd_limit_bottom: value.rud_cmd_limit_bottom,
+            rud_rate_limit: value.rud_rate_limit,
+            alpha_limit_top: value.alpha_limit_top,
+            alpha_limit_bottom: value.alpha_limit_bottom,
+            beta_limit_top: value.beta_limit_top,
+            beta_limit_bottom: value.beta_limit_bottom,
+        }
+    }
+}
+
+impl From<ControlLimitOverrideGen> for ControlLimitOverride {
+    fn from(value: ControlLimitOverrideGen) -> Self {
+        ControlLimitOverride {
+            thrust_cmd_limit_top: value.thrust_cmd_limit_top,
+            thrust_cmd_limit_bottom: value.thrust_cmd_limit_bottom,
+            thrust_rate_limit: value.thrust_rate_limit,
+            ele_cmd_limit_top: value.ele_cmd_limit_top,
+            ele_cmd_limit_bottom: value.ele_cmd_limit_bottom,
+            ele_rate_limit: value.ele_rate_limit,
+            ail_cmd_limit_top: value.ail_cmd_limit_top,
+            ail_cmd_limit_bottom: value.ail_cmd_limit_bottom,
+            ail_rate_limit: value.ail_rate_limit,
+            rud_cmd_limit_top: value.rud_cmd_limit_top,
+            rud_cmd_limit_bottom: value.rud_cmd_limit_bottom,
+            rud_rate_limit: value.rud_rate_limit,
+            alpha_limit_top: value.alpha_limit_top,
+            alpha_limit_bottom: value.alpha_limit_bottom,
+            beta_limit_top: value.beta_limit_top,
+            beta_limit_bottom: value.beta_limit_bottom,
+        }
+    }
+}
+
+impl From<InitState> for InitStateGen {
+    fn from(value: InitState) -> Self {
+        InitStateGen {
+            state: Some(value.state.into()),
+            control: Some(value.control.into()),
+        }
+    }
+}
+
+impl From<InitStateGen> for InitState {
+    fn from(value: InitStateGen) -> Self {
+        InitState {
+            state: value.state.unwrap_or_default().into(),
+            control: value.control.unwrap_or_default().into(),
+        }
+    }
+}
+
 impl From<PlaneInitCfg> for PlaneInitCfgGen {
     fn from(value: PlaneInitCfg) -> Self {
         let deflection = match value.deflection {
@@ -342,6 +666,10 @@ impl From<PlaneInitCfg> for PlaneInitCfgGen {
             trim_init,
             flight_condition,
             optim_options,
+            diagnostic: value.diagnostic,
+            constants: value.constants.map(|a| a.into()),
+            ctrl_limit: value.ctrl_limit.map(|a| a.into()),
+            init_state: value.init_state.map(|a| a.into()),
         }
     }
 }
@@ -396,6 +724,10 @@ impl From<PlaneInitCfgGen> for PlaneInitCfg {
             trim_init,
             flight_condition,
             optim_options,
+            diagnostic: value.diagnostic,
+            constants: value.constants.map(|a| a.into()),
+            ctrl_limit: value.ctrl_limit.map(|a| a.into()),
+            init_state: value.init_state.map(|a| a.into()),
         }
     }
 }
@@ -440,6 +772,7 @@ impl From<PushPlaneResponseGen> for PushPlaneResponse {
     fn from(value: PushPlaneResponseGen) -> Self {
         PushPlaneResponse {
             plane_id: value.plane_id.unwrap().into(),
+            trim_output: value.trim_output.map(|a| a.into()),
         }
     }
 }
@@ -448,6 +781,656 @@ impl From<PushPlaneResponse> for PushPlaneResponseGen {
     fn from(value: PushPlaneResponse) -> Self {
         PushPlaneResponseGen {
             plane_id: Some(value.plane_id.into()),
+            trim_output: value.trim_output.map(|a| a.into()),
+        }
+    }
+}
+
+impl From<NelderMeadResultGen> for NelderMeadResult {
+    fn from(value: NelderMeadResultGen) -> Self {
+        NelderMeadResult {
+            x: Vector::from(value.x),
+            fval: value.fval,
+            iter: value.iter as usize,
+            fun_evals: value.fun_evals as usize,
+        }
+    }
+}
+
+impl From<NelderMeadResult> for NelderMeadResultGen {
+    fn from(value: NelderMeadResult) -> Self {
+        NelderMeadResultGen {
+            x: value.x.data,
+            fval: value.fval,
+            iter: value.iter as u32,
+            fun_evals: value.fun_evals as u32,
+        }
+    }
+}
+
+impl From<TrimOutputGen> for TrimOutput {
+    fn from(value: TrimOutputGen) -> Self {
+        TrimOutput {
+            state: value.state.unwrap_or_default().into(),
+            control: value.control.unwrap_or_default().into(),
+            state_extend: value.state_extend.unwrap_or_default().into(),
+            nelder_mead_result: value.nelder_mead_result.unwrap_or_default().into(),
+        }
+    }
+}
+
+impl From<TrimOutput> for TrimOutputGen {
+    fn from(value: TrimOutput) -> Self {
+        TrimOutputGen {
+            state: Some(value.state.into()),
+            control: Some(value.control.into()),
+            state_extend: Some(value.state_extend.into()),
+            nelder_mead_result: Some(value.nelder_mead_result.into()),
+        }
+    }
+}
+
+impl From<TrimRequestGen> for TrimRequest {
+    fn from(value: TrimRequestGen) -> Self {
+        TrimRequest {
+            model_id: value.model_id.unwrap().into(),
+            plane_init_cfg: value.plane_init_cfg.map(|a| a.into()),
+        }
+    }
+}
+
+impl From<TrimRequest> for TrimRequestGen {
+    fn from(value: TrimRequest) -> Self {
+        TrimRequestGen {
+            model_id: Some(value.model_id.into()),
+            plane_init_cfg: value.plane_init_cfg.map(|a| a.into()),
+        }
+    }
+}
+
+impl From<TrimResponseGen> for TrimResponse {
+    fn from(value: TrimResponseGen) -> Self {
+        TrimResponse {
+            model_id: value.model_id.unwrap().into(),
+            trim_output: value.trim_output.map(|a| a.into()),
+        }
+    }
+}
+
+impl From<TrimResponse> for TrimResponseGen {
+    fn from(value: TrimResponse) -> Self {
+        TrimResponseGen {
+            model_id: Some(value.model_id.into()),
+            trim_output: value.trim_output.map(|a| a.into()),
+        }
+    }
+}
+
+impl From<PlaneRequestGen> for PlaneRequest {
+    fn from(value: PlaneRequestGen) -> Self {
+        PlaneRequest {
+            plane_id: value.plane_id.map(|a| a.into()),
+        }
+    }
+}
+
+impl From<PlaneRequest> for PlaneRequestGen {
+    fn from(value: PlaneRequest) -> Self {
+        PlaneRequestGen {
+            plane_id: value.plane_id.map(|a| a.into()),
+        }
+    }
+}
+
+impl From<SetTimeScaleRequestGen> for SetTimeScaleRequest {
+    fn from(value: SetTimeScaleRequestGen) -> Self {
+        SetTimeScaleRequest {
+            plane_id: value.plane_id.map(|a| a.into()),
+            time_scale: value.time_scale,
+        }
+    }
+}
+
+impl From<SetTimeScaleRequest> for SetTimeScaleRequestGen {
+    fn from(value: SetTimeScaleRequest) -> Self {
+        SetTimeScaleRequestGen {
+            plane_id: value.plane_id.map(|a| a.into()),
+            time_scale: value.time_scale,
+        }
+    }
+}
+
+impl From<PlaneResponseGen> for PlaneResponse {
+    fn from(value: PlaneResponseGen) -> Self {
+        PlaneResponse {
+            plane_ids: value.plane_ids.into_iter().map(|a| a.into()).collect(),
+        }
+    }
+}
+
+impl From<PlaneResponse> for PlaneResponseGen {
+    fn from(value: PlaneResponse) -> Self {
+        PlaneResponseGen {
+            plane_ids: value.plane_ids.into_iter().map(|a| a.into()).collect(),
+        }
+    }
+}
+
+impl From<PlaneEventGen> for PlaneEvent {
+    fn from(value: PlaneEventGen) -> Self {
+        let kind = match value.kind() {
+            PlaneEventKindGen::Removed => PlaneEventKind::Removed,
+            PlaneEventKindGen::Paused => PlaneEventKind::Paused,
+            PlaneEventKindGen::Resumed => PlaneEventKind::Resumed,
+            PlaneEventKindGen::Reset => PlaneEventKind::Reset,
+            PlaneEventKindGen::TimeScaleChanged => PlaneEventKind::TimeScaleChanged,
+            PlaneEventKindGen::OwnerChanged => PlaneEventKind::OwnerChanged,
+        };
+        PlaneEvent {
+            plane_id: value.plane_id.unwrap().into(),
+            kind,
+            time_scale: value.time_scale,
+        }
+    }
+}
+
+impl From<PlaneEvent> for PlaneEventGen {
+    fn from(value: PlaneEvent) -> Self {
+        let kind = match value.kind {
+            PlaneEventKind::Removed => PlaneEventKindGen::Removed,
+            PlaneEventKind::Paused => PlaneEventKindGen::Paused,
+            PlaneEventKind::Resumed => PlaneEventKindGen::Resumed,
+            PlaneEventKind::Reset => PlaneEventKindGen::Reset,
+            PlaneEventKind::TimeScaleChanged => PlaneEventKindGen::TimeScaleChanged,
+            PlaneEventKind::OwnerChanged => PlaneEventKindGen::OwnerChanged,
+        };
+        PlaneEventGen {
+            plane_id: Some(value.plane_id.into()),
+            kind: kind as i32,
+            time_scale: value.time_scale,
+        }
+    }
+}
+
+impl From<PlaneInfoGen> for PlaneInfo {
+    fn from(value: PlaneInfoGen) -> Self {
+        let status = match value.status() {
+            PlaneStatusGen::Running => PlaneStatus::Running,
+            PlaneStatusGen::Paused => PlaneStatus::Paused,
+        };
+        PlaneInfo {
+            plane_id: value.plane_id.unwrap().into(),
+            model_id: value.model_id.unwrap().into(),
+            owner: value.owner,
+            status,
+            time: value.time,
+            pilots: value.pilots,
+        }
+    }
+}
+
+impl From<PlaneInfo> for PlaneInfoGen {
+    fn from(value: PlaneInfo) -> Self {
+        let status = match value.status {
+            PlaneStatus::Running => PlaneStatusGen::Running,
+            PlaneStatus::Paused => PlaneStatusGen::Paused,
+        };
+        PlaneInfoGen {
+            plane_id: Some(value.plane_id.into()),
+            model_id: Some(value.model_id.into()),
+            owner: value.owner,
+            status: status as i32,
+            time: value.time,
+            pilots: value.pilots,
+        }
+    }
+}
+
+impl From<ListPlanesResponseGen> for ListPlanesResponse {
+    fn from(value: ListPlanesResponseGen) -> Self {
+        ListPlanesResponse {
+            planes: value.planes.into_iter().map(|a| a.into()).collect(),
+        }
+    }
+}
+
+impl From<ListPlanesResponse> for ListPlanesResponseGen {
+    fn from(value: ListPlanesResponse) -> Self {
+        ListPlanesResponseGen {
+            planes: value.planes.into_iter().map(|a| a.into()).collect(),
+        }
+    }
+}
+
+impl From<GetPlaneStateRequestGen> for GetPlaneStateRequest {
+    fn from(value: GetPlaneStateRequestGen) -> Self {
+        GetPlaneStateRequest {
+            plane_id: value.plane_id.unwrap().into(),
+        }
+    }
+}
+
+impl From<GetPlaneStateRequest> for GetPlaneStateRequestGen {
+    fn from(value: GetPlaneStateRequest) -> Self {
+        GetPlaneStateRequestGen {
+            plane_id: Some(value.plane_id.into()),
+        }
+    }
+}
+
+impl From<GetModelDetailsRequestGen> for GetModelDetailsRequest {
+    fn from(value: GetModelDetailsRequestGen) -> Self {
+        GetModelDetailsRequest {
+            model_id: value.model_id.unwrap().into(),
+        }
+    }
+}
+
+impl From<GetModelDetailsRequest> for GetModelDetailsRequestGen {
+    fn from(value: GetModelDetailsRequest) -> Self {
+        GetModelDetailsRequestGen {
+            model_id: Some(value.model_id.into()),
+        }
+    }
+}
+
+impl From<GetModelDetailsResponseGen> for GetModelDetailsResponse {
+    fn from(value: GetModelDetailsResponseGen) -> Self {
+        GetModelDetailsResponse {
+            model_info: value.model_info.map(|a| a.into()),
+            constants: value.constants.map(|a| a.into()),
+            control_limit: value.control_limit.map(|a| a.into()),
+        }
+    }
+}
+
+impl From<GetModelDetailsResponse> for GetModelDetailsResponseGen {
+    fn from(value: GetModelDetailsResponse) -> Self {
+        GetModelDetailsResponseGen {
+            model_info: value.model_info.map(|a| a.into()),
+            constants: value.constants.map(|a| a.into()),
+            control_limit: value.control_limit.map(|a| a.into()),
+        }
+    }
+}
+
+impl From<ErrorResponseGen> for ErrorResponse {
+    fn from(value: ErrorResponseGen) -> Self {
+        let code = match value.code() {
+            ErrorCodeGen::Internal => ErrorCode::Internal,
+            ErrorCodeGen::InvalidRequest => ErrorCode::InvalidRequest,
+            ErrorCodeGen::InvalidArgs => ErrorCode::InvalidArgs,
+            ErrorCodeGen::InvalidId => ErrorCode::InvalidId,
+            ErrorCodeGen::ModelNotAvailable => ErrorCode::ModelNotAvailable,
+            ErrorCodeGen::PlaneNotFound => ErrorCode::PlaneNotFound,
+            ErrorCodeGen::TrimFailed => ErrorCode::TrimFailed,
+            ErrorCodeGen::HandshakeRequired => ErrorCode::HandshakeRequired,
+            ErrorCodeGen::MalformedFrame => ErrorCode::MalformedFrame,
+            ErrorCodeGen::PermissionDenied => ErrorCode::PermissionDenied,
+            ErrorCodeGen::NotPlaneOwner => ErrorCode::NotPlaneOwner,
+        };
+        ErrorResponse {
+            code,
+            message: value.message,
+            request: value.request,
+            request_id: value.request_id,
+        }
+    }
+}
+
+impl From<ErrorResponse> for ErrorResponseGen {
+    fn from(value: ErrorResponse) -> Self {
+        let code = match value.code {
+            ErrorCode::Internal => ErrorCodeGen::Internal,
+            ErrorCode::InvalidRequest => ErrorCodeGen::InvalidRequest,
+            ErrorCode::InvalidArgs => ErrorCodeGen::InvalidArgs,
+            ErrorCode::InvalidId => ErrorCodeGen::InvalidId,
+            ErrorCode::ModelNotAvailable => ErrorCodeGen::ModelNotAvailable,
+            ErrorCode::PlaneNotFound => ErrorCodeGen::PlaneNotFound,
+            ErrorCode::TrimFailed => ErrorCodeGen::TrimFailed,
+            ErrorCode::HandshakeRequired => ErrorCodeGen::HandshakeRequired,
+            ErrorCode::MalformedFrame => ErrorCodeGen::MalformedFrame,
+            ErrorCode::PermissionDenied => ErrorCodeGen::PermissionDenied,
+            ErrorCode::NotPlaneOwner => ErrorCodeGen::NotPlaneOwner,
+        };
+        ErrorResponseGen {
+            code: code as i32,
+            message: value.message,
+            request: value.request,
+            request_id: value.request_id,
+        }
+    }
+}
+
+impl From<FeatureGen> for Feature {
+    fn from(value: FeatureGen) -> Self {
+        match value {
+            FeatureGen::Compression => Feature::Compression,
+            FeatureGen::Batching => Feature::Batching,
+            FeatureGen::Subscription => Feature::Subscription,
+            FeatureGen::Checksum => Feature::Checksum,
+        }
+    }
+}
+
+impl From<Feature> for FeatureGen {
+    fn from(value: Feature) -> Self {
+        match value {
+            Feature::Compression => FeatureGen::Compression,
+            Feature::Batching => FeatureGen::Batching,
+            Feature::Subscription => FeatureGen::Subscription,
+            Feature::Checksum => FeatureGen::Checksum,
+        }
+    }
+}
+
+impl From<CompressionGen> for Compression {
+    fn from(value: CompressionGen) -> Self {
+        match value {
+            CompressionGen::None => Compression::None,
+            CompressionGen::Zstd => Compression::Zstd,
+            CompressionGen::Lz4 => Compression::Lz4,
+        }
+    }
+}
+
+impl From<Compression> for CompressionGen {
+    fn from(value: Compression) -> Self {
+        match value {
+            Compression::None => CompressionGen::None,
+            Compression::Zstd => CompressionGen::Zstd,
+            Compression::Lz4 => CompressionGen::Lz4,
+        }
+    }
+}
+
+impl From<ClientRoleGen> for ClientRole {
+    fn from(value: ClientRoleGen) -> Self {
+        match value {
+            ClientRoleGen::Pilot => ClientRole::Pilot,
+            ClientRoleGen::Observer => ClientRole::Observer,
+            ClientRoleGen::Admin => ClientRole::Admin,
+        }
+    }
+}
+
+impl From<ClientRole> for ClientRoleGen {
+    fn from(value: ClientRole) -> Self {
+        match value {
+            ClientRole::Pilot => ClientRoleGen::Pilot,
+            ClientRole::Observer => ClientRoleGen::Observer,
+            ClientRole::Admin => ClientRoleGen::Admin,
+        }
+    }
+}
+
+impl From<HelloGen> for Hello {
+    fn from(value: HelloGen) -> Self {
+        let role = value.role().into();
+        Hello {
+            protocol_version: value.protocol_version,
+            features: value.features().map(Feature::from).collect(),
+            compressions: value.compressions().map(Compression::from).collect(),
+            client_name: value.client_name,
+            client_version: value.client_version,
+            role,
+            token: value.token,
+            session: value.session,
+            room: value.room,
+            create_room: value.create_room,
+        }
+    }
+}
+
+impl From<Hello> for HelloGen {
+    fn from(value: Hello) -> Self {
+        HelloGen {
+            protocol_version: value.protocol_version,
+            client_name: value.client_name,
+            client_version: value.client_version,
+            role: ClientRoleGen::from(value.role) as i32,
+            features: value
+                .features
+                .into_iter()
+                .map(|f| FeatureGen::from(f) as i32)
+                .collect(),
+            compressions: value
+                .compressions
+                .into_iter()
+                .map(|c| CompressionGen::from(c) as i32)
+                .collect(),
+            token: value.token,
+            session: value.session,
+            room: value.room,
+            create_room: value.create_room,
+        }
+    }
+}
+
+impl From<HelloAckGen> for HelloAck {
+    fn from(value: HelloAckGen) -> Self {
+        HelloAck {
+            accepted: value.accepted,
+            protocol_version: value.protocol_version,
+            features: value.features().map(Feature::from).collect(),
+            compression: value.compression().into(),
+            compression_threshold: value.compression_threshold,
+            role: value.role().into(),
+            reason: value.reason,
+            server_name: value.server_name,
+            server_version: value.server_version,
+            session: value.session,
+            resumed_planes: value.resumed_planes.into_iter().map(|a| a.into()).collect(),
+            room: value.room,
+        }
+    }
+}
+
+impl From<HelloAck> for HelloAckGen {
+    fn from(value: HelloAck) -> Self {
+        HelloAckGen {
+            accepted: value.accepted,
+            reason: value.reason,
+            protocol_version: value.protocol_version,
+            server_name: value.server_name,
+            server_version: value.server_version,
+            features: value
+                .features
+                .into_iter()
+                .map(|f| FeatureGen::from(f) as i32)
+                .collect(),
+            compression: CompressionGen::from(value.compression) as i32,
+            compression_threshold: value.compression_threshold,
+            role: ClientRoleGen::from(value.role) as i32,
+            session: value.session,
+            resumed_planes: value.resumed_planes.into_iter().map(|a| a.into()).collect(),
+            room: value.room,
+        }
+    }
+}
+
+impl From<OutputFieldGen> for OutputField {
+    fn from(value: OutputFieldGen) -> Self {
+        match value {
+            OutputFieldGen::State => OutputField::State,
+            OutputFieldGen::Control => OutputField::Control,
+            OutputFieldGen::StateExtend => OutputField::StateExtend,
+        }
+    }
+}
+
+impl From<OutputField> for OutputFieldGen {
+    fn from(value: OutputField) -> Self {
+        match value {
+            OutputField::State => OutputFieldGen::State,
+            OutputField::Control => OutputFieldGen::Control,
+            OutputField::StateExtend => OutputFieldGen::StateExtend,
+        }
+    }
+}
+
+impl From<SubscriptionGen> for Subscription {
+    fn from(value: SubscriptionGen) -> Self {
+        Subscription {
+            all: value.all,
+            decimation: value.decimation,
+            fields: value.fields().map(OutputField::from).collect(),
+            plane_ids: value.plane_ids.into_iter().map(|a| a.into()).collect(),
+            owners: value.owners,
+        }
+    }
+}
+
+impl From<Subscription> for SubscriptionGen {
+    fn from(value: Subscription) -> Self {
+        SubscriptionGen {
+            all: value.all,
+            plane_ids: value.plane_ids.into_iter().map(|a| a.into()).collect(),
+            owners: value.owners,
+            decimation: value.decimation,
+            fields: value
+                .fields
+                .into_iter()
+                .map(|f| OutputFieldGen::from(f) as i32)
+                .collect(),
+        }
+    }
+}
+
+impl From<OpenUdpRequestGen> for OpenUdpRequest {
+    fn from(value: OpenUdpRequestGen) -> Self {
+        OpenUdpRequest {
+            outputs: value.outputs,
+        }
+    }
+}
+
+impl From<OpenUdpRequest> for OpenUdpRequestGen {
+    fn from(value: OpenUdpRequest) -> Self {
+        OpenUdpRequestGen {
+            outputs: value.outputs,
+        }
+    }
+}
+
+impl From<OpenUdpResponseGen> for OpenUdpResponse {
+    fn from(value: OpenUdpResponseGen) -> Self {
+        OpenUdpResponse {
+            port: value.port as u16,
+            token: value.token,
+        }
+    }
+}
+
+impl From<OpenUdpResponse> for OpenUdpResponseGen {
+    fn from(value: OpenUdpResponse) -> Self {
+        OpenUdpResponseGen {
+            port: value.port as u32,
+            token: value.token,
+        }
+    }
+}
+
+impl From<TransferPlaneRequestGen> for TransferPlaneRequest {
+    fn from(value: TransferPlaneRequestGen) -> Self {
+        TransferPlaneRequest {
+            plane_id: value.plane_id.unwrap().into(),
+            owner: value.owner,
+        }
+    }
+}
+
+impl From<TransferPlaneRequest> for TransferPlaneRequestGen {
+    fn from(value: TransferPlaneRequest) -> Self {
+        TransferPlaneRequestGen {
+            plane_id: Some(value.plane_id.into()),
+            owner: value.owner,
+        }
+    }
+}
+
+impl From<SharePlaneRequestGen> for SharePlaneRequest {
+    fn from(value: SharePlaneRequestGen) -> Self {
+        SharePlaneRequest {
+            plane_id: value.plane_id.unwrap().into(),
+            pilot: value.pilot,
+            revoke: value.revoke,
+        }
+    }
+}
+
+impl From<SharePlaneRequest> for SharePlaneRequestGen {
+    fn from(value: SharePlaneRequest) -> Self {
+        SharePlaneRequestGen {
+            plane_id: Some(value.plane_id.into()),
+            pilot: value.pilot,
+            revoke: value.revoke,
+        }
+    }
+}
+
+impl From<UdpDatagramGen> for UdpDatagram {
+    fn from(value: UdpDatagramGen) -> Self {
+        UdpDatagram {
+            token: value.token,
+            sequence: value.sequence,
+            payload: value.payload.map(|p| match p {
+                UdpPayloadGen::Control(control) => UdpPayload::Control(control.into()),
+                UdpPayloadGen::Output(output) => UdpPayload::Output(output.into()),
+            }),
+        }
+    }
+}
+
+impl From<UdpDatagram> for UdpDatagramGen {
+    fn from(value: UdpDatagram) -> Self {
+        UdpDatagramGen {
+            token: value.token,
+            sequence: value.sequence,
+            payload: value.payload.map(|p| match p {
+                UdpPayload::Control(control) => UdpPayloadGen::Control(control.into()),
+                UdpPayload::Output(output) => UdpPayloadGen::Output(output.into()),
+            }),
+        }
+    }
+}
+
+impl From<ClientStatusGen> for ClientStatus {
+    fn from(value: ClientStatusGen) -> Self {
+        ClientStatus {
+            addr: value.addr,
+            queued: value.queued,
+            dropped: value.dropped,
+            conflated: value.conflated,
+            lagged: value.lagged,
+        }
+    }
+}
+
+impl From<ClientStatus> for ClientStatusGen {
+    fn from(value: ClientStatus) -> Self {
+        ClientStatusGen {
+            addr: value.addr,
+            queued: value.queued,
+            dropped: value.dropped,
+            conflated: value.conflated,
+            lagged: value.lagged,
+        }
+    }
+}
+
+impl From<GetStatusResponseGen> for GetStatusResponse {
+    fn from(value: GetStatusResponseGen) -> Self {
+        GetStatusResponse {
+            clients: value.clients.into_iter().map(|c| c.into()).collect(),
+        }
+    }
+}
+
+impl From<GetStatusResponse> for GetStatusResponseGen {
+    fn from(value: GetStatusResponse) -> Self {
+        GetStatusResponseGen {
+            clients: value.clients.into_iter().map(|c| c.into()).collect(),
         }
     }
 }
@@ -460,6 +1443,23 @@ impl From<Args> for ArgsGen {
             Args::SendControl(req) => ArgsGen::SendControl(req.into()),
             Args::Tick => ArgsGen::Tick(()),
             Args::Disconnect => ArgsGen::Disconnect(()),
+            Args::Trim(req) => ArgsGen::Trim(req.into()),
+            Args::RemovePlane(req) => ArgsGen::RemovePlane(req.into()),
+            Args::PausePlane(req) => ArgsGen::PausePlane(req.into()),
+            Args::ResumePlane(req) => ArgsGen::ResumePlane(req.into()),
+            Args::ResetPlane(req) => ArgsGen::ResetPlane(req.into()),
+            Args::SetTimeScale(req) => ArgsGen::SetTimeScale(req.into()),
+            Args::ListPlanes => ArgsGen::ListPlanes(()),
+            Args::GetPlaneState(req) => ArgsGen::GetPlaneState(req.into()),
+            Args::GetModelDetails(req) => ArgsGen::GetModelDetails(req.into()),
+            Args::Hello(hello) => ArgsGen::Hello(hello.into()),
+            Args::Subscribe(sub) => ArgsGen::Subscribe(sub.into()),
+            Args::Unsubscribe(sub) => ArgsGen::Unsubscribe(sub.into()),
+            Args::OpenUdp(request) => ArgsGen::OpenUdp(request.into()),
+            Args::TransferPlane(request) => ArgsGen::TransferPlane(request.into()),
+            Args::SharePlane(request) => ArgsGen::SharePlane(request.into()),
+            Args::Ping => ArgsGen::Ping(()),
+            Args::GetStatus => ArgsGen::GetStatus(()),
         }
     }
 }
@@ -472,6 +1472,23 @@ impl From<ArgsGen> for Args {
             ArgsGen::SendControl(req) => Args::SendControl(req.into()),
             ArgsGen::Tick(()) => Args::Tick,
             ArgsGen::Disconnect(()) => Args::Disconnect,
+            ArgsGen::Trim(req) => Args::Trim(req.into()),
+            ArgsGen::RemovePlane(req) => Args::RemovePlane(req.into()),
+            ArgsGen::PausePlane(req) => Args::PausePlane(req.into()),
+            ArgsGen::ResumePlane(req) => Args::ResumePlane(req.into()),
+            ArgsGen::ResetPlane(req) => Args::ResetPlane(req.into()),
+            ArgsGen::SetTimeScale(req) => Args::SetTimeScale(req.into()),
+            ArgsGen::ListPlanes(()) => Args::ListPlanes,
+            ArgsGen::GetPlaneState(req) => Args::GetPlaneState(req.into()),
+            ArgsGen::GetModelDetails(req) => Args::GetModelDetails(req.into()),
+            ArgsGen::Hello(hello) => Args::Hello(hello.into()),
+            ArgsGen::Subscribe(sub) => Args::Subscribe(sub.into()),
+            ArgsGen::Unsubscribe(sub) => Args::Unsubscribe(sub.into()),
+            ArgsGen::OpenUdp(request) => Args::OpenUdp(request.into()),
+            ArgsGen::TransferPlane(request) => Args::TransferPlane(request.into()),
+            ArgsGen::SharePlane(request) => Args::SharePlane(request.into()),
+            ArgsGen::Ping(()) => Args::Ping,
+            ArgsGen::GetStatus(()) => Args::GetStatus,
         }
     }
 }
@@ -480,6 +1497,7 @@ impl From<ServiceCallGen> for ServiceCall {
     fn from(value: ServiceCallGen) -> Self {
         ServiceCall {
             name: value.name,
+            request_id: value.request_id,
             args: value.args.map(|a| a.into()),
         }
     }
@@ -489,6 +1507,7 @@ impl From<ServiceCall> for ServiceCallGen {
     fn from(value: ServiceCall) -> Self {
         ServiceCallGen {
             name: value.name,
+            request_id: value.request_id,
             args: value.args.map(|a| a.into()),
         }
     }
@@ -503,7 +1522,27 @@ impl From<ResponseGen> for Response {
             ResponseGen::Output(output) => Response::Output(output.into()),
             ResponseGen::LostPlane(id) => Response::LostPlane(id.into()),
             ResponseGen::NewPlane(id) => Response::NewPlane(id.into()),
-            ResponseGen::Error(e) => Response::Error(e),
+            ResponseGen::Error(e) => Response::Error(e.into()),
+            ResponseGen::Diagnostic(diagnostic) => Response::Diagnostic(diagnostic.into()),
+            ResponseGen::Trim(trim) => Response::Trim(trim.into()),
+            ResponseGen::RemovePlane(r) => Response::RemovePlane(r.into()),
+            ResponseGen::PausePlane(r) => Response::PausePlane(r.into()),
+            ResponseGen::ResumePlane(r) => Response::ResumePlane(r.into()),
+            ResponseGen::ResetPlane(r) => Response::ResetPlane(r.into()),
+            ResponseGen::SetTimeScale(r) => Response::SetTimeScale(r.into()),
+            ResponseGen::PlaneEvent(event) => Response::PlaneEvent(event.into()),
+            ResponseGen::ListPlanes(r) => Response::ListPlanes(r.into()),
+            ResponseGen::GetPlaneState(output) => Response::GetPlaneState(output.into()),
+            ResponseGen::GetModelDetails(r) => Response::GetModelDetails(r.into()),
+            ResponseGen::Hello(ack) => Response::Hello(ack.into()),
+            ResponseGen::Subscribe(sub) => Response::Subscribe(sub.into()),
+            ResponseGen::Unsubscribe(sub) => Response::Unsubscribe(sub.into()),
+            ResponseGen::OutputGroup(group) => Response::OutputGroup(group.into()),
+            ResponseGen::OpenUdp(udp) => Response::OpenUdp(udp.into()),
+            ResponseGen::TransferPlane(info) => Response::TransferPlane(info.into()),
+            ResponseGen::SharePlane(info) => Response::SharePlane(info.into()),
+            ResponseGen::Pong(()) => Response::Pong,
+            ResponseGen::GetStatus(status) => Response::GetStatus(status.into()),
         }
     }
 }
@@ -517,7 +1556,27 @@ impl From<Response> for ResponseGen {
             Response::Output(output) => ResponseGen::Output(output.into()),
             Response::LostPlane(id) => ResponseGen::LostPlane(id.into()),
             Response::NewPlane(id) => ResponseGen::NewPlane(id.into()),
-            Response::Error(e) => ResponseGen::Error(e),
+            Response::Error(e) => ResponseGen::Error(e.into()),
+            Response::Diagnostic(diagnostic) => ResponseGen::Diagnostic(diagnostic.into()),
+            Response::Trim(trim) => ResponseGen::Trim(trim.into()),
+            Response::RemovePlane(r) => ResponseGen::RemovePlane(r.into()),
+            Response::PausePlane(r) => ResponseGen::PausePlane(r.into()),
+            Response::ResumePlane(r) => ResponseGen::ResumePlane(r.into()),
+            Response::ResetPlane(r) => ResponseGen::ResetPlane(r.into()),
+            Response::SetTimeScale(r) => ResponseGen::SetTimeScale(r.into()),
+            Response::PlaneEvent(event) => ResponseGen::PlaneEvent(event.into()),
+            Response::ListPlanes(r) => ResponseGen::ListPlanes(r.into()),
+            Response::GetPlaneState(output) => ResponseGen::GetPlaneState(output.into()),
+            Response::GetModelDetails(r) => ResponseGen::GetModelDetails(r.into()),
+            Response::Hello(ack) => ResponseGen::Hello(ack.into()),
+            Response::Subscribe(sub) => ResponseGen::Subscribe(sub.into()),
+            Response::Unsubscribe(sub) => ResponseGen::Unsubscribe(sub.into()),
+            Response::OutputGroup(group) => ResponseGen::OutputGroup(group.into()),
+            Response::OpenUdp(udp) => ResponseGen::OpenUdp(udp.into()),
+            Response::TransferPlane(info) => ResponseGen::TransferPlane(info.into()),
+            Response::SharePlane(info) => ResponseGen::SharePlane(info.into()),
+            Response::Pong => ResponseGen::Pong(()),
+            Response::GetStatus(status) => ResponseGen::GetStatus(status.into()),
         }
     }
 }
@@ -526,6 +1585,7 @@ impl From<ServiceCallResponseGen> for ServiceCallResponse {
     fn from(value: ServiceCallResponseGen) -> Self {
         ServiceCallResponse {
             name: value.name,
+            request_id: value.request_id,
             response: value.response.map(|a| a.into()),
         }
     }
@@ -535,6 +1595,7 @@ impl From<ServiceCallResponse> for ServiceCallResponseGen {
     fn from(value: ServiceCallResponse) -> Self {
         ServiceCallResponseGen {
             name: value.name,
+            request_id: value.request_id,
             response: value.response.map(|a| a.into()),
         }
     }
@@ -569,3 +1630,32 @@ impl Decoder for ServiceCallResponse {
         Ok(Into::<ServiceCallResponse>::into(response))
     }
 }
+
+impl Encoder for UdpDatagram {
+    fn encode(self) -> fly_ruler_utils::error::FrResult<Vec<u8>> {
+        let datagram = Into::<UdpDatagramGen>::into(self);
+        Ok(datagram.encode_to_vec())
+    }
+}
+
+impl Decoder for UdpDatagram {
+    fn decode(input: &[u8]) -> Result<UdpDatagram, fly_ruler_utils::error::FrError> {
+        let datagram = UdpDatagramGen::decode(input)
+            .map_err(|e| fly_ruler_utils::error::FrError::Codec(e.to_string()))?;
+        // datagrams come from anywhere, reject the ones which can not be converted
+        let valid = match &datagram.payload {
+            Some(UdpPayloadGen::Control(control)) => control.plane_id.is_some(),
+            Some(UdpPayloadGen::Output(output)) => output
+                .id
+                .as_ref()
+                .is_some_and(|id| Uuid::parse_str(&id.id).is_ok()),
+            None => true,
+        };
+        if !valid {
+            return Err(fly_ruler_utils::error::FrError::Codec(
+                "udp datagram without a valid plane id".to_string(),
+            ));
+        }
+        Ok(Into::<UdpDatagram>::into(datagram))
+    }
+}
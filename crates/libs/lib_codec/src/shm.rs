@@ -0,0 +1,306 @@
+use fly_ruler_utils::{
+    error::{FrError, FrResult},
+    plane_model::CoreOutput,
+};
+use memmap2::{Mmap, MmapMut};
+use std::{
+    cell::UnsafeCell,
+    collections::HashMap,
+    fs::{File, OpenOptions},
+    hint,
+    mem::size_of,
+    path::Path,
+    ptr,
+    sync::atomic::{fence, AtomicU64, Ordering},
+};
+use uuid::Uuid;
+
+/// first bytes of the mapped file
+pub const SHM_MAGIC: u64 = u64::from_le_bytes(*b"FLYRULER");
+/// version of the layout of the mapped file
+pub const SHM_VERSION: u32 = 1;
+/// outputs kept for every plane, an entry is overwritten `SHM_RING_LEN` outputs later
+pub const SHM_RING_LEN: usize = 4;
+/// reads of an entry retried while the server overwrites it
+const SHM_READ_RETRIES: usize = 64;
+
+/// start of the mapped file, followed by `capacity` slots
+#[repr(C)]
+pub struct ShmHeader {
+    pub magic: u64,
+    pub version: u32,
+    pub capacity: u32,
+}
+
+/// one output of a plane, guarded by a seqlock:
+/// the server sets `sequence` to 0, writes the output, then publishes the new sequence
+#[repr(C)]
+pub struct ShmEntry {
+    /// 0 while the entry is written
+    sequence: AtomicU64,
+    time: UnsafeCell<f64>,
+    output: UnsafeCell<CoreOutput>,
+}
+
+impl ShmEntry {
+    /// number of the output in the slot, starting from 1
+    pub fn sequence(&self) -> u64 {
+        self.sequence.load(Ordering::Acquire)
+    }
+
+    /// copy of the sequence, time and output, `None` if the server kept overwriting the entry
+    pub fn read(&self) -> Option<(u64, f64, CoreOutput)> {
+        for _ in 0..SHM_READ_RETRIES {
+            let sequence = self.sequence.load(Ordering::Acquire);
+            if sequence == 0 {
+                hint::spin_loop();
+                continue;
+            }
+            // the copy may be torn, it is only kept if the sequence did not move meanwhile
+            let (time, output) = unsafe {
+                (
+                    ptr::read_volatile(self.time.get()),
+                    ptr::read_volatile(self.output.get()),
+                )
+            };
+            fence(Ordering::Acquire);
+            if self.sequence.load(Ordering::Relaxed) == sequence {
+                return Some((sequence, time, output));
+            }
+        }
+        None
+    }
+}
+
+/// ring of the latest outputs of a plane
+#[repr(C)]
+pub struct ShmSlot {
+    /// bytes of the id in native order, nil for a free slot
+    plane_id: [AtomicU64; 2],
+    /// number of outputs written since the plane took the slot
+    head: AtomicU64,
+    entries: [ShmEntry; SHM_RING_LEN],
+}
+
+impl ShmSlot {
+    /// a torn id, read while the slot changes hands, matches no plane
+    pub fn plane_id(&self) -> Uuid {
+        let mut bytes = [0; 16];
+        bytes[..8].copy_from_slice(&self.plane_id[0].load(Ordering::Acquire).to_ne_bytes());
+        bytes[8..].copy_from_slice(&self.plane_id[1].load(Ordering::Acquire).to_ne_bytes());
+        Uuid::from_bytes(bytes)
+    }
+
+    fn set_plane_id(&self, plane_id: &Uuid) {
+        let bytes = plane_id.as_bytes();
+        let (low, high) = bytes.split_at(8);
+        self.plane_id[0].store(
+            u64::from_ne_bytes(low.try_into().unwrap()),
+            Ordering::Release,
+        );
+        self.plane_id[1].store(
+            u64::from_ne_bytes(high.try_into().unwrap()),
+            Ordering::Release,
+        );
+    }
+
+    /// the latest output, `None` before the first one
+    pub fn latest(&self) -> Option<&ShmEntry> {
+        match self.head.load(Ordering::Acquire) {
+            0 => None,
+            head => Some(self.entry(head)),
+        }
+    }
+
+    fn entry(&self, sequence: u64) -> &ShmEntry {
+        &self.entries[((sequence - 1) % SHM_RING_LEN as u64) as usize]
+    }
+
+    fn push(&self, time: f64, output: &CoreOutput) {
+        let head = self.head.load(Ordering::Relaxed) + 1;
+        let entry = self.entry(head);
+        entry.sequence.store(0, Ordering::Relaxed);
+        fence(Ordering::Release);
+        unsafe {
+            ptr::write_volatile(entry.time.get(), time);
+            ptr::write_volatile(entry.output.get(), *output);
+        }
+        entry.sequence.store(head, Ordering::Release);
+        self.head.store(head, Ordering::Release);
+    }
+}
+
+fn map_len(capacity: u32) -> usize {
+    size_of::<ShmHeader>() + capacity as usize * size_of::<ShmSlot>()
+}
+
+/// # Safety
+/// `ptr` is the start of a mapped file of at least `map_len(capacity)` bytes
+unsafe fn slots<'a>(ptr: *const u8, capacity: u32) -> &'a [ShmSlot] {
+    let first = ptr.add(size_of::<ShmHeader>()) as *const ShmSlot;
+    std::slice::from_raw_parts(first, capacity as usize)
+}
+
+/// the latest outputs of the planes in a memory mapped file, written by the server
+pub struct ShmWriter {
+    map: MmapMut,
+    capacity: u32,
+    planes: HashMap<Uuid, usize>,
+}
+
+impl ShmWriter {
+    /// create or truncate the file with room for `capacity` planes
+    pub fn create(path: impl AsRef<Path>, capacity: u32) -> FrResult<Self> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
+        // the file is zero filled, so every slot is free
+        file.set_len(map_len(capacity) as u64)?;
+        let mut map = unsafe { MmapMut::map_mut(&file)? };
+        let header = ShmHeader {
+            magic: SHM_MAGIC,
+            version: SHM_VERSION,
+            capacity,
+        };
+        unsafe { std::ptr::write(map.as_mut_ptr() as *mut ShmHeader, header) };
+        Ok(Self {
+            map,
+            capacity,
+            planes: HashMap::new(),
+        })
+    }
+
+    fn slots(&self) -> &[ShmSlot] {
+        unsafe { slots(self.map.as_ptr(), self.capacity) }
+    }
+
+    /// write the output of a plane, `false` if every slot is taken
+    pub fn write(&mut self, plane_id: &Uuid, time: f64, output: &CoreOutput) -> bool {
+        let index = match self.planes.get(plane_id) {
+            Some(index) => *index,
+            None => {
+                let Some(index) = self.slots().iter().position(|s| s.plane_id().is_nil()) else {
+                    return false;
+                };
+                self.slots()[index].set_plane_id(plane_id);
+                self.planes.insert(*plane_id, index);
+                index
+            }
+        };
+        self.slots()[index].push(time, output);
+        true
+    }
+
+    /// free the slot of a removed plane
+    pub fn remove(&mut self, plane_id: &Uuid) {
+        if let Some(index) = self.planes.remove(plane_id) {
+            let slot = &self.slots()[index];
+            slot.head.store(0, Ordering::Release);
+            slot.set_plane_id(&Uuid::nil());
+        }
+    }
+
+    /// free the slots of the planes `live` rejects, such as the planes whose removal was missed
+    pub fn retain(&mut self, mut live: impl FnMut(&Uuid) -> bool) {
+        let removed: Vec<Uuid> = self.planes.keys().filter(|id| !live(id)).copied().collect();
+        for plane_id in &removed {
+            self.remove(plane_id);
+        }
+    }
+}
+
+/// the latest outputs of the planes in a memory mapped file, read in place by the clients
+/// on the same host
+pub struct ShmReader {
+    map: Mmap,
+    capacity: u32,
+}
+
+impl ShmReader {
+    pub fn open(path: impl AsRef<Path>) -> FrResult<Self> {
+        let file = File::open(path)?;
+        let map = unsafe { Mmap::map(&file)? };
+        if map.len() < size_of::<ShmHeader>() {
+            return Err(FrError::Codec("shared memory is too short".to_string()));
+        }
+        let header = unsafe { &*(map.as_ptr() as *const ShmHeader) };
+        if header.magic != SHM_MAGIC || header.version != SHM_VERSION {
+            return Err(FrError::Codec(format!(
+                "shared memory layout {} is not supported",
+                header.version
+            )));
+        }
+        let capacity = header.capacity;
+        if map.len() < map_len(capacity) {
+            return Err(FrError::Codec(format!(
+                "shared memory is too short for {} planes",
+                capacity
+            )));
+        }
+        Ok(Self { map, capacity })
+    }
+
+    pub fn slots(&self) -> &[ShmSlot] {
+        unsafe { slots(self.map.as_ptr(), self.capacity) }
+    }
+
+    /// the slot of a plane, the slot is given to another plane after the plane is removed
+    pub fn find(&self, plane_id: &Uuid) -> Option<&ShmSlot> {
+        self.slots().iter().find(|s| s.plane_id() == *plane_id)
+    }
+}
+
+#[cfg(test)]
+mod codec_shm_tests {
+    use super::*;
+    use fly_ruler_utils::plane_model::{Control, State, StateExtend};
+
+    fn output(thrust: f64) -> CoreOutput {
+        CoreOutput::new(
+            State::default(),
+            Control {
+                thrust,
+                elevator: 0.0,
+                aileron: 0.0,
+                rudder: 0.0,
+            },
+            StateExtend::default(),
+        )
+    }
+
+    #[test]
+    fn test_latest_output() {
+        let path = std::env::temp_dir().join(format!("fly_ruler_shm_{}", Uuid::new_v4()));
+        let mut writer = ShmWriter::create(&path, 2).unwrap();
+        let reader = ShmReader::open(&path).unwrap();
+        let (a, b, c) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+
+        for i in 1..=10 {
+            assert!(writer.write(&a, i as f64, &output(i as f64)));
+        }
+        assert!(writer.write(&b, 0.5, &output(0.5)));
+        assert!(!writer.write(&c, 0.5, &output(0.5)));
+
+        let latest = reader.find(&a).unwrap().latest().unwrap();
+        assert_eq!(latest.sequence(), 10);
+        let (sequence, time, copy) = latest.read().unwrap();
+        assert_eq!(sequence, 10);
+        assert_eq!(time, 10.0);
+        assert_eq!(copy.control.thrust, 10.0);
+
+        writer.remove(&a);
+        assert!(reader.find(&a).is_none());
+        assert!(writer.write(&c, 1.0, &output(1.0)));
+        assert_eq!(reader.find(&c).unwrap().latest().unwrap().sequence(), 1);
+
+        writer.retain(|id| *id == c);
+        assert!(reader.find(&b).is_none());
+        assert!(reader.find(&c).is_some());
+        assert!(writer.write(&a, 2.0, &output(2.0)));
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -56,8 +56,9 @@ fn criterion_benchmark(c: &mut Criterion) {
     let plane = Rc::new(RefCell::new(MechanicalModel::new(&model).unwrap()));
     let trim_target = TrimTarget::new(15000.0, 500.0, None, None);
     let trim_output = trim(plane, trim_target, None, CL, None, None).unwrap();
+    let constants = model.load_constants().unwrap();
     let plane_block = Rc::new(RefCell::new(
-        PlaneBlock::new("123", &model, &trim_output, &[0.0, 0.0, 0.0], CL).unwrap(),
+        PlaneBlock::new("123", &model, &trim_output, &[0.0, 0.0, 0.0], CL, constants).unwrap(),
     ));
 
     group.bench_function("plane", |b| {
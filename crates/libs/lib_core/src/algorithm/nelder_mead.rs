@@ -37,7 +37,7 @@ impl Default for NelderMeadOptions {
 }
 
 /// 单纯形搜索法结果
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NelderMeadResult {
     /// 最小值所在点
     pub x: Vector,
@@ -8,24 +8,22 @@ where
     fn now(&mut self) -> Duration;
     fn pause(&mut self);
     fn resume(&mut self);
+    fn set_time_scale(&mut self, time_scale: f64);
 }
 
 pub struct Clock {
-    actual_start_time: Instant,
-    pause_time: Instant,
     last_call: Instant,
     is_pause: bool,
+    time_scale: f64,
     now_virtual_time: Duration,
 }
 
 impl Clock {
     pub fn new() -> Self {
-        let actual_start_time = Instant::now();
         Self {
-            actual_start_time,
-            pause_time: actual_start_time,
-            last_call: actual_start_time,
+            last_call: Instant::now(),
             is_pause: false,
+            time_scale: 1.0,
             now_virtual_time: Duration::from_secs(0),
         }
     }
@@ -34,9 +32,7 @@ impl Clock {
 impl AsClock for Clock {
     /// start clock
     fn start(&mut self) {
-        self.actual_start_time = Instant::now();
-        self.pause_time = self.actual_start_time;
-        self.last_call = self.actual_start_time;
+        self.last_call = Instant::now();
         self.is_pause = false;
         self.now_virtual_time = Duration::from_secs(0);
     }
@@ -44,20 +40,18 @@ impl AsClock for Clock {
     /// virtual current time
     fn now(&mut self) -> Duration {
         if self.is_pause {
-            return self.pause_time - self.actual_start_time;
+            return self.now_virtual_time;
         }
         let now = Instant::now();
-        let virtual_delta_time = now - self.actual_start_time;
-
+        self.now_virtual_time += (now - self.last_call).mul_f64(self.time_scale);
         self.last_call = now;
-        self.now_virtual_time = virtual_delta_time;
-        return virtual_delta_time;
+        self.now_virtual_time
     }
 
     // pause
     fn pause(&mut self) {
         if !self.is_pause {
-            self.pause_time = Instant::now();
+            self.now();
             self.is_pause = true;
         }
     }
@@ -65,34 +59,34 @@ impl AsClock for Clock {
     // resume
     fn resume(&mut self) {
         if self.is_pause {
-            let pause_duration = Instant::now() - self.pause_time;
-            self.actual_start_time += pause_duration;
-            self.last_call += pause_duration;
-            self.pause_time = self.actual_start_time;
+            self.last_call = Instant::now();
             self.is_pause = false;
         }
     }
+
+    /// the time elapsed before is kept, only the following time is scaled
+    fn set_time_scale(&mut self, time_scale: f64) {
+        self.now();
+        self.time_scale = time_scale;
+    }
 }
 
 unsafe impl Send for Clock {}
 
 pub struct FixedClock {
-    actual_start_time: Instant,
-    last_call: Instant,
     is_pause: bool,
     time_scale: f64,
     sample_time: Duration,
+    now_virtual_time: Duration,
 }
 
 impl FixedClock {
     pub fn new(sample_time: Duration, time_scale: Option<f64>) -> Self {
-        let actual_start_time = Instant::now();
         Self {
-            actual_start_time,
-            last_call: actual_start_time,
             is_pause: false,
             time_scale: time_scale.unwrap_or(1.0),
             sample_time,
+            now_virtual_time: Duration::from_secs(0),
         }
     }
 }
@@ -102,17 +96,16 @@ unsafe impl Send for FixedClock {}
 impl AsClock for FixedClock {
     /// start clock
     fn start(&mut self) {
-        self.actual_start_time = Instant::now();
-        self.last_call = self.actual_start_time;
         self.is_pause = false;
+        self.now_virtual_time = Duration::from_secs(0);
     }
 
     /// virtual current time
     fn now(&mut self) -> Duration {
         if !self.is_pause {
-            self.last_call += self.sample_time;
+            self.now_virtual_time += self.sample_time.mul_f64(self.time_scale);
         }
-        return (self.last_call - self.actual_start_time).mul_f64(self.time_scale);
+        self.now_virtual_time
     }
 
     // pause
@@ -128,6 +121,11 @@ impl AsClock for FixedClock {
             self.is_pause = false;
         }
     }
+
+    /// the time elapsed before is kept, only the following steps are scaled
+    fn set_time_scale(&mut self, time_scale: f64) {
+        self.time_scale = time_scale;
+    }
 }
 
 #[cfg(test)]
@@ -167,4 +165,18 @@ mod core_clock_tests {
         let r = clock.now();
         assert_eq!(r.as_secs(), 5)
     }
+
+    #[tokio::test]
+    async fn test_set_time_scale() {
+        let mut clock = FixedClock::new(Duration::from_millis(1000), None);
+        clock.start();
+        let r = clock.now();
+        assert_eq!(r.as_millis(), 1000);
+        clock.set_time_scale(2.0);
+        let r = clock.now();
+        assert_eq!(r.as_millis(), 3000);
+        clock.pause();
+        let r = clock.now();
+        assert_eq!(r.as_millis(), 3000);
+    }
 }
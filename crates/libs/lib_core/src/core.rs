@@ -4,15 +4,20 @@ use crate::{
     parts::{
         block::PlaneBlock,
         flight::MechanicalModel,
-        trim::{trim, TrimInit, TrimOutput, TrimTarget},
+        trim::{init_state, trim, InitState, TrimInit, TrimOutput, TrimTarget},
     },
 };
 use fly_ruler_plugin::AerodynamicModel;
 use fly_ruler_utils::{
+    diagnostic_channel,
     error::{FatalCoreError, FrError, FrResult},
-    input_channel,
-    plane_model::{CoreOutput, FlightCondition},
-    state_channel, CancellationToken, InputReceiver, InputSender, OutputReceiver, OutputSender,
+    input_channel, plane_command_channel,
+    plane_model::{
+        ControlLimit, ControlLimitOverride, CoreOutput, FlightCondition, PlaneCommand,
+        PlaneConstants, PlaneConstantsOverride,
+    },
+    state_channel, CancellationToken, DiagnosticReceiver, DiagnosticSender, InputReceiver,
+    InputSender, OutputReceiver, OutputSender, PlaneCommandReceiver, PlaneCommandSender,
 };
 use serde::{Deserialize, Serialize};
 use std::{cell::RefCell, rc::Rc, time::Duration};
@@ -41,6 +46,14 @@ pub struct PlaneInitCfg {
     pub trim_init: Option<TrimInit>,
     pub flight_condition: Option<FlightCondition>,
     pub optim_options: Option<NelderMeadOptions>,
+    /// partial override of the constants loaded from the model
+    pub constants: Option<PlaneConstantsOverride>,
+    /// partial override of the control limits loaded from the model
+    pub ctrl_limit: Option<ControlLimitOverride>,
+    /// start from this state and control, trim is skipped when set
+    pub init_state: Option<InitState>,
+    /// open a diagnostic channel for this plane
+    pub diagnostic: Option<bool>,
 }
 
 impl std::fmt::Display for PlaneInitCfg {
@@ -58,14 +71,32 @@ impl std::fmt::Display for PlaneInitCfg {
             "Flight Condition: {}",
             self.flight_condition.unwrap_or_default()
         )?;
-        write!(
+        writeln!(
             f,
             "Optim Options: \n{}",
             self.optim_options.unwrap_or_default()
-        )
+        )?;
+        writeln!(f, "Constants Override: {:?}", self.constants)?;
+        writeln!(f, "Control Limit Override: {:?}", self.ctrl_limit)?;
+        match self.init_state {
+            Some(init_state) => writeln!(f, "Init State: \n{}", init_state)?,
+            None => writeln!(f, "Init State: None")?,
+        }
+        write!(f, "Diagnostic: {}", self.diagnostic.unwrap_or(false))
     }
 }
 
+/// id, output, input, task, trim result, diagnostic and commands of a pushed plane
+pub type PushedPlane = (
+    Uuid,
+    OutputReceiver,
+    InputSender,
+    JoinHandle<FrResult<()>>,
+    TrimOutput,
+    Option<DiagnosticReceiver>,
+    PlaneCommandSender,
+);
+
 pub struct Core {
     // controllers
     clock_mode: ClockMode,
@@ -89,33 +120,28 @@ impl Core {
         controller_buffer: usize,
         init_cfg: PlaneInitCfg,
         cancellation_token: CancellationToken,
-    ) -> Result<
-        (
-            Uuid,
-            OutputReceiver,
-            InputSender,
-            JoinHandle<FrResult<()>>,
-            TrimOutput,
-        ),
-        FrError,
-    > {
-        let ctrl_limits = model
-            .load_ctrl_limits()
-            .map_err(|e| FrError::Core(FatalCoreError::from(e)))?;
-        let plane = Rc::new(RefCell::new(
-            MechanicalModel::new(model).map_err(|e| FrError::Core(e))?,
-        ));
-
-        let trim_output = trim(
-            plane,
-            init_cfg.trim_target,
-            init_cfg.trim_init,
-            ctrl_limits,
-            init_cfg.flight_condition,
-            init_cfg.optim_options,
+    ) -> Result<PushedPlane, FrError> {
+        let clock_mode = self.clock_mode;
+        self.push_plane_with_clock(
+            model,
+            controller_buffer,
+            init_cfg,
+            clock_mode,
+            cancellation_token,
         )
-        .map_err(|e| FrError::Core(e))?;
-        event!(Level::DEBUG, "model trim successfully");
+    }
+
+    /// add a new plant driven by `clock_mode` instead of the clock of the core
+    #[instrument(skip(self, model, cancellation_token, init_cfg), level = Level::DEBUG)]
+    pub fn push_plane_with_clock(
+        &mut self,
+        model: &AerodynamicModel,
+        controller_buffer: usize,
+        init_cfg: PlaneInitCfg,
+        clock_mode: ClockMode,
+        cancellation_token: CancellationToken,
+    ) -> Result<PushedPlane, FrError> {
+        let (trim_output, ctrl_limits, constants) = Self::init_plane(model, &init_cfg)?;
         let id = Uuid::new_v4();
 
         let plane_block = PlaneBlock::new(
@@ -124,21 +150,39 @@ impl Core {
             &trim_output,
             &init_cfg.deflection.unwrap_or([0.0, 0.0, 0.0]),
             ctrl_limits,
+            constants,
         )
         .map_err(|e| FrError::Core(e))?;
         event!(Level::DEBUG, "model build successfully");
 
-        let (tx, rx) = state_channel(&CoreOutput {
+        let init_output = CoreOutput {
             state: trim_output.state,
             control: trim_output.control,
             state_extend: trim_output.state_extend,
-        });
+        };
+        let (tx, rx) = state_channel(&init_output);
         let (tx1, rx1) = input_channel(controller_buffer);
-
-        let handler = match self.clock_mode {
-            ClockMode::Realtime(_) => {
-                self.build_task(id, Clock::new(), plane_block, tx, rx1, cancellation_token)
+        let (tx3, rx3) = plane_command_channel(16);
+        let (tx2, rx2) = match init_cfg.diagnostic {
+            Some(true) => {
+                let (tx2, rx2) = diagnostic_channel();
+                (Some(tx2), Some(rx2))
             }
+            _ => (None, None),
+        };
+
+        let handler = match clock_mode {
+            ClockMode::Realtime(_) => self.build_task(
+                id,
+                Clock::new(),
+                plane_block,
+                init_output,
+                tx,
+                tx2,
+                rx1,
+                rx3,
+                cancellation_token,
+            ),
             ClockMode::Fixed {
                 sample_time,
                 time_scale,
@@ -146,25 +190,86 @@ impl Core {
                 id,
                 FixedClock::new(Duration::from_millis(sample_time), time_scale),
                 plane_block,
+                init_output,
                 tx,
+                tx2,
                 rx1,
+                rx3,
                 cancellation_token,
             ),
         };
 
         event!(Level::DEBUG, "plane {id} append successfully");
-        Ok((id, rx, tx1, handler, trim_output))
+        Ok((id, rx, tx1, handler, trim_output, rx2, tx3))
+    }
+
+    /// trim a plane without spawning it
+    #[instrument(skip(self, model, init_cfg), level = Level::DEBUG)]
+    pub fn trim(
+        &self,
+        model: &AerodynamicModel,
+        init_cfg: PlaneInitCfg,
+    ) -> Result<TrimOutput, FrError> {
+        let (trim_output, _, _) = Self::init_plane(model, &init_cfg)?;
+        Ok(trim_output)
+    }
+
+    /// apply the overrides and find the initial state of a plane
+    fn init_plane(
+        model: &AerodynamicModel,
+        init_cfg: &PlaneInitCfg,
+    ) -> Result<(TrimOutput, ControlLimit, PlaneConstants), FrError> {
+        let ctrl_limits = model
+            .load_ctrl_limits()
+            .map_err(|e| FrError::Core(FatalCoreError::from(e)))?;
+        let ctrl_limits = match init_cfg.ctrl_limit {
+            Some(ctrl_limit) => ctrl_limits.with_override(&ctrl_limit)?,
+            None => ctrl_limits,
+        };
+        let mut plane = MechanicalModel::new(model).map_err(|e| FrError::Core(e))?;
+        if let Some(constants) = init_cfg.constants {
+            plane.set_constants(plane.constants().with_override(&constants)?);
+        }
+        let constants = plane.constants();
+        let plane = Rc::new(RefCell::new(plane));
+
+        let trim_output = match init_cfg.init_state {
+            Some(state) => {
+                ctrl_limits.check_control(&state.control)?;
+                let trim_output = init_state(plane, state).map_err(FrError::Core)?;
+                event!(Level::DEBUG, "model init from explicit state, trim skipped");
+                trim_output
+            }
+            None => {
+                let trim_output = trim(
+                    plane,
+                    init_cfg.trim_target,
+                    init_cfg.trim_init,
+                    ctrl_limits,
+                    init_cfg.flight_condition,
+                    init_cfg.optim_options,
+                )
+                .map_err(|e| FrError::Core(e))?;
+                event!(Level::DEBUG, "model trim successfully");
+                trim_output
+            }
+        };
+        Ok((trim_output, ctrl_limits, constants))
     }
 
     /// main loop step
-    #[instrument(skip(self, plane, cancellation_token, clock, state_sender, controller), level = Level::DEBUG)]
+    #[allow(clippy::too_many_arguments)]
+    #[instrument(skip(self, plane, init_output, cancellation_token, clock, state_sender, diagnostic_sender, controller, commands), level = Level::DEBUG)]
     fn build_task(
         &self,
         plane_id: Uuid,
         mut clock: impl AsClock + 'static,
         mut plane: PlaneBlock,
+        init_output: CoreOutput,
         state_sender: OutputSender,
+        mut diagnostic_sender: Option<DiagnosticSender>,
         mut controller: InputReceiver,
+        mut commands: PlaneCommandReceiver,
         cancellation_token: CancellationToken,
     ) -> JoinHandle<FrResult<()>> {
         let handler: JoinHandle<FrResult<()>> = tokio::spawn({
@@ -173,39 +278,106 @@ impl Core {
                 let ctk = cancellation_token.clone();
                 let h: JoinHandle<FrResult<()>> = tokio::spawn(async move {
                     clock.start();
+                    let mut is_pause = false;
+                    let mut commands_open = true;
+                    // time of the last step, the clock only moves on a step
+                    let mut t = Duration::ZERO;
                     loop {
                         if cancellation_token.is_cancelled() {
                             break;
                         }
-                        let t = clock.now();
-                        let control = controller.recv().await;
-                        match control {
-                            Some(control) => {
+                        tokio::select! {
+                            biased;
+                            command = commands.recv(), if commands_open => {
+                                let command = match command {
+                                    Some(command) => command,
+                                    None => {
+                                        commands_open = false;
+                                        continue;
+                                    }
+                                };
                                 event!(
-                                    Level::DEBUG,
-                                    "[t:{:.4}] Plane {plane_id} received Control: {}",
+                                    Level::INFO,
+                                    "[t:{:.4}] Plane {plane_id} received Command: {}",
                                     t.as_secs_f32(),
-                                    control
-                                );
-
-                                let result = plane
-                                    .update(control, t.as_secs_f64())
-                                    .map_err(|e| FrError::Core(e))?;
-
-                                event!(
-                                    Level::DEBUG,
-                                    "[t:{:.4}] Plane {plane_id} output:\n{result}",
-                                    t.as_secs_f32()
+                                    command
                                 );
-
-                                state_sender.send(&(t.as_secs_f64(), result))?;
+                                match command {
+                                    PlaneCommand::Remove => break,
+                                    PlaneCommand::Pause => {
+                                        clock.pause();
+                                        is_pause = true;
+                                    }
+                                    PlaneCommand::Resume => {
+                                        clock.resume();
+                                        is_pause = false;
+                                    }
+                                    PlaneCommand::Reset => {
+                                        plane.reset();
+                                        clock.start();
+                                        t = Duration::ZERO;
+                                        if is_pause {
+                                            clock.pause();
+                                        }
+                                        state_sender.send(&(0.0, init_output))?;
+                                    }
+                                    PlaneCommand::SetTimeScale(time_scale) => {
+                                        // `mul_f64` of the clock panics on such a scale
+                                        if !time_scale.is_finite() || time_scale <= 0.0 {
+                                            event!(
+                                                Level::WARN,
+                                                "Plane {plane_id} ignored time scale {}",
+                                                time_scale
+                                            );
+                                            continue;
+                                        }
+                                        clock.set_time_scale(time_scale);
+                                    }
+                                }
                             }
-                            None => {
-                                return Err(FrError::Core(FatalCoreError::Controller(
-                                    plane_id.to_string(),
-                                )));
+                            control = controller.recv() => {
+                                match control {
+                                    Some(control) => {
+                                        if is_pause {
+                                            continue;
+                                        }
+                                        t = clock.now();
+                                        event!(
+                                            Level::DEBUG,
+                                            "[t:{:.4}] Plane {plane_id} received Control: {}",
+                                            t.as_secs_f32(),
+                                            control
+                                        );
+
+                                        let result = plane
+                                            .update(control, t.as_secs_f64())
+                                            .map_err(|e| FrError::Core(e))?;
+
+                                        event!(
+                                            Level::DEBUG,
+                                            "[t:{:.4}] Plane {plane_id} output:\n{result}",
+                                            t.as_secs_f32()
+                                        );
+
+                                        state_sender.send(&(t.as_secs_f64(), result))?;
+                                        // the diagnostics are opt-in, the plane keeps flying without a reader
+                                        if let Some(ref sender) = diagnostic_sender {
+                                            if sender
+                                                .send(&(t.as_secs_f64(), plane.diagnostic()))
+                                                .is_err()
+                                            {
+                                                diagnostic_sender = None;
+                                            }
+                                        }
+                                    }
+                                    None => {
+                                        return Err(FrError::Core(FatalCoreError::Controller(
+                                            plane_id.to_string(),
+                                        )));
+                                    }
+                                };
                             }
-                        };
+                        }
                     }
                     plane.delete_model();
                     Ok(())
@@ -268,6 +440,10 @@ mod core_tests {
             trim_target,
             flight_condition: None,
             optim_options: nm_options,
+            constants: None,
+            ctrl_limit: None,
+            init_state: None,
+            diagnostic: None,
         };
 
         (model, Core::new(core_init), plane_init)
@@ -280,7 +456,7 @@ mod core_tests {
         let ctk = CancellationToken::new();
         let res = core.push_plane(&model, 10, plane_init, ctk.clone());
         assert!(matches!(res, Ok(_)));
-        let (_id, mut viewer, controller, handler, _) = res.unwrap();
+        let (_id, mut viewer, controller, handler, _, _, _) = res.unwrap();
 
         let h = std::thread::spawn(move || {
             let rt = tokio::runtime::Builder::new_current_thread()
@@ -313,6 +489,45 @@ mod core_tests {
         assert!(matches!(res, Ok(Ok(_))));
     }
 
+    #[tokio::test]
+    async fn test_core_fixed_steps() {
+        let (model, _, plane_init) = test_core_init();
+        let mut core = Core::new(CoreInitCfg {
+            clock_mode: ClockMode::Fixed {
+                sample_time: 10,
+                time_scale: None,
+            },
+        });
+
+        let ctk = CancellationToken::new();
+        let res = core.push_plane(&model, 10, plane_init, ctk.clone());
+        assert!(res.is_ok());
+        let (_id, mut viewer, controller, handler, _, _, commander) = res.unwrap();
+
+        // the commands between two controls do not move the clock
+        let control = Control::from([3000.0, 0.0, 0.0, 0.0]);
+        for n in 1..=5 {
+            for command in [
+                PlaneCommand::Pause,
+                PlaneCommand::SetTimeScale(-1.0),
+                PlaneCommand::Resume,
+                PlaneCommand::SetTimeScale(1.0),
+            ] {
+                assert!(commander.send(command).await.is_ok());
+            }
+            assert!(controller.send(&control).await.is_ok());
+            assert!(viewer.changed().await.is_ok());
+            let (time, _) = viewer.get_and_update();
+            assert!((time - n as f64 * 0.01).abs() < 1e-9);
+        }
+
+        ctk.cancel();
+        let _ = handler.await;
+
+        let res = model.plugin().uninstall();
+        assert!(matches!(res, Ok(Ok(_))));
+    }
+
     #[tokio::test]
     async fn test_core_multi() {
         let (model, mut core, plane_init) = test_core_init();
@@ -328,8 +543,8 @@ mod core_tests {
         let r2 = core.push_plane(&model, 10, plane_init, cancellation_token2);
         assert!(matches!(r1, Ok(_)));
         assert!(matches!(r2, Ok(_)));
-        let (_, viewer1, controller1, handler1, _) = r1.unwrap();
-        let (_, viewer2, controller2, handler2, _) = r2.unwrap();
+        let (_, viewer1, controller1, handler1, _, _, _) = r1.unwrap();
+        let (_, viewer2, controller2, handler2, _, _, _) = r2.unwrap();
 
         let h1 = std::thread::spawn(move || {
             let rt = tokio::runtime::Builder::new_current_thread()
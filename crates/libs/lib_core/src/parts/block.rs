@@ -7,7 +7,10 @@ use fly_ruler_plugin::AsPlugin;
 use fly_ruler_utils::{
     error::FatalCoreError,
     parts::{Actuator, VectorIntegrator},
-    plane_model::{Control, ControlLimit, CoreOutput, MechanicalModelInput, State, StateExtend},
+    plane_model::{
+        Control, ControlLimit, CoreOutput, Diagnostic, MechanicalModelInput, PlaneConstants, State,
+        StateExtend,
+    },
     Vector,
 };
 use tracing::{event, span, Level};
@@ -111,6 +114,7 @@ pub struct PlaneBlock {
     integrator: VectorIntegrator,
     plane: MechanicalModel,
     extend: Option<StateExtend>,
+    diagnostic: Option<Diagnostic>,
     alpha_limit_top: f64,
     alpha_limit_bottom: f64,
     beta_limit_top: f64,
@@ -124,6 +128,7 @@ impl PlaneBlock {
         init: &TrimOutput,
         deflection: &[f64; 3],
         ctrl_limit: ControlLimit,
+        constants: PlaneConstants,
     ) -> Result<Self, FatalCoreError> {
         let s =
             span!(Level::TRACE, "new", model_name = model.info().name, init_state = %init.state);
@@ -131,6 +136,7 @@ impl PlaneBlock {
         let control = ControllerBlock::new(init.control, deflection, ctrl_limit);
         let integrator = VectorIntegrator::new(Into::<Vector>::into(init.state));
         let mut plane = MechanicalModel::new(model)?;
+        plane.set_constants(constants);
         plane.init(
             id,
             &MechanicalModelInput {
@@ -143,6 +149,7 @@ impl PlaneBlock {
             integrator,
             plane,
             extend: None,
+            diagnostic: None,
             alpha_limit_top: ctrl_limit.alpha_limit_top,
             alpha_limit_bottom: ctrl_limit.alpha_limit_bottom,
             beta_limit_top: ctrl_limit.beta_limit_top,
@@ -203,6 +210,7 @@ impl PlaneBlock {
         }
 
         self.extend = Some(StateExtend::from(extend));
+        self.diagnostic = Some(model_output.diagnostic);
 
         let block_output = CoreOutput::new(
             State::from(state),
@@ -214,9 +222,13 @@ impl PlaneBlock {
         Ok(block_output)
     }
 
+    /// go back to the initial state, the time restarts at next update
     pub fn reset(&mut self) {
         self.control.reset();
         self.integrator.reset();
+        self.extend = None;
+        self.diagnostic = None;
+        self.start_time = None;
     }
 
     pub fn state(&self) -> Result<CoreOutput, FatalCoreError> {
@@ -230,6 +242,11 @@ impl PlaneBlock {
         ))
     }
 
+    /// diagnostic of the last step
+    pub fn diagnostic(&self) -> Diagnostic {
+        self.diagnostic.unwrap_or_default()
+    }
+
     pub fn delete_model(&mut self) {
         self.plane.delete()
     }
@@ -367,7 +384,8 @@ mod core_parts_tests {
         // set_time_scale(5.0).unwrap();
 
         let control: [f64; 4] = result.control.into();
-        let f16_block = PlaneBlock::new("123", &model, &result, &[0.0, 0.0, 0.0], CL);
+        let constants = model.load_constants().unwrap();
+        let f16_block = PlaneBlock::new("123", &model, &result, &[0.0, 0.0, 0.0], CL, constants);
         let mut f16_block = f16_block.unwrap();
 
         let path = Path::new("output.csv");
@@ -410,8 +428,8 @@ mod core_parts_tests {
                 trace!("time: {:?} \n{:?}\n", delta_time, state);
 
                 let mut state: Vec<f64> = state.data.clone();
-                let extend: [f64; 6] = result.state_extend.into();
-                state.extend_from_slice(&extend);
+                let extend: [f64; 22] = result.state_extend.into();
+                state.extend_from_slice(&extend[..6]);
 
                 let data: Vec<String> = state.iter().map(|d| d.to_string()).collect();
                 let mut record = vec![delta_time.as_secs_f32().to_string()];
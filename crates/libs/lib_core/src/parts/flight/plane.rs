@@ -8,7 +8,8 @@ use fly_ruler_utils::{
     error::FatalCoreError,
     parts::Atmos,
     plane_model::{
-        MechanicalModelInput, MechanicalModelOutput, PlaneConstants, State, StateExtend, C,
+        Diagnostic, MechanicalModelInput, MechanicalModelOutput, PlaneConstants, State,
+        StateExtend, C,
     },
 };
 use tracing::warn;
@@ -16,6 +17,7 @@ use tracing::warn;
 pub struct MechanicalModel {
     id: Option<String>,
     constants: PlaneConstants,
+    model_constants: PlaneConstants,
     model_trim_func: Box<AerodynamicModelTrimFn>,
     model_init_func: Box<AerodynamicModelInitFn>,
     model_step_func: Box<AerodynamicModelStepFn>,
@@ -47,6 +49,7 @@ impl MechanicalModel {
         Ok(Self {
             id: None,
             constants,
+            model_constants: constants,
             model_trim_func,
             model_init_func,
             model_step_func,
@@ -54,6 +57,25 @@ impl MechanicalModel {
         })
     }
 
+    /// replace the constants loaded from the model
+    pub fn set_constants(&mut self, constants: PlaneConstants) {
+        self.constants = constants;
+    }
+
+    pub fn constants(&self) -> PlaneConstants {
+        self.constants
+    }
+
+    /// move the moment reference of the model coefficients to the overridden cg
+    fn shift_cg(&self, mut c: C) -> C {
+        let model = &self.model_constants;
+        let constants = &self.constants;
+        let delta = (constants.x_cg_r - constants.x_cg) - (model.x_cg_r - model.x_cg);
+        c.c_m += c.c_z * delta;
+        c.c_n -= c.c_y * delta * constants.c_bar / constants.b;
+        c
+    }
+
     pub fn init(
         &mut self,
         id: &str,
@@ -76,11 +98,13 @@ impl MechanicalModel {
         let velocity = state.velocity.max(0.01);
         let altitude = state.altitude;
 
-        let (mach, qbar, ps) = Atmos::atmos(altitude, velocity).into();
+        let atmos = Atmos::atmos(altitude, velocity);
+        let (_, qbar, _) = atmos.into();
         let (position_dot, sub_velocity) = navgation(velocity, &orientation, &air_angles);
         let orientation_dot = kinematics(&orientation, &angle_rates);
 
         let c = (self.model_trim_func)(model_input).map_err(|e| FatalCoreError::from(e))?;
+        let c = self.shift_cg(c);
 
         let (velocity_dot, sub_velocity_dot) = velocity_derivation(
             &c,
@@ -112,9 +136,30 @@ impl MechanicalModel {
             angle_rate_dot.q,
             angle_rate_dot.r,
         ]);
-        let state_extend = StateExtend::from([n.x, n.y, n.z, mach, qbar, ps]);
+        let state_extend = extend(
+            &atmos,
+            altitude,
+            velocity,
+            velocity_dot,
+            &position_dot,
+            &sub_velocity,
+            &n,
+        );
+        let diagnostic = diagnose(
+            &c,
+            &self.constants,
+            qbar,
+            &orientation,
+            &angle_rates,
+            &angle_rate_dot,
+            control.thrust,
+        );
 
-        Ok(MechanicalModelOutput::new(state_dot, state_extend))
+        Ok(MechanicalModelOutput::new(
+            state_dot,
+            state_extend,
+            diagnostic,
+        ))
     }
 
     pub fn step(
@@ -136,12 +181,14 @@ impl MechanicalModel {
         let velocity = state.velocity.max(0.01);
         let altitude = state.altitude;
 
-        let (mach, qbar, ps) = Atmos::atmos(altitude, velocity).into();
+        let atmos = Atmos::atmos(altitude, velocity);
+        let (_, qbar, _) = atmos.into();
         let (position_dot, sub_velocity) = navgation(velocity, &orientation, &air_angles);
         let orientation_dot = kinematics(&orientation, &angle_rates);
 
         let c = (self.model_step_func)(id.unwrap(), model_input, t)
             .map_err(|e| FatalCoreError::from(e))?;
+        let c = self.shift_cg(c);
         let (velocity_dot, sub_velocity_dot) = velocity_derivation(
             &c,
             &self.constants,
@@ -172,9 +219,30 @@ impl MechanicalModel {
             angle_rate_dot.q,
             angle_rate_dot.r,
         ]);
-        let state_extend = StateExtend::from([n.x, n.y, n.z, mach, qbar, ps]);
+        let state_extend = extend(
+            &atmos,
+            altitude,
+            velocity,
+            velocity_dot,
+            &position_dot,
+            &sub_velocity,
+            &n,
+        );
+        let diagnostic = diagnose(
+            &c,
+            &self.constants,
+            qbar,
+            &orientation,
+            &angle_rates,
+            &angle_rate_dot,
+            control.thrust,
+        );
 
-        Ok(MechanicalModelOutput::new(state_dot, state_extend))
+        Ok(MechanicalModelOutput::new(
+            state_dot,
+            state_extend,
+            diagnostic,
+        ))
     }
 
     pub fn delete(&mut self) {
@@ -298,3 +366,82 @@ fn accels(
 
     Vector3::new(nx_cg, ny_cg, nz_cg)
 }
+
+/// return the derived flight outputs
+fn extend(
+    atmos: &Atmos,
+    altitude: f64,
+    velocity: f64,
+    velocity_dot: f64,
+    position_dot: &Vector3,
+    sub_velocity: &Vector3,
+    n: &Vector3,
+) -> StateExtend {
+    let v_north = position_dot.x;
+    let v_east = position_dot.y;
+    let climb_rate = position_dot.z;
+    let ground_speed = v_north.hypot(v_east);
+
+    StateExtend {
+        nx: n.x,
+        ny: n.y,
+        nz: n.z,
+        mach: atmos.mach,
+        qbar: atmos.qbar,
+        ps: atmos.ps,
+        u: sub_velocity.x,
+        v: sub_velocity.y,
+        w: sub_velocity.z,
+        v_north,
+        v_east,
+        v_down: -climb_rate,
+        gamma: climb_rate.atan2(ground_speed),
+        track: v_east.atan2(v_north),
+        ground_speed,
+        climb_rate,
+        tas: velocity,
+        cas: atmos.cas(),
+        eas: atmos.eas(),
+        load_factor: (n.x.powi(2) + n.y.powi(2) + n.z.powi(2)).sqrt(),
+        specific_energy: altitude + velocity.powi(2) / (2.0 * G),
+        specific_excess_power: climb_rate + velocity * velocity_dot / G,
+    }
+}
+
+/// return the coefficients, forces and moments behind the derivatives
+fn diagnose(
+    c: &C,
+    constants: &PlaneConstants,
+    qbar: f64,
+    orientation: &Orientation,
+    angle_rates: &AngleRates,
+    angle_rate_dot: &AngleRates,
+    thrust: f64,
+) -> Diagnostic {
+    let qs = qbar * constants.s;
+    let weight = constants.m * G;
+    let h_eng = constants.h_eng;
+
+    let stheta = orientation.trigonal_theta[0];
+    let ctheta = orientation.trigonal_theta[1];
+    let sphi = orientation.trigonal_phi[0];
+    let cphi = orientation.trigonal_phi[1];
+
+    Diagnostic {
+        c: *c,
+        aero_force: [qs * c.c_x, qs * c.c_y, qs * c.c_z],
+        thrust_force: [thrust, 0.0, 0.0],
+        gravity_force: [
+            -weight * stheta,
+            weight * ctheta * sphi,
+            weight * ctheta * cphi,
+        ],
+        aero_moment: [
+            qs * constants.b * c.c_l,
+            qs * constants.c_bar * c.c_m,
+            qs * constants.b * c.c_n,
+        ],
+        thrust_moment: [0.0, -angle_rates.r * h_eng, angle_rates.q * h_eng],
+        angular_accel: [angle_rate_dot.p, angle_rate_dot.q, angle_rate_dot.r],
+    }
+}
@@ -73,7 +73,7 @@ impl TrimTarget {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TrimOutput {
     pub state: State,
     pub control: Control,
@@ -106,6 +106,77 @@ impl TrimOutput {
     }
 }
 
+/// explicit initial state and control, used instead of trimming
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+pub struct InitState {
+    pub state: State,
+    pub control: Control,
+}
+
+impl std::fmt::Display for InitState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "state:  \n{}", self.state)?;
+        write!(f, "control:\n{}", self.control)
+    }
+}
+
+impl InitState {
+    pub fn new(state: State, control: Control) -> Self {
+        Self { state, control }
+    }
+}
+
+/// reuse a saved trim result as initial state
+impl From<&TrimOutput> for InitState {
+    fn from(value: &TrimOutput) -> Self {
+        Self {
+            state: value.state,
+            control: value.control,
+        }
+    }
+}
+
+/// Skip trim and start from an explicit state and control
+/// state_extend is computed from one model evaluation,
+/// fval of the result holds the squared norm of the state derivative
+pub fn init_state(
+    plane: Rc<RefCell<MechanicalModel>>,
+    init_state: InitState,
+) -> Result<TrimOutput, FatalCoreError> {
+    let s = span!(Level::TRACE, "init_state", init_state = %init_state);
+    let _enter = s.enter();
+
+    let state = init_state.state;
+    let control = init_state.control;
+    let output = plane
+        .borrow()
+        .trim(&MechanicalModelInput::new(state, control))?;
+
+    let state_extend = output.state_extend;
+    if Into::<Vec<f64>>::into(state_extend)
+        .iter()
+        .any(|x| x.is_nan())
+    {
+        return Err(FatalCoreError::Nan);
+    }
+
+    let state_dot = Vector::from(Into::<Vec<f64>>::into(output.state_dot));
+    let mut x: Vec<f64> = control.into();
+    x.push(state.alpha);
+
+    Ok(TrimOutput::new(
+        state,
+        control,
+        state_extend,
+        NelderMeadResult {
+            x: Vector::from(x),
+            fval: state_dot.dot(&state_dot),
+            iter: 0,
+            fun_evals: 1,
+        },
+    ))
+}
+
 /// Trim aircraft to desired altitude and velocity
 /// fi_flag: true means hifi model
 pub fn trim(
@@ -178,7 +249,7 @@ pub fn trim(
     Ok(TrimOutput::new(
         state,
         Control::from(&res.x[..4]),
-        StateExtend::from(&o[12..18]),
+        StateExtend::from(&o[12..12 + StateExtend::LEN]),
         res,
     ))
 }
@@ -284,7 +355,7 @@ mod core_trim_tests {
         algorithm::nelder_mead::NelderMeadOptions,
         parts::{
             flight::MechanicalModel,
-            trim::{trim, TrimTarget},
+            trim::{init_state, trim, InitState, TrimTarget},
         },
     };
     use fly_ruler_plugin::{AerodynamicModel, AsPlugin};
@@ -345,4 +416,33 @@ mod core_trim_tests {
         let res = model.plugin().uninstall();
         assert!(matches!(res, Ok(Ok(_))));
     }
+
+    #[test]
+    fn test_init_state() {
+        test_logger_init();
+        let model = AerodynamicModel::new("../../../LSE/models/f16_model");
+        assert!(model.is_ok());
+
+        let model = model.unwrap();
+        let res = model
+            .plugin()
+            .install(&["../../../LSE/models/f16_model/data"]);
+        assert!(matches!(res, Ok(Ok(_))));
+
+        let plane = Rc::new(RefCell::new(MechanicalModel::new(&model).unwrap()));
+
+        let trim_target = TrimTarget::new(15000.0, 500.0, None, None);
+        let trimmed = trim(plane.clone(), trim_target, None, CL, None, None).unwrap();
+
+        let result = init_state(plane.clone(), InitState::from(&trimmed)).unwrap();
+        assert_eq!(result.nelder_mead_result.fun_evals, 1);
+        let trimmed_extend: Vec<f64> = trimmed.state_extend.into();
+        let result_extend: Vec<f64> = result.state_extend.into();
+        for (a, b) in trimmed_extend.iter().zip(result_extend.iter()) {
+            assert!((a - b).abs() < 1e-6);
+        }
+
+        let res = model.plugin().uninstall();
+        assert!(matches!(res, Ok(Ok(_))));
+    }
 }
@@ -4,6 +4,8 @@ pub(crate) mod model;
 pub mod parts;
 
 pub use model::{
-    input_channel, matrix::Matrix, plane as plane_model, state_channel, vector::Vector,
-    CancellationToken, Counter, InputReceiver, InputSender, OutputReceiver, OutputSender, Signal,
+    diagnostic_channel, input_channel, matrix::Matrix, plane as plane_model, plane_command_channel,
+    state_channel, vector::Vector, CancellationToken, Counter, DiagnosticReceiver,
+    DiagnosticSender, InputReceiver, InputSender, OutputReceiver, OutputSender,
+    PlaneCommandReceiver, PlaneCommandSender, Signal,
 };
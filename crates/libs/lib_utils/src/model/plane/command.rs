@@ -0,0 +1,42 @@
+use crate::error::FrError;
+use serde::{Deserialize, Serialize};
+
+/// Lifecycle command of a running plane
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum PlaneCommand {
+    /// stop the plane and delete its model
+    Remove,
+    /// freeze the clock of the plane
+    Pause,
+    /// continue after `Pause`
+    Resume,
+    /// go back to the trim point
+    Reset,
+    /// change the time scale of the clock
+    SetTimeScale(f64),
+}
+
+impl PlaneCommand {
+    /// `SetTimeScale`, the scale of a clock must be finite and positive
+    pub fn set_time_scale(time_scale: f64) -> Result<Self, FrError> {
+        if time_scale.is_finite() && time_scale > 0.0 {
+            Ok(Self::SetTimeScale(time_scale))
+        } else {
+            Err(FrError::Cfg(format!(
+                "time scale must be finite and positive, got {time_scale}"
+            )))
+        }
+    }
+}
+
+impl std::fmt::Display for PlaneCommand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Remove => write!(f, "Remove"),
+            Self::Pause => write!(f, "Pause"),
+            Self::Resume => write!(f, "Resume"),
+            Self::Reset => write!(f, "Reset"),
+            Self::SetTimeScale(s) => write!(f, "SetTimeScale({})", s),
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use super::{control::Control, state::State, state_extend::StateExtend};
 use serde::{Deserialize, Serialize};
 
+#[repr(C)]
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct CoreOutput {
     pub state: State,
@@ -0,0 +1,36 @@
+use super::other::C;
+use serde::{Deserialize, Serialize};
+
+/// What the `diagnostic` represent
+/// c: aerodynamic coefficients returned by the model
+/// aero_force(lb) thrust_force(lb) gravity_force(lb): body-axis forces (x, y, z)
+/// aero_moment(lb ft) thrust_moment(lb ft): body-axis moments (l, m, n)
+/// thrust_moment is the gyroscopic moment of the engine
+/// angular_accel(rad/s^2): p_dot q_dot r_dot
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct Diagnostic {
+    pub c: C,
+    pub aero_force: [f64; 3],
+    pub thrust_force: [f64; 3],
+    pub gravity_force: [f64; 3],
+    pub aero_moment: [f64; 3],
+    pub thrust_moment: [f64; 3],
+    pub angular_accel: [f64; 3],
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "c_x: {:.4}, c_y: {:.4}, c_z: {:.4}, c_l: {:.4}, c_m: {:.4}, c_n: {:.4}",
+            self.c.c_x, self.c.c_y, self.c.c_z, self.c.c_l, self.c.c_m, self.c.c_n
+        )?;
+        writeln!(f, "aero_force: {:.2?} lb", self.aero_force)?;
+        writeln!(f, "thrust_force: {:.2?} lb", self.thrust_force)?;
+        writeln!(f, "gravity_force: {:.2?} lb", self.gravity_force)?;
+        writeln!(f, "aero_moment: {:.2?} lb ft", self.aero_moment)?;
+        writeln!(f, "thrust_moment: {:.2?} lb ft", self.thrust_moment)?;
+        writeln!(f, "angular_accel: {:.4?} rad/s^2", self.angular_accel)
+    }
+}
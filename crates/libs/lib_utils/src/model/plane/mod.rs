@@ -1,13 +1,17 @@
+pub(crate) mod command;
 pub(crate) mod control;
 pub(crate) mod core_output;
+pub(crate) mod diagnostic;
 pub(crate) mod model_input;
 pub(crate) mod model_output;
 pub(crate) mod other;
 pub(crate) mod state;
 pub(crate) mod state_extend;
 
+pub use command::*;
 pub use control::*;
 pub use core_output::*;
+pub use diagnostic::*;
 pub use model_input::*;
 pub use model_output::*;
 pub use other::*;
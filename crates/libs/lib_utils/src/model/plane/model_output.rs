@@ -1,4 +1,4 @@
-use super::{state::State, state_extend::StateExtend};
+use super::{diagnostic::Diagnostic, state::State, state_extend::StateExtend};
 
 /// The Ouput of the Model
 /// the value's index in `state_dot` is as same of the `state` in `ModelInput`
@@ -6,13 +6,19 @@ use super::{state::State, state_extend::StateExtend};
 pub struct MechanicalModelOutput {
     pub state_dot: State,
     pub state_extend: StateExtend,
+    pub diagnostic: Diagnostic,
 }
 
 impl MechanicalModelOutput {
-    pub fn new(state_dot: impl Into<State>, state_extend: impl Into<StateExtend>) -> Self {
+    pub fn new(
+        state_dot: impl Into<State>,
+        state_extend: impl Into<StateExtend>,
+        diagnostic: Diagnostic,
+    ) -> Self {
         Self {
             state_dot: state_dot.into(),
             state_extend: state_extend.into(),
+            diagnostic,
         }
     }
 }
@@ -20,6 +26,7 @@ impl MechanicalModelOutput {
 impl std::fmt::Display for MechanicalModelOutput {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         writeln!(f, "State_dot(*/t):   \n{}", self.state_dot)?;
-        writeln!(f, "State_extend:\n{}", self.state_extend)
+        writeln!(f, "State_extend:\n{}", self.state_extend)?;
+        writeln!(f, "Diagnostic:\n{}", self.diagnostic)
     }
 }
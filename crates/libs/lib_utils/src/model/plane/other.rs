@@ -1,8 +1,10 @@
+use super::control::Control;
+use crate::error::FrError;
 use serde::{Deserialize, Serialize};
 
 /// Aerodynamic coefficient
 #[repr(C)]
-#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
 pub struct C {
     pub c_x: f64,
     pub c_z: f64,
@@ -35,7 +37,7 @@ impl C {
 /// h_eng: turbine momentum along roll axis
 /// j_y, j_xz, j_z, j_x: slug-ft^2
 #[repr(C)]
-#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
 pub struct PlaneConstants {
     pub m: f64,
     pub b: f64,
@@ -100,8 +102,87 @@ impl std::fmt::Display for PlaneConstants {
     }
 }
 
+/// Partial override of `PlaneConstants`
+/// a `None` field keeps the value loaded from the model
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct PlaneConstantsOverride {
+    pub m: Option<f64>,
+    pub b: Option<f64>,
+    pub s: Option<f64>,
+    pub c_bar: Option<f64>,
+    pub x_cg_r: Option<f64>,
+    pub x_cg: Option<f64>,
+    pub h_eng: Option<f64>,
+    pub j_y: Option<f64>,
+    pub j_xz: Option<f64>,
+    pub j_z: Option<f64>,
+    pub j_x: Option<f64>,
+}
+
+impl PlaneConstants {
+    /// apply a partial override and validate the result
+    pub fn with_override(&self, value: &PlaneConstantsOverride) -> Result<Self, FrError> {
+        let constants = Self {
+            m: value.m.unwrap_or(self.m),
+            b: value.b.unwrap_or(self.b),
+            s: value.s.unwrap_or(self.s),
+            c_bar: value.c_bar.unwrap_or(self.c_bar),
+            x_cg_r: value.x_cg_r.unwrap_or(self.x_cg_r),
+            x_cg: value.x_cg.unwrap_or(self.x_cg),
+            h_eng: value.h_eng.unwrap_or(self.h_eng),
+            j_y: value.j_y.unwrap_or(self.j_y),
+            j_xz: value.j_xz.unwrap_or(self.j_xz),
+            j_z: value.j_z.unwrap_or(self.j_z),
+            j_x: value.j_x.unwrap_or(self.j_x),
+        };
+        constants.validate()?;
+        Ok(constants)
+    }
+
+    pub fn validate(&self) -> Result<(), FrError> {
+        let values: [f64; 11] = [
+            self.m,
+            self.b,
+            self.s,
+            self.c_bar,
+            self.x_cg_r,
+            self.x_cg,
+            self.h_eng,
+            self.j_y,
+            self.j_xz,
+            self.j_z,
+            self.j_x,
+        ];
+        if values.iter().any(|v| !v.is_finite()) {
+            return Err(FrError::Cfg("plane constants must be finite".to_string()));
+        }
+        let positive = [
+            ("m", self.m),
+            ("b", self.b),
+            ("s", self.s),
+            ("c_bar", self.c_bar),
+            ("j_x", self.j_x),
+            ("j_y", self.j_y),
+            ("j_z", self.j_z),
+        ];
+        for (name, v) in positive {
+            if v <= 0.0 {
+                return Err(FrError::Cfg(format!(
+                    "plane constant `{name}` must be positive, got {v}"
+                )));
+            }
+        }
+        if self.j_x * self.j_z - self.j_xz.powi(2) <= 0.0 {
+            return Err(FrError::Cfg(
+                "plane constants: j_x * j_z must be greater than j_xz^2".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
 #[repr(C)]
-#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
 pub struct ControlLimit {
     pub thrust_cmd_limit_top: f64,
     pub thrust_cmd_limit_bottom: f64,
@@ -196,6 +277,150 @@ impl std::fmt::Display for ControlLimit {
     }
 }
 
+/// Partial override of `ControlLimit`
+/// a `None` field keeps the value loaded from the model
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ControlLimitOverride {
+    pub thrust_cmd_limit_top: Option<f64>,
+    pub thrust_cmd_limit_bottom: Option<f64>,
+    pub thrust_rate_limit: Option<f64>,
+    pub ele_cmd_limit_top: Option<f64>,
+    pub ele_cmd_limit_bottom: Option<f64>,
+    pub ele_rate_limit: Option<f64>,
+    pub ail_cmd_limit_top: Option<f64>,
+    pub ail_cmd_limit_bottom: Option<f64>,
+    pub ail_rate_limit: Option<f64>,
+    pub rud_cmd_limit_top: Option<f64>,
+    pub rud_cmd_limit_bottom: Option<f64>,
+    pub rud_rate_limit: Option<f64>,
+    pub alpha_limit_top: Option<f64>,
+    pub alpha_limit_bottom: Option<f64>,
+    pub beta_limit_top: Option<f64>,
+    pub beta_limit_bottom: Option<f64>,
+}
+
+impl ControlLimit {
+    /// apply a partial override and validate the result
+    pub fn with_override(&self, value: &ControlLimitOverride) -> Result<Self, FrError> {
+        let limit = Self {
+            thrust_cmd_limit_top: value
+                .thrust_cmd_limit_top
+                .unwrap_or(self.thrust_cmd_limit_top),
+            thrust_cmd_limit_bottom: value
+                .thrust_cmd_limit_bottom
+                .unwrap_or(self.thrust_cmd_limit_bottom),
+            thrust_rate_limit: value.thrust_rate_limit.unwrap_or(self.thrust_rate_limit),
+            ele_cmd_limit_top: value.ele_cmd_limit_top.unwrap_or(self.ele_cmd_limit_top),
+            ele_cmd_limit_bottom: value
+                .ele_cmd_limit_bottom
+                .unwrap_or(self.ele_cmd_limit_bottom),
+            ele_rate_limit: value.ele_rate_limit.unwrap_or(self.ele_rate_limit),
+            ail_cmd_limit_top: value.ail_cmd_limit_top.unwrap_or(self.ail_cmd_limit_top),
+            ail_cmd_limit_bottom: value
+                .ail_cmd_limit_bottom
+                .unwrap_or(self.ail_cmd_limit_bottom),
+            ail_rate_limit: value.ail_rate_limit.unwrap_or(self.ail_rate_limit),
+            rud_cmd_limit_top: value.rud_cmd_limit_top.unwrap_or(self.rud_cmd_limit_top),
+            rud_cmd_limit_bottom: value
+                .rud_cmd_limit_bottom
+                .unwrap_or(self.rud_cmd_limit_bottom),
+            rud_rate_limit: value.rud_rate_limit.unwrap_or(self.rud_rate_limit),
+            alpha_limit_top: value.alpha_limit_top.unwrap_or(self.alpha_limit_top),
+            alpha_limit_bottom: value.alpha_limit_bottom.unwrap_or(self.alpha_limit_bottom),
+            beta_limit_top: value.beta_limit_top.unwrap_or(self.beta_limit_top),
+            beta_limit_bottom: value.beta_limit_bottom.unwrap_or(self.beta_limit_bottom),
+        };
+        limit.validate()?;
+        Ok(limit)
+    }
+
+    pub fn validate(&self) -> Result<(), FrError> {
+        let ranges = [
+            (
+                "thrust command",
+                self.thrust_cmd_limit_top,
+                self.thrust_cmd_limit_bottom,
+            ),
+            (
+                "elevator command",
+                self.ele_cmd_limit_top,
+                self.ele_cmd_limit_bottom,
+            ),
+            (
+                "aileron command",
+                self.ail_cmd_limit_top,
+                self.ail_cmd_limit_bottom,
+            ),
+            (
+                "rudder command",
+                self.rud_cmd_limit_top,
+                self.rud_cmd_limit_bottom,
+            ),
+            ("alpha", self.alpha_limit_top, self.alpha_limit_bottom),
+            ("beta", self.beta_limit_top, self.beta_limit_bottom),
+        ];
+        for (name, top, bottom) in ranges {
+            if !top.is_finite() || !bottom.is_finite() || top < bottom {
+                return Err(FrError::Cfg(format!(
+                    "control limit: invalid {name} range ({bottom}, {top})"
+                )));
+            }
+        }
+        let rates = [
+            ("thrust", self.thrust_rate_limit),
+            ("elevator", self.ele_rate_limit),
+            ("aileron", self.ail_rate_limit),
+            ("rudder", self.rud_rate_limit),
+        ];
+        for (name, rate) in rates {
+            if !rate.is_finite() || rate <= 0.0 {
+                return Err(FrError::Cfg(format!(
+                    "control limit: {name} rate must be positive, got {rate}"
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// reject a command out of the command ranges, such as the control of an explicit initial state
+    pub fn check_control(&self, control: &Control) -> Result<(), FrError> {
+        let commands = [
+            (
+                "thrust",
+                control.thrust,
+                self.thrust_cmd_limit_top,
+                self.thrust_cmd_limit_bottom,
+            ),
+            (
+                "elevator",
+                control.elevator,
+                self.ele_cmd_limit_top,
+                self.ele_cmd_limit_bottom,
+            ),
+            (
+                "aileron",
+                control.aileron,
+                self.ail_cmd_limit_top,
+                self.ail_cmd_limit_bottom,
+            ),
+            (
+                "rudder",
+                control.rudder,
+                self.rud_cmd_limit_top,
+                self.rud_cmd_limit_bottom,
+            ),
+        ];
+        for (name, value, top, bottom) in commands {
+            if !(bottom..=top).contains(&value) {
+                return Err(FrError::Cfg(format!(
+                    "control: {name} {value} out of range ({bottom}, {top})"
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Clone, Copy, Deserialize, Serialize)]
 pub enum FlightCondition {
     WingsLevel,
@@ -220,3 +445,85 @@ impl Default for FlightCondition {
         FlightCondition::WingsLevel
     }
 }
+
+#[cfg(test)]
+mod utils_model_tests {
+    use super::{
+        Control, ControlLimit, ControlLimitOverride, PlaneConstants, PlaneConstantsOverride,
+    };
+
+    #[test]
+    fn test_constants_override() {
+        let constants = PlaneConstants::new(
+            636.94, 30.0, 300.0, 11.32, 0.35, 0.30, 160.0, 55814.0, 982.0, 63100.0, 9496.0,
+        );
+
+        let r = constants.with_override(&PlaneConstantsOverride::default());
+        assert_eq!(r.unwrap(), constants);
+
+        let r = constants.with_override(&PlaneConstantsOverride {
+            m: Some(700.0),
+            x_cg: Some(0.25),
+            ..Default::default()
+        });
+        let r = r.unwrap();
+        assert_eq!(r.m, 700.0);
+        assert_eq!(r.x_cg, 0.25);
+        assert_eq!(r.j_x, constants.j_x);
+
+        let r = constants.with_override(&PlaneConstantsOverride {
+            m: Some(-1.0),
+            ..Default::default()
+        });
+        assert!(r.is_err());
+
+        let r = constants.with_override(&PlaneConstantsOverride {
+            j_xz: Some(1e6),
+            ..Default::default()
+        });
+        assert!(r.is_err());
+    }
+
+    #[test]
+    fn test_ctrl_limit_override() {
+        let limit = ControlLimit::new(
+            19000.0, 1000.0, 10000.0, 25.0, -25.0, 60.0, 21.5, -21.5, 80.0, 30.0, -30.0, 120.0,
+            45.0, -20.0, 30.0, -30.0,
+        );
+
+        let r = limit.with_override(&ControlLimitOverride {
+            alpha_limit_top: Some(30.0),
+            ..Default::default()
+        });
+        assert_eq!(r.unwrap().alpha_limit_top, 30.0);
+
+        let r = limit.with_override(&ControlLimitOverride {
+            ele_cmd_limit_bottom: Some(40.0),
+            ..Default::default()
+        });
+        assert!(r.is_err());
+
+        let r = limit.with_override(&ControlLimitOverride {
+            thrust_rate_limit: Some(0.0),
+            ..Default::default()
+        });
+        assert!(r.is_err());
+    }
+
+    #[test]
+    fn test_ctrl_limit_check_control() {
+        let limit = ControlLimit::new(
+            19000.0, 1000.0, 10000.0, 25.0, -25.0, 60.0, 21.5, -21.5, 80.0, 30.0, -30.0, 120.0,
+            45.0, -20.0, 30.0, -30.0,
+        );
+
+        let control = Control::from([5000.0, -2.0, 0.0, 0.0]);
+        assert!(limit.check_control(&control).is_ok());
+
+        let control = Control::from([5000.0, 30.0, 0.0, 0.0]);
+        assert!(limit.check_control(&control).is_err());
+
+        let control = Control::from([f64::NAN, 0.0, 0.0, 0.0]);
+        assert!(limit.check_control(&control).is_err());
+    }
+}
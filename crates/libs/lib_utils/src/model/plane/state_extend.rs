@@ -6,6 +6,13 @@ use std::collections::HashMap;
 /// nx(g) ny(g) nz(g)
 /// mach
 /// qbar(lb/ft ft) ps(lb/ft ft)
+/// u(ft/s) v(ft/s) w(ft/s): body-axis velocities
+/// v_north(ft/s) v_east(ft/s) v_down(ft/s): NED velocities
+/// gamma(rad): flight-path angle, track(rad): ground track
+/// ground_speed(ft/s) climb_rate(ft/s)
+/// tas(ft/s) cas(ft/s) eas(ft/s)
+/// load_factor(g): magnitude of (nx, ny, nz)
+/// specific_energy(ft) specific_excess_power(ft/s)
 #[repr(C)]
 #[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
 pub struct StateExtend {
@@ -15,6 +22,27 @@ pub struct StateExtend {
     pub mach: f64,
     pub qbar: f64,
     pub ps: f64,
+    pub u: f64,
+    pub v: f64,
+    pub w: f64,
+    pub v_north: f64,
+    pub v_east: f64,
+    pub v_down: f64,
+    pub gamma: f64,
+    pub track: f64,
+    pub ground_speed: f64,
+    pub climb_rate: f64,
+    pub tas: f64,
+    pub cas: f64,
+    pub eas: f64,
+    pub load_factor: f64,
+    pub specific_energy: f64,
+    pub specific_excess_power: f64,
+}
+
+impl StateExtend {
+    /// number of values carried by `StateExtend`
+    pub const LEN: usize = 22;
 }
 
 impl std::fmt::Display for StateExtend {
@@ -29,6 +57,31 @@ impl std::fmt::Display for StateExtend {
             f,
             "qbar: {:.2} lb/ft^2, ps: {:.2} lb/ft ft",
             self.qbar, self.ps
+        )?;
+        writeln!(
+            f,
+            "u: {:.2} ft/s, v: {:.2} ft/s, w: {:.2} ft/s",
+            self.u, self.v, self.w
+        )?;
+        writeln!(
+            f,
+            "v_north: {:.2} ft/s, v_east: {:.2} ft/s, v_down: {:.2} ft/s",
+            self.v_north, self.v_east, self.v_down
+        )?;
+        writeln!(
+            f,
+            "gamma: {:.4} rad, track: {:.4} rad, ground_speed: {:.2} ft/s, climb_rate: {:.2} ft/s",
+            self.gamma, self.track, self.ground_speed, self.climb_rate
+        )?;
+        writeln!(
+            f,
+            "tas: {:.2} ft/s, cas: {:.2} ft/s, eas: {:.2} ft/s",
+            self.tas, self.cas, self.eas
+        )?;
+        writeln!(
+            f,
+            "load_factor: {:.4} g, specific_energy: {:.2} ft, specific_excess_power: {:.2} ft/s",
+            self.load_factor, self.specific_energy, self.specific_excess_power
         )
     }
 }
@@ -42,26 +95,58 @@ impl From<&[f64]> for StateExtend {
             mach: value[3],
             qbar: value[4],
             ps: value[5],
+            u: value[6],
+            v: value[7],
+            w: value[8],
+            v_north: value[9],
+            v_east: value[10],
+            v_down: value[11],
+            gamma: value[12],
+            track: value[13],
+            ground_speed: value[14],
+            climb_rate: value[15],
+            tas: value[16],
+            cas: value[17],
+            eas: value[18],
+            load_factor: value[19],
+            specific_energy: value[20],
+            specific_excess_power: value[21],
         }
     }
 }
 
-impl From<[f64; 6]> for StateExtend {
-    fn from(value: [f64; 6]) -> Self {
-        Self {
-            nx: value[0],
-            ny: value[1],
-            nz: value[2],
-            mach: value[3],
-            qbar: value[4],
-            ps: value[5],
-        }
+impl From<[f64; 22]> for StateExtend {
+    fn from(value: [f64; 22]) -> Self {
+        Self::from(&value[..])
     }
 }
 
-impl Into<[f64; 6]> for StateExtend {
-    fn into(self) -> [f64; 6] {
-        [self.nx, self.ny, self.nz, self.mach, self.qbar, self.ps]
+impl Into<[f64; 22]> for StateExtend {
+    fn into(self) -> [f64; 22] {
+        [
+            self.nx,
+            self.ny,
+            self.nz,
+            self.mach,
+            self.qbar,
+            self.ps,
+            self.u,
+            self.v,
+            self.w,
+            self.v_north,
+            self.v_east,
+            self.v_down,
+            self.gamma,
+            self.track,
+            self.ground_speed,
+            self.climb_rate,
+            self.tas,
+            self.cas,
+            self.eas,
+            self.load_factor,
+            self.specific_energy,
+            self.specific_excess_power,
+        ]
     }
 }
 
@@ -73,7 +158,7 @@ impl From<Vec<f64>> for StateExtend {
 
 impl From<StateExtend> for Vec<f64> {
     fn from(value: StateExtend) -> Self {
-        Vec::from(<StateExtend as Into<[f64; 6]>>::into(value))
+        Vec::from(<StateExtend as Into<[f64; 22]>>::into(value))
     }
 }
 
@@ -98,6 +183,25 @@ impl Into<HashMap<String, f64>> for StateExtend {
         map.insert("mach".to_string(), self.mach);
         map.insert("qbar".to_string(), self.qbar);
         map.insert("ps".to_string(), self.ps);
+        map.insert("u".to_string(), self.u);
+        map.insert("v".to_string(), self.v);
+        map.insert("w".to_string(), self.w);
+        map.insert("v_north".to_string(), self.v_north);
+        map.insert("v_east".to_string(), self.v_east);
+        map.insert("v_down".to_string(), self.v_down);
+        map.insert("gamma".to_string(), self.gamma);
+        map.insert("track".to_string(), self.track);
+        map.insert("ground_speed".to_string(), self.ground_speed);
+        map.insert("climb_rate".to_string(), self.climb_rate);
+        map.insert("tas".to_string(), self.tas);
+        map.insert("cas".to_string(), self.cas);
+        map.insert("eas".to_string(), self.eas);
+        map.insert("load_factor".to_string(), self.load_factor);
+        map.insert("specific_energy".to_string(), self.specific_energy);
+        map.insert(
+            "specific_excess_power".to_string(),
+            self.specific_excess_power,
+        );
         map
     }
 }
@@ -1,15 +1,12 @@
 use crate::{
     error::FrError,
-    plane_model::{Control, CoreOutput},
+    plane_model::{Control, CoreOutput, Diagnostic, PlaneCommand},
 };
-use std::{
-    sync::{
-        atomic::{AtomicBool, AtomicUsize, Ordering},
-        Arc,
-    },
-    time::Duration,
+use std::sync::{
+    atomic::{AtomicBool, AtomicUsize, Ordering},
+    Arc, Mutex,
 };
-use tokio::sync::{mpsc, watch};
+use tokio::sync::{mpsc, watch, Notify};
 
 /// Create a state channel
 /// this method is a wrapper of spmc which means single-producer, multi-consumer
@@ -78,6 +75,55 @@ impl OutputReceiver {
     }
 }
 
+/// Create a diagnostic channel
+/// works the same as `state_channel`, but carries the per step `Diagnostic` of a plane
+pub fn diagnostic_channel() -> (DiagnosticSender, DiagnosticReceiver) {
+    let (sender, receiver) = watch::channel::<(f64, Diagnostic)>((0.0, Diagnostic::default()));
+    let sender = DiagnosticSender(Arc::new(sender));
+    let receiver = DiagnosticReceiver(receiver);
+    (sender, receiver)
+}
+
+/// The sender end of diagnostic channel
+#[derive(Clone)]
+pub struct DiagnosticSender(Arc<watch::Sender<(f64, Diagnostic)>>);
+
+impl DiagnosticSender {
+    pub fn send(&self, diagnostic: &(f64, Diagnostic)) -> Result<(), FrError> {
+        let sender = &self.0;
+        sender
+            .send(*diagnostic)
+            .map_err(|e| FrError::Sync(e.to_string()))
+    }
+
+    pub fn subscribe(&self) -> DiagnosticReceiver {
+        let sender = &self.0;
+        DiagnosticReceiver(sender.subscribe())
+    }
+}
+
+#[derive(Clone)]
+pub struct DiagnosticReceiver(watch::Receiver<(f64, Diagnostic)>);
+
+impl DiagnosticReceiver {
+    pub async fn changed(&mut self) -> Result<(), FrError> {
+        let recv = &mut self.0;
+        recv.changed()
+            .await
+            .map_err(|e| FrError::Sync(e.to_string()))
+    }
+
+    pub fn get(&self) -> (f64, Diagnostic) {
+        let recv = &self.0;
+        *recv.borrow()
+    }
+
+    pub fn get_and_update(&mut self) -> (f64, Diagnostic) {
+        let recv = &mut self.0;
+        *recv.borrow_and_update()
+    }
+}
+
 /// Create a command channel
 /// A multi-producer, single-consumer channel that only retains the last sent value.
 pub fn input_channel(buffer: usize) -> (InputSender, InputReceiver) {
@@ -88,20 +134,34 @@ pub fn input_channel(buffer: usize) -> (InputSender, InputReceiver) {
 }
 
 /// The sender end of command
+/// the clones share the last sent control
 #[derive(Clone)]
-pub struct InputSender(mpsc::Sender<Control>);
+pub struct InputSender {
+    sender: mpsc::Sender<Control>,
+    last: Arc<Mutex<Option<Control>>>,
+}
 
 impl InputSender {
     pub fn new(r: mpsc::Sender<Control>) -> Self {
-        Self(r)
+        Self {
+            sender: r,
+            last: Arc::new(Mutex::new(None)),
+        }
     }
 
     pub async fn send(&self, control: &Control) -> Result<(), FrError> {
-        let sender = &self.0;
-        Ok(sender
-            .send(control.clone())
+        let sender = &self.sender;
+        sender
+            .send(*control)
             .await
-            .map_err(|e| FrError::Sync(e.to_string()))?)
+            .map_err(|e| FrError::Sync(e.to_string()))?;
+        *self.last.lock().unwrap() = Some(*control);
+        Ok(())
+    }
+
+    /// the last control sent, `None` if the plane has not been controlled yet
+    pub fn last(&self) -> Option<Control> {
+        *self.last.lock().unwrap()
     }
 }
 
@@ -132,32 +192,72 @@ impl InputReceiver {
     }
 }
 
+/// Create a plane command channel
+/// carries the lifecycle commands of a running plane, see `PlaneCommand`
+pub fn plane_command_channel(buffer: usize) -> (PlaneCommandSender, PlaneCommandReceiver) {
+    let (sender, receiver) = mpsc::channel::<PlaneCommand>(buffer);
+    (PlaneCommandSender(sender), PlaneCommandReceiver(receiver))
+}
+
+/// The sender end of plane command channel
 #[derive(Clone)]
-pub struct CancellationToken(Arc<AtomicBool>);
+pub struct PlaneCommandSender(mpsc::Sender<PlaneCommand>);
+
+impl PlaneCommandSender {
+    pub async fn send(&self, command: PlaneCommand) -> Result<(), FrError> {
+        let sender = &self.0;
+        sender
+            .send(command)
+            .await
+            .map_err(|e| FrError::Sync(e.to_string()))
+    }
+}
+
+/// The receiver end of plane command channel, which advised to be owned by plane model
+pub struct PlaneCommandReceiver(mpsc::Receiver<PlaneCommand>);
+
+impl PlaneCommandReceiver {
+    pub async fn recv(&mut self) -> Option<PlaneCommand> {
+        let recv = &mut self.0;
+        recv.recv().await
+    }
+}
+
+struct Cancellation {
+    cancelled: AtomicBool,
+    notify: Notify,
+}
+
+#[derive(Clone)]
+pub struct CancellationToken(Arc<Cancellation>);
 
 impl CancellationToken {
     pub fn new() -> Self {
-        Self(Arc::new(AtomicBool::new(false)))
+        Self(Arc::new(Cancellation {
+            cancelled: AtomicBool::new(false),
+            notify: Notify::new(),
+        }))
     }
 
+    /// cancel the token and wake every task waiting on `cancelled`
     pub fn cancel(&self) {
-        self.0.store(true, Ordering::SeqCst);
+        self.0.cancelled.store(true, Ordering::SeqCst);
+        self.0.notify.notify_waiters();
     }
 
     pub fn is_cancelled(&self) -> bool {
-        self.0.load(Ordering::SeqCst)
+        self.0.cancelled.load(Ordering::SeqCst)
     }
 
+    /// wait until the token is cancelled, without polling
     pub async fn cancelled(&self) {
-        let _ = tokio::spawn({
-            let c = self.clone();
-            async move {
-                while !c.is_cancelled() {
-                    tokio::time::sleep(Duration::from_millis(100)).await;
-                }
-            }
-        })
-        .await;
+        let mut notified = std::pin::pin!(self.0.notify.notified());
+        // registered before the check, so a cancel in between is not missed
+        notified.as_mut().enable();
+        if self.is_cancelled() {
+            return;
+        }
+        notified.await;
     }
 }
 
@@ -9,13 +9,20 @@ pub struct Atmos {
 }
 
 impl Atmos {
+    /// sea level density slug/ft^3
+    const RHO0: f64 = 2.377e-3;
+    /// sea level static pressure lb/ft^2
+    const P0: f64 = 1715.0 * Self::RHO0 * 519.0;
+    /// sea level speed of sound ft/s
+    const A0: f64 = 1116.4;
+
     pub fn new(mach: f64, qbar: f64, ps: f64) -> Self {
         Self { mach, qbar, ps }
     }
 
     /// Function for mach and qbar
     pub fn atmos(altitude: f64, velocity: f64) -> Self {
-        let rho0 = 2.377e-3;
+        let rho0 = Self::RHO0;
         let tfac = 1.0 - 0.703e-5 * altitude;
 
         let mut temp = 519.0 * tfac;
@@ -34,6 +41,35 @@ impl Atmos {
 
         Atmos::new(mach, qbar, ps)
     }
+
+    /// Equivalent airspeed (ft/s)
+    pub fn eas(&self) -> f64 {
+        (2.0 * self.qbar / Self::RHO0).sqrt()
+    }
+
+    /// Calibrated airspeed (ft/s)
+    /// impact pressure from the isentropic relation below mach 1
+    /// and the Rayleigh pitot formula above it
+    pub fn cas(&self) -> f64 {
+        let mach = self.mach;
+        let qc = if mach < 1.0 {
+            self.ps * ((1.0 + 0.2 * mach.powi(2)).powf(3.5) - 1.0)
+        } else {
+            self.ps * (166.92158 * mach.powi(7) / (7.0 * mach.powi(2) - 1.0).powf(2.5) - 1.0)
+        };
+        let ratio = qc / Self::P0 + 1.0;
+
+        let mut cas = Self::A0 * (5.0 * (ratio.powf(2.0 / 7.0) - 1.0)).sqrt();
+        if cas > Self::A0 {
+            for _ in 0..10 {
+                let m = cas / Self::A0;
+                cas = Self::A0
+                    * 0.881285
+                    * (ratio * (1.0 - 1.0 / (7.0 * m.powi(2))).powf(2.5)).sqrt();
+            }
+        }
+        cas
+    }
 }
 
 impl Into<(f64, f64, f64)> for Atmos {
@@ -157,7 +193,7 @@ pub fn step(init: f64, end: f64, step_time: f64, t: f64) -> f64 {
 
 #[cfg(test)]
 mod core_parts_tests {
-    use super::Integrator;
+    use super::{Atmos, Integrator};
     use crate::{logger::test_logger_init, parts::basic::VectorIntegrator};
     use log::{info, trace};
     use std::time::{Duration, SystemTime};
@@ -203,4 +239,19 @@ mod core_parts_tests {
         assert!((r[0] - 0.5).abs() < 1e-3);
         assert!((r[1] - 1.0).abs() < 1e-3);
     }
+
+    #[test]
+    fn test_atmos_airspeeds() {
+        test_logger_init();
+        let atmos = Atmos::atmos(0.0, 300.0);
+        assert!((atmos.eas() - 300.0).abs() < 1e-6);
+        assert!((atmos.cas() - 300.0).abs() < 1.0);
+
+        let atmos = Atmos::atmos(15000.0, 500.0);
+        assert!(atmos.eas() < 500.0);
+        assert!(atmos.cas() > atmos.eas());
+
+        let atmos = Atmos::atmos(0.0, 1500.0);
+        assert!((atmos.cas() - 1500.0).abs() < 5.0);
+    }
 }
@@ -33,9 +33,46 @@ class StateExtendWrapper:
     mach: float
     qbar: float
     ps: float
-
-    def __init__(self, nx: float, ny: float, nz: float,
-                 mach: float, qbar: float, ps: float): ...
+    u: float
+    v: float
+    w: float
+    v_north: float
+    v_east: float
+    v_down: float
+    gamma: float
+    track: float
+    ground_speed: float
+    climb_rate: float
+    tas: float
+    cas: float
+    eas: float
+    load_factor: float
+    specific_energy: float
+    specific_excess_power: float
+
+    def __init__(self,
+                 nx: float,
+                 ny: float,
+                 nz: float,
+                 mach: float,
+                 qbar: float,
+                 ps: float,
+                 u: float,
+                 v: float,
+                 w: float,
+                 v_north: float,
+                 v_east: float,
+                 v_down: float,
+                 gamma: float,
+                 track: float,
+                 ground_speed: float,
+                 climb_rate: float,
+                 tas: float,
+                 cas: float,
+                 eas: float,
+                 load_factor: float,
+                 specific_energy: float,
+                 specific_excess_power: float): ...
 
 
 class CoreOutputWrapper:
@@ -53,6 +90,155 @@ class PlaneMessageWrapper:
     output: CoreOutputWrapper
 
 
+class DiagnosticWrapper:
+    c_x: float
+    c_y: float
+    c_z: float
+    c_l: float
+    c_m: float
+    c_n: float
+    aero_force: list[float]
+    thrust_force: list[float]
+    gravity_force: list[float]
+    aero_moment: list[float]
+    thrust_moment: list[float]
+    angular_accel: list[float]
+
+
+class PlaneDiagnosticWrapper:
+    id: UuidWrapper
+    time: float
+    diagnostic: DiagnosticWrapper | None
+
+
+class PlaneEventWrapper:
+    id: UuidWrapper
+    kind: str
+    time_scale: float | None
+
+
+class ErrorCodeWrapper:
+    Internal: ErrorCodeWrapper
+    InvalidRequest: ErrorCodeWrapper
+    InvalidArgs: ErrorCodeWrapper
+    InvalidId: ErrorCodeWrapper
+    ModelNotAvailable: ErrorCodeWrapper
+    PlaneNotFound: ErrorCodeWrapper
+    TrimFailed: ErrorCodeWrapper
+    HandshakeRequired: ErrorCodeWrapper
+    MalformedFrame: ErrorCodeWrapper
+    PermissionDenied: ErrorCodeWrapper
+    NotPlaneOwner: ErrorCodeWrapper
+
+
+class ErrorResponseWrapper:
+    code: ErrorCodeWrapper
+    message: str
+    request: str
+
+
+class ClientRoleWrapper:
+    Pilot: ClientRoleWrapper
+    Observer: ClientRoleWrapper
+    Admin: ClientRoleWrapper
+
+
+class CompressionWrapper:
+    Zstd: CompressionWrapper
+    Lz4: CompressionWrapper
+
+
+class HelloAckWrapper:
+    protocol_version: int
+    server_name: str
+    server_version: str
+    features: list[str]
+    compression: str
+    compression_threshold: int
+    role: ClientRoleWrapper
+    session: str
+    resumed_planes: list[str]
+    room: str
+
+
+class ClientStatusWrapper:
+    addr: str
+    queued: int
+    dropped: int
+    conflated: int
+    lagged: int
+
+
+class OutputFieldWrapper:
+    State: OutputFieldWrapper
+    Control: OutputFieldWrapper
+    StateExtend: OutputFieldWrapper
+
+
+class SubscriptionWrapper:
+    all: bool
+    plane_ids: list[UuidWrapper]
+    owners: list[str]
+    decimation: int
+    fields: list[OutputFieldWrapper]
+
+    def __init__(self, all: bool, plane_ids: list[UuidWrapper], owners: list[str],
+                 decimation: int, fields: list[OutputFieldWrapper]) -> None: ...
+
+
+class PlaneStatusWrapper:
+    Running: PlaneStatusWrapper
+    Paused: PlaneStatusWrapper
+
+
+class PlaneInfoWrapper:
+    id: UuidWrapper
+    model_id: UuidWrapper
+    owner: str
+    status: PlaneStatusWrapper
+    time: float
+    pilots: list[str]
+
+
+class PlaneConstantsWrapper:
+    m: float
+    b: float
+    s: float
+    c_bar: float
+    x_cg_r: float
+    x_cg: float
+    h_eng: float
+    j_y: float
+    j_xz: float
+    j_z: float
+    j_x: float
+
+
+class ControlLimitWrapper:
+    thrust_cmd_limit_top: float
+    thrust_cmd_limit_bottom: float
+    thrust_rate_limit: float
+    ele_cmd_limit_top: float
+    ele_cmd_limit_bottom: float
+    ele_rate_limit: float
+    ail_cmd_limit_top: float
+    ail_cmd_limit_bottom: float
+    ail_rate_limit: float
+    rud_cmd_limit_top: float
+    rud_cmd_limit_bottom: float
+    rud_rate_limit: float
+    alpha_limit_top: float
+    alpha_limit_bottom: float
+    beta_limit_top: float
+    beta_limit_bottom: float
+
+
+class ModelDetailsWrapper:
+    model_info: PluginInfoTupleWrapper | None
+    constants: PlaneConstantsWrapper | None
+    control_limit: ControlLimitWrapper | None
+
+
 class PluginInfoWrapper:
     name: str
     author: str
@@ -109,6 +295,13 @@ class TrimInitWrapper:
     def __init__(self, control: ControlWrapper, alpha: float): ...
 
 
+class InitStateWrapper:
+    state: StateWrapper
+    control: ControlWrapper
+
+    def __init__(self, state: StateWrapper, control: ControlWrapper): ...
+
+
 class NelderMeadOptionsWrapper:
     max_fun_evals: int
     max_iter: int
@@ -136,42 +329,200 @@ class FlightConditionWrapper:
     def roll() -> FlightConditionWrapper: ...
 
 
+class PlaneConstantsOverrideWrapper:
+    m: float | None
+    b: float | None
+    s: float | None
+    c_bar: float | None
+    x_cg_r: float | None
+    x_cg: float | None
+    h_eng: float | None
+    j_y: float | None
+    j_xz: float | None
+    j_z: float | None
+    j_x: float | None
+
+    def __init__(self,
+                 m: float | None = None,
+                 b: float | None = None,
+                 s: float | None = None,
+                 c_bar: float | None = None,
+                 x_cg_r: float | None = None,
+                 x_cg: float | None = None,
+                 h_eng: float | None = None,
+                 j_y: float | None = None,
+                 j_xz: float | None = None,
+                 j_z: float | None = None,
+                 j_x: float | None = None): ...
+
+
+class ControlLimitOverrideWrapper:
+    thrust_cmd_limit_top: float | None
+    thrust_cmd_limit_bottom: float | None
+    thrust_rate_limit: float | None
+    ele_cmd_limit_top: float | None
+    ele_cmd_limit_bottom: float | None
+    ele_rate_limit: float | None
+    ail_cmd_limit_top: float | None
+    ail_cmd_limit_bottom: float | None
+    ail_rate_limit: float | None
+    rud_cmd_limit_top: float | None
+    rud_cmd_limit_bottom: float | None
+    rud_rate_limit: float | None
+    alpha_limit_top: float | None
+    alpha_limit_bottom: float | None
+    beta_limit_top: float | None
+    beta_limit_bottom: float | None
+
+    def __init__(self,
+                 thrust_cmd_limit_top: float | None = None,
+                 thrust_cmd_limit_bottom: float | None = None,
+                 thrust_rate_limit: float | None = None,
+                 ele_cmd_limit_top: float | None = None,
+                 ele_cmd_limit_bottom: float | None = None,
+                 ele_rate_limit: float | None = None,
+                 ail_cmd_limit_top: float | None = None,
+                 ail_cmd_limit_bottom: float | None = None,
+                 ail_rate_limit: float | None = None,
+                 rud_cmd_limit_top: float | None = None,
+                 rud_cmd_limit_bottom: float | None = None,
+                 rud_rate_limit: float | None = None,
+                 alpha_limit_top: float | None = None,
+                 alpha_limit_bottom: float | None = None,
+                 beta_limit_top: float | None = None,
+                 beta_limit_bottom: float | None = None): ...
+
+
+class NelderMeadResultWrapper:
+    x: list[float]
+    fval: float
+    iter: int
+    fun_evals: int
+
+    def __init__(self, x: list[float], fval: float, iter: int, fun_evals: int): ...
+
+
+class TrimOutputWrapper:
+    state: StateWrapper
+    control: ControlWrapper
+    state_extend: StateExtendWrapper
+    nelder_mead_result: NelderMeadResultWrapper
+
+    def __init__(self, state: StateWrapper, control: ControlWrapper,
+                 state_extend: StateExtendWrapper,
+                 nelder_mead_result: NelderMeadResultWrapper): ...
+
+    def init_state(self) -> InitStateWrapper: ...
+
+
 class PlaneInitCfgWrapper:
     deflection: list[float] | None
     trim_target: TrimTargetWrapper
     trim_init: TrimInitWrapper | None
     flight_condition: FlightConditionWrapper | None
     optim_options: NelderMeadOptionsWrapper | None
+    diagnostic: bool | None
+    constants: PlaneConstantsOverrideWrapper | None
+    ctrl_limit: ControlLimitOverrideWrapper | None
+    init_state: InitStateWrapper | None
 
     def __init__(self, trim_target: TrimTargetWrapper,
                  deflection: list[float] | None,
                  trim_init: TrimInitWrapper | None,
                  flight_condition: FlightConditionWrapper | None,
-                 optim_options: NelderMeadOptionsWrapper | None): ...
+                 optim_options: NelderMeadOptionsWrapper | None,
+                 diagnostic: bool | None,
+                 constants: PlaneConstantsOverrideWrapper | None,
+                 ctrl_limit: ControlLimitOverrideWrapper | None,
+                 init_state: InitStateWrapper | None): ...
 
 
 class PyClient:
     @staticmethod
-    async def new(host: str, port: int) -> PyClient: ...
+    async def new(host: str, port: int,
+                  role: ClientRoleWrapper | None = None,
+                  compression: CompressionWrapper | None = None,
+                  checksum: bool = False,
+                  token: str | None = None,
+                  tls: bool = False,
+                  ca_path: str | None = None,
+                  session: str | None = None,
+                  room: str | None = None,
+                  create_room: bool = False) -> PyClient: ...
 
     async def stop(self): ...
+
+    def server_info(self) -> HelloAckWrapper: ...
+
+    def dropped_outputs(self) -> int: ...
+
+    def dropped_diagnostics(self) -> int: ...
     
     def tick(self, tick_period: int | None): ...
 
+    def keep_alive(self, period: int | None): ...
+
+    async def ping(self) -> float: ...
+
+    async def get_status(self) -> list[ClientStatusWrapper]: ...
+
     async def get_model_infos(self) -> list[PluginInfoTupleWrapper]: ...
 
+    async def get_model_details(
+        self, model_id: UuidWrapper) -> ModelDetailsWrapper: ...
+
+    async def list_planes(self) -> list[PlaneInfoWrapper]: ...
+
+    async def get_plane_state(
+        self, plane_id: UuidWrapper) -> PlaneMessageWrapper: ...
+
     async def push_plane(
-        self, arg: tuple[UuidWrapper, PlaneInitCfgWrapper | None]) -> UuidWrapper: ...
+        self, arg: tuple[UuidWrapper, PlaneInitCfgWrapper | None]
+    ) -> tuple[UuidWrapper, TrimOutputWrapper | None]: ...
+
+    async def trim(
+        self, arg: tuple[UuidWrapper, PlaneInitCfgWrapper | None]) -> TrimOutputWrapper: ...
 
     async def send_control(self, arg: tuple[UuidWrapper, ControlWrapper | None]): ...
 
+    async def remove_plane(
+        self, plane_id: UuidWrapper | None) -> list[UuidWrapper]: ...
+
+    async def pause_plane(
+        self, plane_id: UuidWrapper | None) -> list[UuidWrapper]: ...
+
+    async def resume_plane(
+        self, plane_id: UuidWrapper | None) -> list[UuidWrapper]: ...
+
+    async def reset_plane(
+        self, plane_id: UuidWrapper | None) -> list[UuidWrapper]: ...
+
+    async def set_time_scale(
+        self, time_scale: float, plane_id: UuidWrapper | None) -> list[UuidWrapper]: ...
+
+    async def transfer_plane(
+        self, plane_id: UuidWrapper, owner: str) -> PlaneInfoWrapper: ...
+
+    async def share_plane(
+        self, plane_id: UuidWrapper, pilot: str, revoke: bool = False) -> PlaneInfoWrapper: ...
+
+    async def subscribe(
+        self, subscription: SubscriptionWrapper) -> SubscriptionWrapper: ...
+
+    async def unsubscribe(
+        self, subscription: SubscriptionWrapper) -> SubscriptionWrapper: ...
+
     async def output(self) -> PlaneMessageWrapper: ...
 
+    async def diagnostic(self) -> PlaneDiagnosticWrapper: ...
+
     async def lost_plane(self) -> str: ...
 
     async def new_plane(self) -> str: ...
 
-    async def error(self) -> str: ...
+    async def plane_event(self) -> PlaneEventWrapper: ...
+
+    async def error(self) -> ErrorResponseWrapper: ...
 
 
 def register_logger(filter: str, dir: str | None, file: str | None): ...
@@ -1,20 +1,44 @@
 pub mod utils;
 
 use fly_ruler_codec::{
-    Args, GetModelInfosResponse, PlaneMessage, PushPlaneRequest, PushPlaneResponse, RequestFrame,
-    Response, ResponseFrame, SendControlRequest, ServiceCall,
+    Args, ClientRole, Compression, ErrorResponse, Feature, GetModelDetailsRequest,
+    GetPlaneStateRequest, Hello, PlaneDiagnostic, PlaneEvent, PlaneMessage, PlaneRequest,
+    PushPlaneRequest, RequestFrame, Response, ResponseFrame, SendControlRequest, ServiceCall,
+    ServiceCallResponse, SetTimeScaleRequest, SharePlaneRequest, TransferPlaneRequest,
+    TrimRequest, TrimResponse, PROTOCOL_VERSION,
 };
 use futures::{SinkExt, StreamExt};
 use lazy_static::lazy_static;
 use pyo3::{exceptions::PyRuntimeError, prelude::*};
 use python_runtime::{
-    ControlWrapper, CoreOutputWrapper, FlightConditionWrapper, NelderMeadOptionsWrapper,
-    PlaneInitCfgWrapper, PlaneMessageWrapper, PluginInfoTupleWrapper, PluginInfoWrapper,
-    PluginStateWrapper, StateExtendWrapper, StateWrapper, TrimInitWrapper, TrimTargetWrapper,
-    UuidWrapper,
+    ClientRoleWrapper, ClientStatusWrapper, CompressionWrapper, ControlLimitOverrideWrapper,
+    ControlLimitWrapper, ControlWrapper, CoreOutputWrapper, DiagnosticWrapper, ErrorCodeWrapper,
+    ErrorResponseWrapper, FlightConditionWrapper, HelloAckWrapper, InitStateWrapper,
+    ModelDetailsWrapper, NelderMeadOptionsWrapper, NelderMeadResultWrapper, OutputFieldWrapper,
+    PlaneConstantsOverrideWrapper, PlaneConstantsWrapper, PlaneDiagnosticWrapper,
+    PlaneEventWrapper, PlaneInfoWrapper, PlaneInitCfgWrapper, PlaneMessageWrapper,
+    PlaneStatusWrapper, PluginInfoTupleWrapper, PluginInfoWrapper, PluginStateWrapper,
+    StateExtendWrapper, StateWrapper, SubscriptionWrapper, TrimInitWrapper, TrimOutputWrapper,
+    TrimTargetWrapper, UuidWrapper,
+};
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+use tokio::{
+    io::{AsyncRead, AsyncWrite},
+    net::TcpStream,
+    sync,
+    task::JoinHandle,
+};
+use tokio_rustls::{
+    rustls::{pki_types::ServerName, ClientConfig, RootCertStore},
+    TlsConnector,
 };
-use std::time::Duration;
-use tokio::{net::TcpStream, sync, task::JoinHandle};
 use tokio_util::codec::{FramedRead, FramedWrite};
 use tracing::{event, Level};
 use tracing_appender::{non_blocking, rolling};
@@ -61,44 +85,179 @@ pub fn register_logger(filter: String, dir: Option<String>, file: Option<String>
     }
 }
 
+/// halves of the connection to the server, plain or tls
+type ConnReader = Box<dyn AsyncRead + Unpin + Send>;
+type ConnWriter = Box<dyn AsyncWrite + Unpin + Send>;
+
+/// connect to the server, the certificate of the server is checked against `ca_path`
+/// or against the web roots if it is not set
+async fn connect(
+    host: &str,
+    port: u16,
+    tls: bool,
+    ca_path: Option<String>,
+) -> anyhow::Result<(ConnReader, ConnWriter)> {
+    let stream = TcpStream::connect(format!("{host}:{port}")).await?;
+    if !tls {
+        let (reader, writer) = stream.into_split();
+        return Ok((Box::new(reader), Box::new(writer)));
+    }
+    let mut roots = RootCertStore::empty();
+    match ca_path {
+        Some(ca_path) => {
+            let pem = std::fs::read(ca_path)?;
+            for cert in rustls_pemfile::certs(&mut pem.as_slice()) {
+                roots.add(cert?)?;
+            }
+        }
+        None => roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned()),
+    }
+    let config = ClientConfig::builder()
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+    let server_name = ServerName::try_from(host.to_string())?;
+    let stream = TlsConnector::from(Arc::new(config))
+        .connect(server_name, stream)
+        .await?;
+    let (reader, writer) = tokio::io::split(stream);
+    Ok((Box::new(reader), Box::new(writer)))
+}
+
+/// requests waiting for the response with the same request id
+type PendingRequests = Arc<Mutex<HashMap<u64, sync::oneshot::Sender<Response>>>>;
+
+/// hands a frequent message to its receiver without waiting, a full queue drops it
+/// so the reader never stalls the responses behind it
+fn forward<T>(tx: &sync::mpsc::Sender<T>, dropped: &AtomicU64, msg: T) -> anyhow::Result<()> {
+    match tx.try_send(msg) {
+        Ok(()) => Ok(()),
+        Err(sync::mpsc::error::TrySendError::Full(_)) => {
+            dropped.fetch_add(1, Ordering::Relaxed);
+            Ok(())
+        }
+        Err(sync::mpsc::error::TrySendError::Closed(_)) => Err(anyhow::anyhow!("Receiver dropped")),
+    }
+}
+
 #[pyclass]
 pub struct PyClient {
     cancellation_token: CancellationToken,
     tick_tx: Option<sync::mpsc::Sender<ServiceCall>>,
     tasks: Vec<JoinHandle<()>>,
     request_sender: sync::mpsc::Sender<ServiceCall>,
-    get_model_infos_receiver: sync::mpsc::Receiver<GetModelInfosResponse>,
-    push_plane_receiver: sync::mpsc::Receiver<PushPlaneResponse>,
+    next_request_id: AtomicU64,
+    server: HelloAckWrapper,
+    pending: PendingRequests,
     output_receiver: sync::mpsc::Receiver<PlaneMessage>,
-    lost_plane_receiver: sync::mpsc::Receiver<String>,
-    new_plane_receiver: sync::mpsc::Receiver<String>,
-    error_receiver: sync::mpsc::Receiver<String>,
+    lost_plane_receiver: sync::mpsc::UnboundedReceiver<String>,
+    new_plane_receiver: sync::mpsc::UnboundedReceiver<String>,
+    error_receiver: sync::mpsc::UnboundedReceiver<ErrorResponse>,
+    diagnostic_receiver: sync::mpsc::Receiver<PlaneDiagnostic>,
+    plane_event_receiver: sync::mpsc::UnboundedReceiver<PlaneEvent>,
+    dropped_outputs: Arc<AtomicU64>,
+    dropped_diagnostics: Arc<AtomicU64>,
 }
 
 #[pymethods]
 impl PyClient {
     #[staticmethod]
-    pub async fn new(host: String, port: u16) -> PyResult<Self> {
+    #[pyo3(signature = (host, port, role=None, compression=None, checksum=false, token=None, tls=false, ca_path=None, session=None, room=None, create_room=false))]
+    pub async fn new(
+        host: String,
+        port: u16,
+        role: Option<ClientRoleWrapper>,
+        compression: Option<CompressionWrapper>,
+        checksum: bool,
+        token: Option<String>,
+        tls: bool,
+        ca_path: Option<String>,
+        session: Option<String>,
+        room: Option<String>,
+        create_room: bool,
+    ) -> PyResult<Self> {
         event!(
             Level::INFO,
             "Connecting to server {host}:{port}",
             host = host,
             port = port
         );
-        let stream = TcpStream::connect(format!("{host}:{port}")).await?;
+        let (reader, writer) = connect(&host, port, tls, ca_path)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let mut reader = FramedRead::new(reader, ResponseFrame::default());
+        let mut writer = FramedWrite::new(writer, RequestFrame::default());
 
-        let (reader, writer) = stream.into_split();
-        let mut reader = FramedRead::new(reader, ResponseFrame);
-        let mut writer = FramedWrite::new(writer, RequestFrame);
+        let mut features = vec![Feature::Batching, Feature::Subscription];
+        let compressions: Vec<Compression> = compression.into_iter().map(|c| c.into()).collect();
+        if !compressions.is_empty() {
+            features.push(Feature::Compression);
+        }
+        if checksum {
+            features.push(Feature::Checksum);
+        }
+        let hello = ServiceCall {
+            name: "Hello".to_string(),
+            request_id: 1,
+            args: Some(Args::Hello(Hello {
+                protocol_version: PROTOCOL_VERSION,
+                client_name: env!("CARGO_PKG_NAME").to_string(),
+                client_version: env!("CARGO_PKG_VERSION").to_string(),
+                role: role.map_or(ClientRole::Pilot, |r| r.into()),
+                features,
+                compressions,
+                token: token.unwrap_or_default(),
+                session: session.unwrap_or_default(),
+                room: room.unwrap_or_default(),
+                create_room,
+            })),
+        };
+        writer
+            .send(hello)
+            .await
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        let r = reader.next().await;
+        event!(Level::DEBUG, "hello {response:?}", response = r);
+        let hello_ack = match r {
+            Some(Ok(ServiceCallResponse {
+                response: Some(Response::Hello(ack)),
+                ..
+            })) => ack,
+            Some(Ok(ServiceCallResponse {
+                response: Some(Response::Error(e)),
+                ..
+            })) => return Err(PyRuntimeError::new_err(e.message)),
+            Some(Err(e)) => return Err(PyRuntimeError::new_err(e.to_string())),
+            _ => return Err(PyRuntimeError::new_err("Failed to handshake")),
+        };
+        if !hello_ack.accepted {
+            return Err(PyRuntimeError::new_err(format!(
+                "Server rejected the client, {}",
+                hello_ack.reason
+            )));
+        }
+        if hello_ack.features.contains(&Feature::Compression) {
+            writer.encoder_mut().set_compression(
+                hello_ack.compression,
+                hello_ack.compression_threshold as usize,
+            );
+        }
+        writer
+            .encoder_mut()
+            .set_checksum(hello_ack.features.contains(&Feature::Checksum));
         let cancellation_token = CancellationToken::new();
         let (tx, mut rx) = sync::mpsc::channel::<ServiceCall>(256);
         let (tick_tx, mut tick_rx) = sync::mpsc::channel::<ServiceCall>(10);
-        let (tx1, rx1) = sync::mpsc::channel::<GetModelInfosResponse>(10);
-        let (tx2, rx2) = sync::mpsc::channel::<PushPlaneResponse>(10);
+        // outputs and diagnostics are dropped once their queue is full, the rare
+        // plane events are all kept
         let (tx3, rx3) = sync::mpsc::channel::<PlaneMessage>(100);
-        let (tx4, rx4) = sync::mpsc::channel::<String>(100);
-        let (tx5, rx5) = sync::mpsc::channel::<String>(100);
-        let (tx6, rx6) = sync::mpsc::channel::<String>(100);
+        let (tx4, rx4) = sync::mpsc::unbounded_channel::<String>();
+        let (tx5, rx5) = sync::mpsc::unbounded_channel::<String>();
+        let (tx6, rx6) = sync::mpsc::unbounded_channel::<ErrorResponse>();
+        let (tx7, rx7) = sync::mpsc::channel::<PlaneDiagnostic>(100);
+        let (tx10, rx10) = sync::mpsc::unbounded_channel::<PlaneEvent>();
+        let dropped_outputs = Arc::new(AtomicU64::new(0));
+        let dropped_diagnostics = Arc::new(AtomicU64::new(0));
+        let pending: PendingRequests = Arc::new(Mutex::new(HashMap::new()));
 
         let writer_task = {
             let w_ct1 = cancellation_token.clone();
@@ -106,17 +265,29 @@ impl PyClient {
             tokio::spawn(async move {
                 let r: Result<Result<(), anyhow::Error>, tokio::task::JoinError> =
                     tokio::spawn(async move {
+                        // the ticks stop once the heartbeat drops its sender
+                        let mut ticking = true;
                         loop {
-                            if w_ct1.is_cancelled() {
-                                break;
-                            }
-                            if let Ok(msg) = rx.try_recv() {
-                                writer.send(msg).await?;
-                            }
-
-                            if let Ok(msg) = tick_rx.try_recv() {
-                                event!(Level::TRACE, "tick");
-                                writer.send(msg).await?;
+                            tokio::select! {
+                                _ = w_ct1.cancelled() => break,
+                                msg = rx.recv() => match msg {
+                                    Some(msg) => {
+                                        // the last call, `stop` waits for it to be written
+                                        let disconnect = matches!(msg.args, Some(Args::Disconnect));
+                                        writer.send(msg).await?;
+                                        if disconnect {
+                                            break;
+                                        }
+                                    }
+                                    None => break,
+                                },
+                                msg = tick_rx.recv(), if ticking => match msg {
+                                    Some(msg) => {
+                                        event!(Level::TRACE, "tick");
+                                        writer.send(msg).await?;
+                                    }
+                                    None => ticking = false,
+                                },
                             }
                         }
                         Ok(())
@@ -139,27 +310,48 @@ impl PyClient {
         };
 
         let reader_task = {
+            let pending = pending.clone();
+            let dropped_outputs = dropped_outputs.clone();
+            let dropped_diagnostics = dropped_diagnostics.clone();
+            let r_pending = pending.clone();
             let r_ct1 = cancellation_token.clone();
             let r_ct2 = cancellation_token.clone();
             tokio::spawn(async move {
                 let r: Result<Result<(), anyhow::Error>, tokio::task::JoinError> =
                     tokio::spawn(async move {
                         loop {
-                            if r_ct1.is_cancelled() {
-                                break;
-                            }
-                            let response = reader.next().await;
+                            let response = tokio::select! {
+                                response = reader.next() => response,
+                                _ = r_ct1.cancelled() => break,
+                            };
                             match response {
                                 Some(response) => match response {
                                     Ok(response) => {
+                                        let waiter = match response.request_id {
+                                            0 => None,
+                                            id => pending.lock().unwrap().remove(&id),
+                                        };
                                         if let Some(response) = response.response {
+                                            if let Some(waiter) = waiter {
+                                                let _ = waiter.send(response);
+                                                continue;
+                                            }
                                             match response {
-                                                Response::GetModelInfos(r) => tx1.send(r).await?,
-                                                Response::PushPlane(r) => tx2.send(r).await?,
-                                                Response::Output(r) => tx3.send(r).await?,
-                                                Response::LostPlane(r) => tx4.send(r).await?,
-                                                Response::NewPlane(r) => tx5.send(r).await?,
-                                                Response::Error(r) => tx6.send(r).await?,
+                                                Response::Output(r) => {
+                                                    forward(&tx3, &dropped_outputs, r)?
+                                                }
+                                                Response::OutputGroup(group) => {
+                                                    for r in group.msg {
+                                                        forward(&tx3, &dropped_outputs, r)?;
+                                                    }
+                                                }
+                                                Response::LostPlane(r) => tx4.send(r)?,
+                                                Response::NewPlane(r) => tx5.send(r)?,
+                                                Response::Error(r) => tx6.send(r)?,
+                                                Response::Diagnostic(r) => {
+                                                    forward(&tx7, &dropped_diagnostics, r)?
+                                                }
+                                                Response::PlaneEvent(r) => tx10.send(r)?,
                                                 _ => {}
                                             }
                                         }
@@ -169,7 +361,7 @@ impl PyClient {
                                     }
                                 },
                                 None => {
-                                    tokio::task::yield_now().await;
+                                    return Err(anyhow::anyhow!("Server closed the connection"));
                                 }
                             }
                         }
@@ -189,6 +381,8 @@ impl PyClient {
                         r_ct2.cancel();
                     }
                 }
+                // wake up the callers still waiting for a response
+                r_pending.lock().unwrap().clear();
             })
         };
 
@@ -199,22 +393,34 @@ impl PyClient {
             tasks,
             tick_tx: Some(tick_tx),
             request_sender: tx,
-            get_model_infos_receiver: rx1,
-            push_plane_receiver: rx2,
+            next_request_id: AtomicU64::new(2),
+            server: hello_ack.into(),
+            pending,
             output_receiver: rx3,
             lost_plane_receiver: rx4,
             new_plane_receiver: rx5,
             error_receiver: rx6,
+            diagnostic_receiver: rx7,
+            plane_event_receiver: rx10,
+            dropped_outputs,
+            dropped_diagnostics,
         })
     }
 
     pub async fn stop(&mut self) {
-        self.cancellation_token.cancel();
         let call = ServiceCall {
             name: "Disconnect".to_string(),
+            request_id: 0,
             args: Some(Args::Disconnect),
         };
-        let _ = self.request_sender.send(call).await;
+        // the server releases the planes of a client which disconnects on purpose,
+        // a dropped connection keeps them for its session
+        if self.request_sender.send(call).await.is_ok() {
+            if let Some(writer_task) = self.tasks.first_mut() {
+                let _ = tokio::time::timeout(Duration::from_secs(1), writer_task).await;
+            }
+        }
+        self.cancellation_token.cancel();
         let mut count = 0;
         while let Some(task) = self.tasks.pop() {
             event!(Level::DEBUG, "task {} stop start", count);
@@ -224,54 +430,99 @@ impl PyClient {
         }
     }
 
-    pub async fn get_model_infos(&mut self) -> PyResult<Vec<PluginInfoTupleWrapper>> {
-        event!(Level::DEBUG, "get_model_infos start");
-        let call = ServiceCall {
-            name: "GetModelInfos".to_string(),
-            args: Some(Args::GetModelInfos),
-        };
-        self.request_sender
-            .send(call)
-            .await
-            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-        let r = self.get_model_infos_receiver.recv().await;
-        event!(
-            Level::DEBUG,
-            "get_model_infos end {response:?}",
-            response = r
-        );
+    /// server build and negotiated features of the handshake
+    pub fn server_info(&self) -> HelloAckWrapper {
+        self.server.clone()
+    }
+
+    /// outputs dropped because `output` was not awaited fast enough
+    pub fn dropped_outputs(&self) -> u64 {
+        self.dropped_outputs.load(Ordering::Relaxed)
+    }
+
+    /// diagnostics dropped because `diagnostic` was not awaited fast enough
+    pub fn dropped_diagnostics(&self) -> u64 {
+        self.dropped_diagnostics.load(Ordering::Relaxed)
+    }
+
+    pub async fn get_model_infos(&self) -> PyResult<Vec<PluginInfoTupleWrapper>> {
+        let r = self.call("GetModelInfos", Args::GetModelInfos).await?;
         match r {
-            Some(r) => Ok(r
+            Response::GetModelInfos(r) => Ok(r
                 .model_infos
                 .into_iter()
                 .map(PluginInfoTupleWrapper::from)
                 .collect()),
-            None => Err(PyRuntimeError::new_err("Failed to get model infos")),
+            _ => Err(PyRuntimeError::new_err("Failed to get model infos")),
+        }
+    }
+
+    pub async fn get_model_details(&self, model_id: UuidWrapper) -> PyResult<ModelDetailsWrapper> {
+        let args = Args::GetModelDetails(GetModelDetailsRequest {
+            model_id: model_id.0.to_string(),
+        });
+        let r = self.call("GetModelDetails", args).await?;
+        match r {
+            Response::GetModelDetails(r) if r.model_info.is_some() => Ok(r.into()),
+            _ => Err(PyRuntimeError::new_err("Failed to get model details")),
+        }
+    }
+
+    pub async fn list_planes(&self) -> PyResult<Vec<PlaneInfoWrapper>> {
+        let r = self.call("ListPlanes", Args::ListPlanes).await?;
+        match r {
+            Response::ListPlanes(r) => {
+                Ok(r.planes.into_iter().map(PlaneInfoWrapper::from).collect())
+            }
+            _ => Err(PyRuntimeError::new_err("Failed to list planes")),
+        }
+    }
+
+    pub async fn get_plane_state(&self, plane_id: UuidWrapper) -> PyResult<PlaneMessageWrapper> {
+        let args = Args::GetPlaneState(GetPlaneStateRequest {
+            plane_id: plane_id.0.to_string(),
+        });
+        let r = self.call("GetPlaneState", args).await?;
+        match r {
+            Response::GetPlaneState(r) if r.output.is_some() => Ok(r.into()),
+            _ => Err(PyRuntimeError::new_err("Failed to get plane state")),
         }
     }
 
     pub async fn push_plane(
-        &mut self,
+        &self,
         arg: (UuidWrapper, Option<PlaneInitCfgWrapper>),
-    ) -> PyResult<UuidWrapper> {
-        event!(Level::DEBUG, "push_plane start");
-        let call = ServiceCall {
-            name: "PushPlane".to_string(),
-            args: Some(Args::PushPlane(PushPlaneRequest {
-                model_id: arg.0 .0.to_string(),
-                plane_init_cfg: arg.1.map(|c| PlaneInitCfgWrapper::into(c)),
-            })),
-        };
-        self.request_sender
-            .send(call)
-            .await
-            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
-        let r = self.push_plane_receiver.recv().await;
-        event!(Level::DEBUG, "push_plane end {response:?}", response = r);
+    ) -> PyResult<(UuidWrapper, Option<TrimOutputWrapper>)> {
+        let args = Args::PushPlane(PushPlaneRequest {
+            model_id: arg.0 .0.to_string(),
+            plane_init_cfg: arg.1.map(|c| PlaneInitCfgWrapper::into(c)),
+        });
+        let r = self.call("PushPlane", args).await?;
         match r {
-            Some(r) => Ok(UuidWrapper::parse_str(&r.plane_id)
-                .map_err(|e| PyRuntimeError::new_err(e.to_string()))?),
-            None => Err(PyRuntimeError::new_err("Failed to push plane")),
+            Response::PushPlane(r) if !r.plane_id.is_empty() => Ok((
+                UuidWrapper::parse_str(&r.plane_id)
+                    .map_err(|e| PyRuntimeError::new_err(e.to_string()))?,
+                r.trim_output.map(TrimOutputWrapper::from),
+            )),
+            _ => Err(PyRuntimeError::new_err("Failed to push plane")),
+        }
+    }
+
+    pub async fn trim(
+        &self,
+        arg: (UuidWrapper, Option<PlaneInitCfgWrapper>),
+    ) -> PyResult<TrimOutputWrapper> {
+        let args = Args::Trim(TrimRequest {
+            model_id: arg.0 .0.to_string(),
+            plane_init_cfg: arg.1.map(|c| PlaneInitCfgWrapper::into(c)),
+        });
+        let r = self.call("Trim", args).await?;
+        match r {
+            Response::Trim(TrimResponse {
+                trim_output: Some(r),
+                ..
+            }) => Ok(r.into()),
+            _ => Err(PyRuntimeError::new_err("Failed to trim")),
         }
     }
 
@@ -282,6 +533,7 @@ impl PyClient {
         event!(Level::DEBUG, "send_control start {arg:?}", arg = arg);
         let call = ServiceCall {
             name: "SendControl".to_string(),
+            request_id: 0,
             args: Some(Args::SendControl(SendControlRequest {
                 plane_id: arg.0 .0.to_string(),
                 control: arg.1.map(|o| o.into()),
@@ -294,10 +546,202 @@ impl PyClient {
         Ok(())
     }
 
+    pub async fn remove_plane(&self, plane_id: Option<UuidWrapper>) -> PyResult<Vec<UuidWrapper>> {
+        let args = Args::RemovePlane(PlaneRequest {
+            plane_id: plane_id.map(|id| id.0.to_string()),
+        });
+        self.plane_call("RemovePlane", args).await
+    }
+
+    pub async fn pause_plane(&self, plane_id: Option<UuidWrapper>) -> PyResult<Vec<UuidWrapper>> {
+        let args = Args::PausePlane(PlaneRequest {
+            plane_id: plane_id.map(|id| id.0.to_string()),
+        });
+        self.plane_call("PausePlane", args).await
+    }
+
+    pub async fn resume_plane(&self, plane_id: Option<UuidWrapper>) -> PyResult<Vec<UuidWrapper>> {
+        let args = Args::ResumePlane(PlaneRequest {
+            plane_id: plane_id.map(|id| id.0.to_string()),
+        });
+        self.plane_call("ResumePlane", args).await
+    }
+
+    pub async fn reset_plane(&self, plane_id: Option<UuidWrapper>) -> PyResult<Vec<UuidWrapper>> {
+        let args = Args::ResetPlane(PlaneRequest {
+            plane_id: plane_id.map(|id| id.0.to_string()),
+        });
+        self.plane_call("ResetPlane", args).await
+    }
+
+    pub async fn set_time_scale(
+        &self,
+        time_scale: f64,
+        plane_id: Option<UuidWrapper>,
+    ) -> PyResult<Vec<UuidWrapper>> {
+        let args = Args::SetTimeScale(SetTimeScaleRequest {
+            plane_id: plane_id.map(|id| id.0.to_string()),
+            time_scale,
+        });
+        self.plane_call("SetTimeScale", args).await
+    }
+
+    /// give an owned plane to the client `owner`, addressed as listed by `list_planes`
+    pub async fn transfer_plane(
+        &self,
+        plane_id: UuidWrapper,
+        owner: String,
+    ) -> PyResult<PlaneInfoWrapper> {
+        let args = Args::TransferPlane(TransferPlaneRequest {
+            plane_id: plane_id.0.to_string(),
+            owner,
+        });
+        let r = self.call("TransferPlane", args).await?;
+        match r {
+            Response::TransferPlane(r) => Ok(r.into()),
+            _ => Err(PyRuntimeError::new_err("Failed to transfer plane")),
+        }
+    }
+
+    /// let the client `pilot` control an owned plane, `revoke` takes the permission back
+    #[pyo3(signature = (plane_id, pilot, revoke=false))]
+    pub async fn share_plane(
+        &self,
+        plane_id: UuidWrapper,
+        pilot: String,
+        revoke: bool,
+    ) -> PyResult<PlaneInfoWrapper> {
+        let args = Args::SharePlane(SharePlaneRequest {
+            plane_id: plane_id.0.to_string(),
+            pilot,
+            revoke,
+        });
+        let r = self.call("SharePlane", args).await?;
+        match r {
+            Response::SharePlane(r) => Ok(r.into()),
+            _ => Err(PyRuntimeError::new_err("Failed to share plane")),
+        }
+    }
+
     pub fn tick(&mut self, tick_period: Option<u64>) -> PyResult<()> {
+        self.heartbeat("Tick", Args::Tick, tick_period.unwrap_or(1000))
+    }
+
+    /// keep an observer alive with pings, it does not need to tick
+    pub fn keep_alive(&mut self, period: Option<u64>) -> PyResult<()> {
+        self.heartbeat("Ping", Args::Ping, period.unwrap_or(1000))
+    }
+
+    /// round trip time to the server, in ms
+    pub async fn ping(&self) -> PyResult<f64> {
+        let start = Instant::now();
+        let r = self.call("Ping", Args::Ping).await?;
+        match r {
+            Response::Pong => Ok(start.elapsed().as_secs_f64() * 1000.0),
+            _ => Err(PyRuntimeError::new_err("Failed to ping")),
+        }
+    }
+
+    /// output queue of this client on the server, of every client of the room for an admin
+    pub async fn get_status(&self) -> PyResult<Vec<ClientStatusWrapper>> {
+        let r = self.call("GetStatus", Args::GetStatus).await?;
+        match r {
+            Response::GetStatus(r) => Ok(r
+                .clients
+                .into_iter()
+                .map(ClientStatusWrapper::from)
+                .collect()),
+            _ => Err(PyRuntimeError::new_err("Failed to get status")),
+        }
+    }
+
+    /// add planes to the output stream, every plane is subscribed after connecting
+    pub async fn subscribe(
+        &self,
+        subscription: SubscriptionWrapper,
+    ) -> PyResult<SubscriptionWrapper> {
+        let args = Args::Subscribe(subscription.into());
+        let r = self.call("Subscribe", args).await?;
+        match r {
+            Response::Subscribe(r) => Ok(r.into()),
+            _ => Err(PyRuntimeError::new_err("Failed to subscribe")),
+        }
+    }
+
+    /// remove planes from the output stream, `all` removes every plane
+    pub async fn unsubscribe(
+        &self,
+        subscription: SubscriptionWrapper,
+    ) -> PyResult<SubscriptionWrapper> {
+        let args = Args::Unsubscribe(subscription.into());
+        let r = self.call("Unsubscribe", args).await?;
+        match r {
+            Response::Unsubscribe(r) => Ok(r.into()),
+            _ => Err(PyRuntimeError::new_err("Failed to unsubscribe")),
+        }
+    }
+
+    pub async fn output(&mut self) -> PyResult<PlaneMessageWrapper> {
+        let r = self.output_receiver.recv().await;
+        event!(Level::DEBUG, "output: {:?}", r);
+        match r {
+            Some(r) => Ok(r.into()),
+            None => Err(PyRuntimeError::new_err("Output channel dropped")),
+        }
+    }
+
+    pub async fn diagnostic(&mut self) -> PyResult<PlaneDiagnosticWrapper> {
+        let r = self.diagnostic_receiver.recv().await;
+        event!(Level::DEBUG, "diagnostic: {:?}", r);
+        match r {
+            Some(r) => Ok(r.into()),
+            None => Err(PyRuntimeError::new_err("Diagnostic channel dropped")),
+        }
+    }
+
+    pub async fn lost_plane(&mut self) -> PyResult<String> {
+        let r = self.lost_plane_receiver.recv().await;
+        event!(Level::INFO, "lost_plane: {:?}", r);
+        match r {
+            Some(r) => Ok(r),
+            None => Err(PyRuntimeError::new_err("Lost Plane channel dropped")),
+        }
+    }
+
+    pub async fn plane_event(&mut self) -> PyResult<PlaneEventWrapper> {
+        let r = self.plane_event_receiver.recv().await;
+        event!(Level::INFO, "plane_event: {:?}", r);
+        match r {
+            Some(r) => Ok(r.into()),
+            None => Err(PyRuntimeError::new_err("Plane Event channel dropped")),
+        }
+    }
+
+    pub async fn new_plane(&mut self) -> PyResult<String> {
+        let r = self.new_plane_receiver.recv().await;
+        event!(Level::INFO, "new_plane: {:?}", r);
+        match r {
+            Some(r) => Ok(r),
+            None => Err(PyRuntimeError::new_err("New Plane channel dropped")),
+        }
+    }
+
+    pub async fn error(&mut self) -> PyResult<ErrorResponseWrapper> {
+        let r = self.error_receiver.recv().await;
+        event!(Level::ERROR, "error: {:?}", r);
+        match r {
+            Some(r) => Ok(r.into()),
+            None => Err(PyRuntimeError::new_err("Error channel dropped")),
+        }
+    }
+}
+
+impl PyClient {
+    /// send `name` every `period` ms until the client stops
+    fn heartbeat(&mut self, name: &'static str, args: Args, period: u64) -> PyResult<()> {
         let tick_tx = match self.tick_tx.take() {
             Some(t) => t,
-            None => return Err(PyRuntimeError::new_err("Tick already running")),
+            None => return Err(PyRuntimeError::new_err("Heartbeat already running")),
         };
         let tick_task = {
             let t_ct1 = self.cancellation_token.clone();
@@ -310,12 +754,12 @@ impl PyClient {
                                 break;
                             }
                             let call = ServiceCall {
-                                name: "Tick".to_string(),
-                                args: Some(Args::Tick),
+                                name: name.to_string(),
+                                request_id: 0,
+                                args: Some(args.clone()),
                             };
                             tick_tx.send(call).await?;
-                            tokio::time::sleep(Duration::from_millis(tick_period.unwrap_or(1000)))
-                                .await;
+                            tokio::time::sleep(Duration::from_millis(period)).await;
                         }
                         Ok(())
                     })
@@ -339,39 +783,52 @@ impl PyClient {
         Ok(())
     }
 
-    pub async fn output(&mut self) -> PyResult<PlaneMessageWrapper> {
-        let r = self.output_receiver.recv().await;
-        event!(Level::DEBUG, "output: {:?}", r);
-        match r {
-            Some(r) => Ok(r.into()),
-            None => Err(PyRuntimeError::new_err("Output channel dropped")),
-        }
-    }
-
-    pub async fn lost_plane(&mut self) -> PyResult<String> {
-        let r = self.lost_plane_receiver.recv().await;
-        event!(Level::INFO, "lost_plane: {:?}", r);
-        match r {
-            Some(r) => Ok(r),
-            None => Err(PyRuntimeError::new_err("Lost Plane channel dropped")),
+    /// send a request and wait for the response with the same request id
+    async fn call(&self, name: &str, args: Args) -> PyResult<Response> {
+        let request_id = self.next_request_id.fetch_add(1, Ordering::SeqCst);
+        let (tx, rx) = sync::oneshot::channel();
+        self.pending.lock().unwrap().insert(request_id, tx);
+        event!(Level::DEBUG, "{name} start, request {request_id}");
+        let call = ServiceCall {
+            name: name.to_string(),
+            request_id,
+            args: Some(args),
+        };
+        if let Err(e) = self.request_sender.send(call).await {
+            self.pending.lock().unwrap().remove(&request_id);
+            return Err(PyRuntimeError::new_err(e.to_string()));
         }
-    }
-
-    pub async fn new_plane(&mut self) -> PyResult<String> {
-        let r = self.new_plane_receiver.recv().await;
-        event!(Level::INFO, "new_plane: {:?}", r);
+        let r = rx.await;
+        event!(
+            Level::DEBUG,
+            "{name} end, request {request_id} {response:?}",
+            response = r
+        );
         match r {
-            Some(r) => Ok(r),
-            None => Err(PyRuntimeError::new_err("New Plane channel dropped")),
+            Ok(Response::Error(e)) => Err(PyRuntimeError::new_err(format!(
+                "{} failed, {}",
+                e.request, e.message
+            ))),
+            Ok(r) => Ok(r),
+            Err(_) => Err(PyRuntimeError::new_err(format!("Failed to {name}"))),
         }
     }
 
-    pub async fn error(&mut self) -> PyResult<String> {
-        let r = self.error_receiver.recv().await;
-        event!(Level::ERROR, "error: {:?}", r);
+    async fn plane_call(&self, name: &str, args: Args) -> PyResult<Vec<UuidWrapper>> {
+        let r = self.call(name, args).await?;
         match r {
-            Some(r) => Ok(PyRuntimeError::new_err(r).to_string()),
-            None => Err(PyRuntimeError::new_err("Error channel dropped")),
+            Response::RemovePlane(r)
+            | Response::PausePlane(r)
+            | Response::ResumePlane(r)
+            | Response::ResetPlane(r)
+            | Response::SetTimeScale(r) => r
+                .plane_ids
+                .iter()
+                .map(|id| {
+                    UuidWrapper::parse_str(id).map_err(|e| PyRuntimeError::new_err(e.to_string()))
+                })
+                .collect(),
+            _ => Err(PyRuntimeError::new_err(format!("Failed to {name}"))),
         }
     }
 }
@@ -382,19 +839,40 @@ fn fr_py_client(m: &Bound<'_, PyModule>) -> PyResult<()> {
     let _guard = &*GUARD;
     m.add_function(wrap_pyfunction!(register_logger, m)?)?;
     m.add_class::<PyClient>()?;
+    m.add_class::<ClientRoleWrapper>()?;
+    m.add_class::<CompressionWrapper>()?;
+    m.add_class::<HelloAckWrapper>()?;
+    m.add_class::<ClientStatusWrapper>()?;
+    m.add_class::<SubscriptionWrapper>()?;
+    m.add_class::<OutputFieldWrapper>()?;
     m.add_class::<PlaneMessageWrapper>()?;
     m.add_class::<ControlWrapper>()?;
     m.add_class::<StateWrapper>()?;
     m.add_class::<StateExtendWrapper>()?;
     m.add_class::<CoreOutputWrapper>()?;
+    m.add_class::<PlaneDiagnosticWrapper>()?;
+    m.add_class::<PlaneEventWrapper>()?;
+    m.add_class::<ErrorResponseWrapper>()?;
+    m.add_class::<ErrorCodeWrapper>()?;
+    m.add_class::<PlaneInfoWrapper>()?;
+    m.add_class::<PlaneStatusWrapper>()?;
+    m.add_class::<ModelDetailsWrapper>()?;
+    m.add_class::<PlaneConstantsWrapper>()?;
+    m.add_class::<ControlLimitWrapper>()?;
+    m.add_class::<DiagnosticWrapper>()?;
     m.add_class::<UuidWrapper>()?;
     m.add_class::<PluginInfoTupleWrapper>()?;
     m.add_class::<PluginInfoWrapper>()?;
     m.add_class::<PluginStateWrapper>()?;
     m.add_class::<TrimInitWrapper>()?;
+    m.add_class::<InitStateWrapper>()?;
+    m.add_class::<TrimOutputWrapper>()?;
+    m.add_class::<NelderMeadResultWrapper>()?;
     m.add_class::<TrimTargetWrapper>()?;
     m.add_class::<NelderMeadOptionsWrapper>()?;
     m.add_class::<FlightConditionWrapper>()?;
     m.add_class::<PlaneInitCfgWrapper>()?;
+    m.add_class::<PlaneConstantsOverrideWrapper>()?;
+    m.add_class::<ControlLimitOverrideWrapper>()?;
     Ok(())
 }
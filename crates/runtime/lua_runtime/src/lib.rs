@@ -1,4 +1,7 @@
-use fly_ruler_utils::{plane_model::Control, CancellationToken, InputSender, OutputReceiver};
+use fly_ruler_utils::{
+    plane_model::{Control, PlaneCommand},
+    CancellationToken, DiagnosticReceiver, InputSender, OutputReceiver, PlaneCommandSender,
+};
 use mlua::prelude::*;
 use uuid::Uuid;
 
@@ -43,6 +46,41 @@ impl mlua::UserData for OutputReceiverWrapper {
     }
 }
 
+#[derive(Clone)]
+pub struct DiagnosticReceiverWrapper(DiagnosticReceiver);
+
+impl From<DiagnosticReceiver> for DiagnosticReceiverWrapper {
+    fn from(value: DiagnosticReceiver) -> Self {
+        Self(value)
+    }
+}
+
+impl mlua::UserData for DiagnosticReceiverWrapper {
+    fn add_methods<'lua, M: mlua::prelude::LuaUserDataMethods<'lua, Self>>(methods: &mut M) {
+        methods.add_async_method_mut("changed", |_lua, this, ()| async move {
+            this.0.changed().await.map_err(mlua::Error::external)
+        });
+
+        methods.add_method("get", |lua, this, ()| {
+            let (time, value) = this.0.get();
+            let table = lua.create_table()?;
+            table.set("time", time)?;
+            table.set("data", lua.to_value(&value)?)?;
+            Ok(mlua::Value::Table(table))
+        });
+
+        methods.add_method_mut("get_and_update", |lua, this, ()| {
+            let (time, value) = this.0.get_and_update();
+            let table = lua.create_table()?;
+            table.set("time", time)?;
+            table.set("data", lua.to_value(&value)?)?;
+            Ok(mlua::Value::Table(table))
+        });
+
+        methods.add_method("clone", |_lua, this, ()| Ok(this.clone()));
+    }
+}
+
 pub struct InputSenderWrapper(InputSender);
 
 impl From<InputSender> for InputSenderWrapper {
@@ -73,6 +111,55 @@ impl mlua::UserData for InputSenderWrapper {
     }
 }
 
+#[derive(Clone)]
+pub struct PlaneCommandSenderWrapper(PlaneCommandSender);
+
+impl From<PlaneCommandSender> for PlaneCommandSenderWrapper {
+    fn from(value: PlaneCommandSender) -> Self {
+        Self(value)
+    }
+}
+
+impl mlua::UserData for PlaneCommandSenderWrapper {
+    fn add_methods<'lua, M: mlua::prelude::LuaUserDataMethods<'lua, Self>>(methods: &mut M) {
+        methods.add_async_method("remove", |_lua, this, ()| async move {
+            this.0
+                .send(PlaneCommand::Remove)
+                .await
+                .map_err(mlua::Error::external)
+        });
+
+        methods.add_async_method("pause", |_lua, this, ()| async move {
+            this.0
+                .send(PlaneCommand::Pause)
+                .await
+                .map_err(mlua::Error::external)
+        });
+
+        methods.add_async_method("resume", |_lua, this, ()| async move {
+            this.0
+                .send(PlaneCommand::Resume)
+                .await
+                .map_err(mlua::Error::external)
+        });
+
+        methods.add_async_method("reset", |_lua, this, ()| async move {
+            this.0
+                .send(PlaneCommand::Reset)
+                .await
+                .map_err(mlua::Error::external)
+        });
+
+        methods.add_async_method("set_time_scale", |_lua, this, time_scale: f64| async move {
+            let command =
+                PlaneCommand::set_time_scale(time_scale).map_err(mlua::Error::external)?;
+            this.0.send(command).await.map_err(mlua::Error::external)
+        });
+
+        methods.add_method("clone", |_lua, this, ()| Ok(this.clone()));
+    }
+}
+
 #[derive(Clone)]
 pub struct UuidWrapper(Uuid);
 
@@ -1,5 +1,5 @@
 use crate::{ControlWrapper, StateExtendWrapper, StateWrapper, UuidWrapper};
-use fly_ruler_codec::PlaneMessage;
+use fly_ruler_codec::{PlaneEvent, PlaneEventKind, PlaneInfo, PlaneMessage, PlaneStatus};
 use fly_ruler_utils::plane_model::CoreOutput;
 use pyo3::prelude::*;
 use std::hash::{DefaultHasher, Hash, Hasher};
@@ -119,3 +119,79 @@ impl Into<PlaneMessage> for PlaneMessageWrapper {
         }
     }
 }
+
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct PlaneEventWrapper {
+    #[pyo3(get, set)]
+    pub id: UuidWrapper,
+    /// one of `removed`, `paused`, `resumed`, `reset`, `time_scale_changed` and `owner_changed`
+    #[pyo3(get, set)]
+    pub kind: String,
+    #[pyo3(get, set)]
+    pub time_scale: Option<f64>,
+}
+
+impl From<PlaneEvent> for PlaneEventWrapper {
+    fn from(value: PlaneEvent) -> Self {
+        let kind = match value.kind {
+            PlaneEventKind::Removed => "removed",
+            PlaneEventKind::Paused => "paused",
+            PlaneEventKind::Resumed => "resumed",
+            PlaneEventKind::Reset => "reset",
+            PlaneEventKind::TimeScaleChanged => "time_scale_changed",
+            PlaneEventKind::OwnerChanged => "owner_changed",
+        };
+        Self {
+            id: UuidWrapper::parse_str(&value.plane_id).unwrap(),
+            kind: kind.to_string(),
+            time_scale: value.time_scale,
+        }
+    }
+}
+
+#[pyclass]
+#[derive(Clone, Debug)]
+pub enum PlaneStatusWrapper {
+    Running,
+    Paused,
+}
+
+impl From<PlaneStatus> for PlaneStatusWrapper {
+    fn from(value: PlaneStatus) -> Self {
+        match value {
+            PlaneStatus::Running => PlaneStatusWrapper::Running,
+            PlaneStatus::Paused => PlaneStatusWrapper::Paused,
+        }
+    }
+}
+
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct PlaneInfoWrapper {
+    #[pyo3(get, set)]
+    pub id: UuidWrapper,
+    #[pyo3(get, set)]
+    pub model_id: UuidWrapper,
+    #[pyo3(get, set)]
+    pub owner: String,
+    #[pyo3(get, set)]
+    pub status: PlaneStatusWrapper,
+    #[pyo3(get, set)]
+    pub time: f64,
+    #[pyo3(get, set)]
+    pub pilots: Vec<String>,
+}
+
+impl From<PlaneInfo> for PlaneInfoWrapper {
+    fn from(value: PlaneInfo) -> Self {
+        Self {
+            id: UuidWrapper::parse_str(&value.plane_id).unwrap(),
+            model_id: UuidWrapper::parse_str(&value.model_id).unwrap(),
+            owner: value.owner,
+            status: value.status.into(),
+            time: value.time,
+            pilots: value.pilots,
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use crate::UuidWrapper;
+use fly_ruler_codec::PlaneDiagnostic;
+use fly_ruler_utils::plane_model::{Diagnostic, C};
+use pyo3::prelude::*;
+
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct DiagnosticWrapper {
+    #[pyo3(get, set)]
+    pub c_x: f64,
+    #[pyo3(get, set)]
+    pub c_y: f64,
+    #[pyo3(get, set)]
+    pub c_z: f64,
+    #[pyo3(get, set)]
+    pub c_l: f64,
+    #[pyo3(get, set)]
+    pub c_m: f64,
+    #[pyo3(get, set)]
+    pub c_n: f64,
+    #[pyo3(get, set)]
+    pub aero_force: [f64; 3],
+    #[pyo3(get, set)]
+    pub thrust_force: [f64; 3],
+    #[pyo3(get, set)]
+    pub gravity_force: [f64; 3],
+    #[pyo3(get, set)]
+    pub aero_moment: [f64; 3],
+    #[pyo3(get, set)]
+    pub thrust_moment: [f64; 3],
+    #[pyo3(get, set)]
+    pub angular_accel: [f64; 3],
+}
+
+impl From<Diagnostic> for DiagnosticWrapper {
+    fn from(value: Diagnostic) -> Self {
+        Self {
+            c_x: value.c.c_x,
+            c_y: value.c.c_y,
+            c_z: value.c.c_z,
+            c_l: value.c.c_l,
+            c_m: value.c.c_m,
+            c_n: value.c.c_n,
+            aero_force: value.aero_force,
+            thrust_force: value.thrust_force,
+            gravity_force: value.gravity_force,
+            aero_moment: value.aero_moment,
+            thrust_moment: value.thrust_moment,
+            angular_accel: value.angular_accel,
+        }
+    }
+}
+
+impl Into<Diagnostic> for DiagnosticWrapper {
+    fn into(self) -> Diagnostic {
+        Diagnostic {
+            c: C::new(self.c_x, self.c_z, self.c_m, self.c_y, self.c_n, self.c_l),
+            aero_force: self.aero_force,
+            thrust_force: self.thrust_force,
+            gravity_force: self.gravity_force,
+            aero_moment: self.aero_moment,
+            thrust_moment: self.thrust_moment,
+            angular_accel: self.angular_accel,
+        }
+    }
+}
+
+unsafe impl Send for DiagnosticWrapper {}
+
+#[pyclass]
+pub struct PlaneDiagnosticWrapper {
+    #[pyo3(get, set)]
+    pub id: UuidWrapper,
+    #[pyo3(get, set)]
+    pub time: f64,
+    #[pyo3(get, set)]
+    pub diagnostic: Option<DiagnosticWrapper>,
+}
+
+impl From<PlaneDiagnostic> for PlaneDiagnosticWrapper {
+    fn from(value: PlaneDiagnostic) -> Self {
+        Self {
+            id: UuidWrapper::parse_str(&value.id).unwrap(),
+            time: value.time,
+            diagnostic: value.diagnostic.map(DiagnosticWrapper::from),
+        }
+    }
+}
+
+impl Into<PlaneDiagnostic> for PlaneDiagnosticWrapper {
+    fn into(self) -> PlaneDiagnostic {
+        PlaneDiagnostic {
+            id: self.id.0.to_string(),
+            time: self.time,
+            diagnostic: self.diagnostic.map(|diagnostic| diagnostic.into()),
+        }
+    }
+}
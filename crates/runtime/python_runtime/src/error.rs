@@ -0,0 +1,71 @@
+use fly_ruler_codec::{ErrorCode, ErrorResponse};
+use pyo3::prelude::*;
+
+#[pyclass]
+#[derive(Clone, Debug)]
+pub enum ErrorCodeWrapper {
+    Internal,
+    InvalidRequest,
+    InvalidArgs,
+    InvalidId,
+    ModelNotAvailable,
+    PlaneNotFound,
+    TrimFailed,
+    HandshakeRequired,
+    MalformedFrame,
+    PermissionDenied,
+    NotPlaneOwner,
+}
+
+impl From<ErrorCode> for ErrorCodeWrapper {
+    fn from(value: ErrorCode) -> Self {
+        match value {
+            ErrorCode::Internal => ErrorCodeWrapper::Internal,
+            ErrorCode::InvalidRequest => ErrorCodeWrapper::InvalidRequest,
+            ErrorCode::InvalidArgs => ErrorCodeWrapper::InvalidArgs,
+            ErrorCode::InvalidId => ErrorCodeWrapper::InvalidId,
+            ErrorCode::ModelNotAvailable => ErrorCodeWrapper::ModelNotAvailable,
+            ErrorCode::PlaneNotFound => ErrorCodeWrapper::PlaneNotFound,
+            ErrorCode::TrimFailed => ErrorCodeWrapper::TrimFailed,
+            ErrorCode::HandshakeRequired => ErrorCodeWrapper::HandshakeRequired,
+            ErrorCode::MalformedFrame => ErrorCodeWrapper::MalformedFrame,
+            ErrorCode::PermissionDenied => ErrorCodeWrapper::PermissionDenied,
+            ErrorCode::NotPlaneOwner => ErrorCodeWrapper::NotPlaneOwner,
+        }
+    }
+}
+
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct ErrorResponseWrapper {
+    #[pyo3(get, set)]
+    pub code: ErrorCodeWrapper,
+    #[pyo3(get, set)]
+    pub message: String,
+    #[pyo3(get, set)]
+    pub request: String,
+}
+
+impl From<ErrorResponse> for ErrorResponseWrapper {
+    fn from(value: ErrorResponse) -> Self {
+        Self {
+            code: value.code.into(),
+            message: value.message,
+            request: value.request,
+        }
+    }
+}
+
+#[pymethods]
+impl ErrorResponseWrapper {
+    pub fn __str__(&self) -> String {
+        format!(
+            "Error(code={:?}, request='{}', message='{}')",
+            self.code, self.request, self.message
+        )
+    }
+
+    pub fn __repr__(&self) -> String {
+        self.__str__()
+    }
+}
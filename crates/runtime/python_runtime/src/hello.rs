@@ -0,0 +1,147 @@
+use fly_ruler_codec::{ClientRole, ClientStatus, Compression, Feature, HelloAck};
+use pyo3::prelude::*;
+
+#[pyclass]
+#[derive(Clone, Debug)]
+pub enum ClientRoleWrapper {
+    Pilot,
+    Observer,
+    Admin,
+}
+
+impl From<ClientRole> for ClientRoleWrapper {
+    fn from(value: ClientRole) -> Self {
+        match value {
+            ClientRole::Pilot => ClientRoleWrapper::Pilot,
+            ClientRole::Observer => ClientRoleWrapper::Observer,
+            ClientRole::Admin => ClientRoleWrapper::Admin,
+        }
+    }
+}
+
+impl Into<ClientRole> for ClientRoleWrapper {
+    fn into(self) -> ClientRole {
+        match self {
+            ClientRoleWrapper::Pilot => ClientRole::Pilot,
+            ClientRoleWrapper::Observer => ClientRole::Observer,
+            ClientRoleWrapper::Admin => ClientRole::Admin,
+        }
+    }
+}
+
+/// codec of the large frames, frames are sent uncompressed if it is not set
+#[pyclass]
+#[derive(Clone, Debug)]
+pub enum CompressionWrapper {
+    Zstd,
+    Lz4,
+}
+
+impl Into<Compression> for CompressionWrapper {
+    fn into(self) -> Compression {
+        match self {
+            CompressionWrapper::Zstd => Compression::Zstd,
+            CompressionWrapper::Lz4 => Compression::Lz4,
+        }
+    }
+}
+
+/// answer of the server to the handshake
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct HelloAckWrapper {
+    #[pyo3(get, set)]
+    pub protocol_version: u32,
+    #[pyo3(get, set)]
+    pub server_name: String,
+    #[pyo3(get, set)]
+    pub server_version: String,
+    /// negotiated features, each one of `compression`, `batching`, `subscription` and `checksum`
+    #[pyo3(get, set)]
+    pub features: Vec<String>,
+    /// one of `none`, `zstd` and `lz4`
+    #[pyo3(get, set)]
+    pub compression: String,
+    /// bytes, smaller frames are sent uncompressed
+    #[pyo3(get, set)]
+    pub compression_threshold: u32,
+    /// role granted by the server
+    #[pyo3(get, set)]
+    pub role: ClientRoleWrapper,
+    /// passed to `PyClient.new` to reclaim the planes after a reconnection, empty if the server keeps no session
+    #[pyo3(get, set)]
+    pub session: String,
+    #[pyo3(get, set)]
+    pub resumed_planes: Vec<String>,
+    /// world joined by the client, empty for the default one
+    #[pyo3(get, set)]
+    pub room: String,
+}
+
+impl From<HelloAck> for HelloAckWrapper {
+    fn from(value: HelloAck) -> Self {
+        let features = value
+            .features
+            .into_iter()
+            .map(|f| {
+                match f {
+                    Feature::Compression => "compression",
+                    Feature::Batching => "batching",
+                    Feature::Subscription => "subscription",
+                    Feature::Checksum => "checksum",
+                }
+                .to_string()
+            })
+            .collect();
+        let compression = match value.compression {
+            Compression::None => "none",
+            Compression::Zstd => "zstd",
+            Compression::Lz4 => "lz4",
+        }
+        .to_string();
+        Self {
+            protocol_version: value.protocol_version,
+            server_name: value.server_name,
+            server_version: value.server_version,
+            features,
+            compression,
+            compression_threshold: value.compression_threshold,
+            role: value.role.into(),
+            session: value.session,
+            resumed_planes: value.resumed_planes,
+            room: value.room,
+        }
+    }
+}
+
+/// output queue of a client on the server
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct ClientStatusWrapper {
+    #[pyo3(get, set)]
+    pub addr: String,
+    /// messages waiting to be sent
+    #[pyo3(get, set)]
+    pub queued: u64,
+    /// outputs dropped because the queue was full
+    #[pyo3(get, set)]
+    pub dropped: u64,
+    /// outputs replaced by a newer output of the same plane
+    #[pyo3(get, set)]
+    pub conflated: u64,
+    /// broadcast messages missed while the client lagged behind
+    #[pyo3(get, set)]
+    pub lagged: u64,
+}
+
+impl From<ClientStatus> for ClientStatusWrapper {
+    fn from(value: ClientStatus) -> Self {
+        Self {
+            addr: value.addr,
+            queued: value.queued,
+            dropped: value.dropped,
+            conflated: value.conflated,
+            lagged: value.lagged,
+        }
+    }
+}
@@ -1,17 +1,29 @@
 pub(crate) mod control;
 pub(crate) mod core_output;
+pub(crate) mod diagnostic;
+pub(crate) mod error;
+pub(crate) mod hello;
+pub(crate) mod model_details;
 pub(crate) mod plane_init_cfg;
 pub(crate) mod plugin;
 pub(crate) mod state;
 pub(crate) mod state_extend;
+pub(crate) mod subscription;
 pub(crate) mod sync;
+pub(crate) mod trim_output;
 pub(crate) mod uuid;
 
 pub use control::ControlWrapper;
 pub use core_output::*;
+pub use diagnostic::*;
+pub use error::*;
+pub use hello::*;
+pub use model_details::*;
 pub use plane_init_cfg::*;
 pub use plugin::*;
 pub use state::*;
 pub use state_extend::*;
+pub use subscription::*;
 pub use sync::*;
+pub use trim_output::*;
 pub use uuid::*;
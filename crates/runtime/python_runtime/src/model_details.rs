@@ -0,0 +1,171 @@
+use crate::PluginInfoTupleWrapper;
+use fly_ruler_codec::GetModelDetailsResponse;
+use fly_ruler_utils::plane_model::{ControlLimit, PlaneConstants};
+use pyo3::prelude::*;
+
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct PlaneConstantsWrapper {
+    #[pyo3(get, set)]
+    pub m: f64,
+    #[pyo3(get, set)]
+    pub b: f64,
+    #[pyo3(get, set)]
+    pub s: f64,
+    #[pyo3(get, set)]
+    pub c_bar: f64,
+    #[pyo3(get, set)]
+    pub x_cg_r: f64,
+    #[pyo3(get, set)]
+    pub x_cg: f64,
+    #[pyo3(get, set)]
+    pub h_eng: f64,
+    #[pyo3(get, set)]
+    pub j_y: f64,
+    #[pyo3(get, set)]
+    pub j_xz: f64,
+    #[pyo3(get, set)]
+    pub j_z: f64,
+    #[pyo3(get, set)]
+    pub j_x: f64,
+}
+
+impl From<PlaneConstants> for PlaneConstantsWrapper {
+    fn from(value: PlaneConstants) -> Self {
+        Self {
+            m: value.m,
+            b: value.b,
+            s: value.s,
+            c_bar: value.c_bar,
+            x_cg_r: value.x_cg_r,
+            x_cg: value.x_cg,
+            h_eng: value.h_eng,
+            j_y: value.j_y,
+            j_xz: value.j_xz,
+            j_z: value.j_z,
+            j_x: value.j_x,
+        }
+    }
+}
+
+impl Into<PlaneConstants> for PlaneConstantsWrapper {
+    fn into(self) -> PlaneConstants {
+        PlaneConstants {
+            m: self.m,
+            b: self.b,
+            s: self.s,
+            c_bar: self.c_bar,
+            x_cg_r: self.x_cg_r,
+            x_cg: self.x_cg,
+            h_eng: self.h_eng,
+            j_y: self.j_y,
+            j_xz: self.j_xz,
+            j_z: self.j_z,
+            j_x: self.j_x,
+        }
+    }
+}
+
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct ControlLimitWrapper {
+    #[pyo3(get, set)]
+    pub thrust_cmd_limit_top: f64,
+    #[pyo3(get, set)]
+    pub thrust_cmd_limit_bottom: f64,
+    #[pyo3(get, set)]
+    pub thrust_rate_limit: f64,
+    #[pyo3(get, set)]
+    pub ele_cmd_limit_top: f64,
+    #[pyo3(get, set)]
+    pub ele_cmd_limit_bottom: f64,
+    #[pyo3(get, set)]
+    pub ele_rate_limit: f64,
+    #[pyo3(get, set)]
+    pub ail_cmd_limit_top: f64,
+    #[pyo3(get, set)]
+    pub ail_cmd_limit_bottom: f64,
+    #[pyo3(get, set)]
+    pub ail_rate_limit: f64,
+    #[pyo3(get, set)]
+    pub rud_cmd_limit_top: f64,
+    #[pyo3(get, set)]
+    pub rud_cmd_limit_bottom: f64,
+    #[pyo3(get, set)]
+    pub rud_rate_limit: f64,
+    #[pyo3(get, set)]
+    pub alpha_limit_top: f64,
+    #[pyo3(get, set)]
+    pub alpha_limit_bottom: f64,
+    #[pyo3(get, set)]
+    pub beta_limit_top: f64,
+    #[pyo3(get, set)]
+    pub beta_limit_bottom: f64,
+}
+
+impl From<ControlLimit> for ControlLimitWrapper {
+    fn from(value: ControlLimit) -> Self {
+        Self {
+            thrust_cmd_limit_top: value.thrust_cmd_limit_top,
+            thrust_cmd_limit_bottom: value.thrust_cmd_limit_bottom,
+            thrust_rate_limit: value.thrust_rate_limit,
+            ele_cmd_limit_top: value.ele_cmd_limit_top,
+            ele_cmd_limit_bottom: value.ele_cmd_limit_bottom,
+            ele_rate_limit: value.ele_rate_limit,
+            ail_cmd_limit_top: value.ail_cmd_limit_top,
+            ail_cmd_limit_bottom: value.ail_cmd_limit_bottom,
+            ail_rate_limit: value.ail_rate_limit,
+            rud_cmd_limit_top: value.rud_cmd_limit_top,
+            rud_cmd_limit_bottom: value.rud_cmd_limit_bottom,
+            rud_rate_limit: value.rud_rate_limit,
+            alpha_limit_top: value.alpha_limit_top,
+            alpha_limit_bottom: value.alpha_limit_bottom,
+            beta_limit_top: value.beta_limit_top,
+            beta_limit_bottom: value.beta_limit_bottom,
+        }
+    }
+}
+
+impl Into<ControlLimit> for ControlLimitWrapper {
+    fn into(self) -> ControlLimit {
+        ControlLimit {
+            thrust_cmd_limit_top: self.thrust_cmd_limit_top,
+            thrust_cmd_limit_bottom: self.thrust_cmd_limit_bottom,
+            thrust_rate_limit: self.thrust_rate_limit,
+            ele_cmd_limit_top: self.ele_cmd_limit_top,
+            ele_cmd_limit_bottom: self.ele_cmd_limit_bottom,
+            ele_rate_limit: self.ele_rate_limit,
+            ail_cmd_limit_top: self.ail_cmd_limit_top,
+            ail_cmd_limit_bottom: self.ail_cmd_limit_bottom,
+            ail_rate_limit: self.ail_rate_limit,
+            rud_cmd_limit_top: self.rud_cmd_limit_top,
+            rud_cmd_limit_bottom: self.rud_cmd_limit_bottom,
+            rud_rate_limit: self.rud_rate_limit,
+            alpha_limit_top: self.alpha_limit_top,
+            alpha_limit_bottom: self.alpha_limit_bottom,
+            beta_limit_top: self.beta_limit_top,
+            beta_limit_bottom: self.beta_limit_bottom,
+        }
+    }
+}
+
+#[pyclass]
+#[derive(Clone)]
+pub struct ModelDetailsWrapper {
+    #[pyo3(get, set)]
+    pub model_info: Option<PluginInfoTupleWrapper>,
+    #[pyo3(get, set)]
+    pub constants: Option<PlaneConstantsWrapper>,
+    #[pyo3(get, set)]
+    pub control_limit: Option<ControlLimitWrapper>,
+}
+
+impl From<GetModelDetailsResponse> for ModelDetailsWrapper {
+    fn from(value: GetModelDetailsResponse) -> Self {
+        Self {
+            model_info: value.model_info.map(PluginInfoTupleWrapper::from),
+            constants: value.constants.map(PlaneConstantsWrapper::from),
+            control_limit: value.control_limit.map(ControlLimitWrapper::from),
+        }
+    }
+}
@@ -1,11 +1,11 @@
 use fly_ruler_core::algorithm::nelder_mead::NelderMeadOptions;
 use fly_ruler_core::core::PlaneInitCfg;
-use fly_ruler_core::parts::trim::{TrimInit, TrimTarget};
-use fly_ruler_utils::plane_model::FlightCondition;
+use fly_ruler_core::parts::trim::{InitState, TrimInit, TrimTarget};
+use fly_ruler_utils::plane_model::{ControlLimitOverride, FlightCondition, PlaneConstantsOverride};
 use pyo3::exceptions::PyRuntimeError;
 use pyo3::prelude::*;
 
-use crate::ControlWrapper;
+use crate::{ControlWrapper, StateWrapper};
 
 #[pyclass]
 #[derive(Clone, Debug)]
@@ -90,6 +90,41 @@ impl TrimInitWrapper {
     }
 }
 
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct InitStateWrapper {
+    #[pyo3(get, set)]
+    pub state: StateWrapper,
+    #[pyo3(get, set)]
+    pub control: ControlWrapper,
+}
+
+impl From<InitState> for InitStateWrapper {
+    fn from(value: InitState) -> Self {
+        InitStateWrapper {
+            state: value.state.into(),
+            control: value.control.into(),
+        }
+    }
+}
+
+impl Into<InitState> for InitStateWrapper {
+    fn into(self) -> InitState {
+        InitState {
+            state: self.state.into(),
+            control: self.control.into(),
+        }
+    }
+}
+
+#[pymethods]
+impl InitStateWrapper {
+    #[new]
+    pub fn new(state: StateWrapper, control: ControlWrapper) -> Self {
+        InitStateWrapper { state, control }
+    }
+}
+
 #[pyclass]
 #[derive(Clone, Debug)]
 pub struct NelderMeadOptionsWrapper {
@@ -203,6 +238,226 @@ impl FlightConditionWrapper {
     }
 }
 
+#[pyclass]
+#[derive(Clone, Debug, Default)]
+pub struct PlaneConstantsOverrideWrapper {
+    #[pyo3(get, set)]
+    pub m: Option<f64>,
+    #[pyo3(get, set)]
+    pub b: Option<f64>,
+    #[pyo3(get, set)]
+    pub s: Option<f64>,
+    #[pyo3(get, set)]
+    pub c_bar: Option<f64>,
+    #[pyo3(get, set)]
+    pub x_cg_r: Option<f64>,
+    #[pyo3(get, set)]
+    pub x_cg: Option<f64>,
+    #[pyo3(get, set)]
+    pub h_eng: Option<f64>,
+    #[pyo3(get, set)]
+    pub j_y: Option<f64>,
+    #[pyo3(get, set)]
+    pub j_xz: Option<f64>,
+    #[pyo3(get, set)]
+    pub j_z: Option<f64>,
+    #[pyo3(get, set)]
+    pub j_x: Option<f64>,
+}
+
+impl From<PlaneConstantsOverride> for PlaneConstantsOverrideWrapper {
+    fn from(value: PlaneConstantsOverride) -> Self {
+        Self {
+            m: value.m,
+            b: value.b,
+            s: value.s,
+            c_bar: value.c_bar,
+            x_cg_r: value.x_cg_r,
+            x_cg: value.x_cg,
+            h_eng: value.h_eng,
+            j_y: value.j_y,
+            j_xz: value.j_xz,
+            j_z: value.j_z,
+            j_x: value.j_x,
+        }
+    }
+}
+
+impl Into<PlaneConstantsOverride> for PlaneConstantsOverrideWrapper {
+    fn into(self) -> PlaneConstantsOverride {
+        PlaneConstantsOverride {
+            m: self.m,
+            b: self.b,
+            s: self.s,
+            c_bar: self.c_bar,
+            x_cg_r: self.x_cg_r,
+            x_cg: self.x_cg,
+            h_eng: self.h_eng,
+            j_y: self.j_y,
+            j_xz: self.j_xz,
+            j_z: self.j_z,
+            j_x: self.j_x,
+        }
+    }
+}
+
+#[pymethods]
+impl PlaneConstantsOverrideWrapper {
+    #[new]
+    pub fn new(
+        m: Option<f64>,
+        b: Option<f64>,
+        s: Option<f64>,
+        c_bar: Option<f64>,
+        x_cg_r: Option<f64>,
+        x_cg: Option<f64>,
+        h_eng: Option<f64>,
+        j_y: Option<f64>,
+        j_xz: Option<f64>,
+        j_z: Option<f64>,
+        j_x: Option<f64>,
+    ) -> Self {
+        Self {
+            m,
+            b,
+            s,
+            c_bar,
+            x_cg_r,
+            x_cg,
+            h_eng,
+            j_y,
+            j_xz,
+            j_z,
+            j_x,
+        }
+    }
+}
+
+#[pyclass]
+#[derive(Clone, Debug, Default)]
+pub struct ControlLimitOverrideWrapper {
+    #[pyo3(get, set)]
+    pub thrust_cmd_limit_top: Option<f64>,
+    #[pyo3(get, set)]
+    pub thrust_cmd_limit_bottom: Option<f64>,
+    #[pyo3(get, set)]
+    pub thrust_rate_limit: Option<f64>,
+    #[pyo3(get, set)]
+    pub ele_cmd_limit_top: Option<f64>,
+    #[pyo3(get, set)]
+    pub ele_cmd_limit_bottom: Option<f64>,
+    #[pyo3(get, set)]
+    pub ele_rate_limit: Option<f64>,
+    #[pyo3(get, set)]
+    pub ail_cmd_limit_top: Option<f64>,
+    #[pyo3(get, set)]
+    pub ail_cmd_limit_bottom: Option<f64>,
+    #[pyo3(get, set)]
+    pub ail_rate_limit: Option<f64>,
+    #[pyo3(get, set)]
+    pub rud_cmd_limit_top: Option<f64>,
+    #[pyo3(get, set)]
+    pub rud_cmd_limit_bottom: Option<f64>,
+    #[pyo3(get, set)]
+    pub rud_rate_limit: Option<f64>,
+    #[pyo3(get, set)]
+    pub alpha_limit_top: Option<f64>,
+    #[pyo3(get, set)]
+    pub alpha_limit_bottom: Option<f64>,
+    #[pyo3(get, set)]
+    pub beta_limit_top: Option<f64>,
+    #[pyo3(get, set)]
+    pub beta_limit_bottom: Option<f64>,
+}
+
+impl From<ControlLimitOverride> for ControlLimitOverrideWrapper {
+    fn from(value: ControlLimitOverride) -> Self {
+        Self {
+            thrust_cmd_limit_top: value.thrust_cmd_limit_top,
+            thrust_cmd_limit_bottom: value.thrust_cmd_limit_bottom,
+            thrust_rate_limit: value.thrust_rate_limit,
+            ele_cmd_limit_top: value.ele_cmd_limit_top,
+            ele_cmd_limit_bottom: value.ele_cmd_limit_bottom,
+            ele_rate_limit: value.ele_rate_limit,
+            ail_cmd_limit_top: value.ail_cmd_limit_top,
+            ail_cmd_limit_bottom: value.ail_cmd_limit_bottom,
+            ail_rate_limit: value.ail_rate_limit,
+            rud_cmd_limit_top: value.rud_cmd_limit_top,
+            rud_cmd_limit_bottom: value.rud_cmd_limit_bottom,
+            rud_rate_limit: value.rud_rate_limit,
+            alpha_limit_top: value.alpha_limit_top,
+            alpha_limit_bottom: value.alpha_limit_bottom,
+            beta_limit_top: value.beta_limit_top,
+            beta_limit_bottom: value.beta_limit_bottom,
+        }
+    }
+}
+
+impl Into<ControlLimitOverride> for ControlLimitOverrideWrapper {
+    fn into(self) -> ControlLimitOverride {
+        ControlLimitOverride {
+            thrust_cmd_limit_top: self.thrust_cmd_limit_top,
+            thrust_cmd_limit_bottom: self.thrust_cmd_limit_bottom,
+            thrust_rate_limit: self.thrust_rate_limit,
+            ele_cmd_limit_top: self.ele_cmd_limit_top,
+            ele_cmd_limit_bottom: self.ele_cmd_limit_bottom,
+            ele_rate_limit: self.ele_rate_limit,
+            ail_cmd_limit_top: self.ail_cmd_limit_top,
+            ail_cmd_limit_bottom: self.ail_cmd_limit_bottom,
+            ail_rate_limit: self.ail_rate_limit,
+            rud_cmd_limit_top: self.rud_cmd_limit_top,
+            rud_cmd_limit_bottom: self.rud_cmd_limit_bottom,
+            rud_rate_limit: self.rud_rate_limit,
+            alpha_limit_top: self.alpha_limit_top,
+            alpha_limit_bottom: self.alpha_limit_bottom,
+            beta_limit_top: self.beta_limit_top,
+            beta_limit_bottom: self.beta_limit_bottom,
+        }
+    }
+}
+
+#[pymethods]
+impl ControlLimitOverrideWrapper {
+    #[new]
+    pub fn new(
+        thrust_cmd_limit_top: Option<f64>,
+        thrust_cmd_limit_bottom: Option<f64>,
+        thrust_rate_limit: Option<f64>,
+        ele_cmd_limit_top: Option<f64>,
+        ele_cmd_limit_bottom: Option<f64>,
+        ele_rate_limit: Option<f64>,
+        ail_cmd_limit_top: Option<f64>,
+        ail_cmd_limit_bottom: Option<f64>,
+        ail_rate_limit: Option<f64>,
+        rud_cmd_limit_top: Option<f64>,
+        rud_cmd_limit_bottom: Option<f64>,
+        rud_rate_limit: Option<f64>,
+        alpha_limit_top: Option<f64>,
+        alpha_limit_bottom: Option<f64>,
+        beta_limit_top: Option<f64>,
+        beta_limit_bottom: Option<f64>,
+    ) -> Self {
+        Self {
+            thrust_cmd_limit_top,
+            thrust_cmd_limit_bottom,
+            thrust_rate_limit,
+            ele_cmd_limit_top,
+            ele_cmd_limit_bottom,
+            ele_rate_limit,
+            ail_cmd_limit_top,
+            ail_cmd_limit_bottom,
+            ail_rate_limit,
+            rud_cmd_limit_top,
+            rud_cmd_limit_bottom,
+            rud_rate_limit,
+            alpha_limit_top,
+            alpha_limit_bottom,
+            beta_limit_top,
+            beta_limit_bottom,
+        }
+    }
+}
+
 #[pyclass]
 #[derive(Clone, Debug)]
 pub struct PlaneInitCfgWrapper {
@@ -211,6 +466,10 @@ pub struct PlaneInitCfgWrapper {
     pub trim_init: Option<TrimInitWrapper>,
     pub flight_condition: Option<FlightConditionWrapper>,
     pub optim_options: Option<NelderMeadOptionsWrapper>,
+    pub diagnostic: Option<bool>,
+    pub constants: Option<PlaneConstantsOverrideWrapper>,
+    pub ctrl_limit: Option<ControlLimitOverrideWrapper>,
+    pub init_state: Option<InitStateWrapper>,
 }
 
 impl Into<PlaneInitCfg> for PlaneInitCfgWrapper {
@@ -221,6 +480,10 @@ impl Into<PlaneInitCfg> for PlaneInitCfgWrapper {
             trim_init: self.trim_init.map(TrimInitWrapper::into),
             flight_condition: self.flight_condition.map(FlightConditionWrapper::into),
             optim_options: self.optim_options.map(NelderMeadOptionsWrapper::into),
+            diagnostic: self.diagnostic,
+            constants: self.constants.map(PlaneConstantsOverrideWrapper::into),
+            ctrl_limit: self.ctrl_limit.map(ControlLimitOverrideWrapper::into),
+            init_state: self.init_state.map(InitStateWrapper::into),
         }
     }
 }
@@ -233,6 +496,10 @@ impl From<PlaneInitCfg> for PlaneInitCfgWrapper {
             trim_init: cfg.trim_init.map(TrimInitWrapper::from),
             flight_condition: cfg.flight_condition.map(FlightConditionWrapper::from),
             optim_options: cfg.optim_options.map(NelderMeadOptionsWrapper::from),
+            diagnostic: cfg.diagnostic,
+            constants: cfg.constants.map(PlaneConstantsOverrideWrapper::from),
+            ctrl_limit: cfg.ctrl_limit.map(ControlLimitOverrideWrapper::from),
+            init_state: cfg.init_state.map(InitStateWrapper::from),
         }
     }
 }
@@ -246,6 +513,10 @@ impl PlaneInitCfgWrapper {
         trim_init: Option<TrimInitWrapper>,
         flight_condition: Option<FlightConditionWrapper>,
         optim_options: Option<NelderMeadOptionsWrapper>,
+        diagnostic: Option<bool>,
+        constants: Option<PlaneConstantsOverrideWrapper>,
+        ctrl_limit: Option<ControlLimitOverrideWrapper>,
+        init_state: Option<InitStateWrapper>,
     ) -> Self {
         Self {
             deflection,
@@ -253,6 +524,10 @@ impl PlaneInitCfgWrapper {
             trim_init,
             flight_condition,
             optim_options,
+            diagnostic,
+            constants,
+            ctrl_limit,
+            init_state,
         }
     }
 }
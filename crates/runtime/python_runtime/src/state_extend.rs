@@ -17,6 +17,38 @@ pub struct StateExtendWrapper {
     pub qbar: f64,
     #[pyo3(get, set)]
     pub ps: f64,
+    #[pyo3(get, set)]
+    pub u: f64,
+    #[pyo3(get, set)]
+    pub v: f64,
+    #[pyo3(get, set)]
+    pub w: f64,
+    #[pyo3(get, set)]
+    pub v_north: f64,
+    #[pyo3(get, set)]
+    pub v_east: f64,
+    #[pyo3(get, set)]
+    pub v_down: f64,
+    #[pyo3(get, set)]
+    pub gamma: f64,
+    #[pyo3(get, set)]
+    pub track: f64,
+    #[pyo3(get, set)]
+    pub ground_speed: f64,
+    #[pyo3(get, set)]
+    pub climb_rate: f64,
+    #[pyo3(get, set)]
+    pub tas: f64,
+    #[pyo3(get, set)]
+    pub cas: f64,
+    #[pyo3(get, set)]
+    pub eas: f64,
+    #[pyo3(get, set)]
+    pub load_factor: f64,
+    #[pyo3(get, set)]
+    pub specific_energy: f64,
+    #[pyo3(get, set)]
+    pub specific_excess_power: f64,
 }
 
 impl From<StateExtend> for StateExtendWrapper {
@@ -28,6 +60,22 @@ impl From<StateExtend> for StateExtendWrapper {
             mach: value.mach,
             qbar: value.qbar,
             ps: value.ps,
+            u: value.u,
+            v: value.v,
+            w: value.w,
+            v_north: value.v_north,
+            v_east: value.v_east,
+            v_down: value.v_down,
+            gamma: value.gamma,
+            track: value.track,
+            ground_speed: value.ground_speed,
+            climb_rate: value.climb_rate,
+            tas: value.tas,
+            cas: value.cas,
+            eas: value.eas,
+            load_factor: value.load_factor,
+            specific_energy: value.specific_energy,
+            specific_excess_power: value.specific_excess_power,
         }
     }
 }
@@ -41,6 +89,22 @@ impl Into<StateExtend> for StateExtendWrapper {
             mach: self.mach,
             qbar: self.qbar,
             ps: self.ps,
+            u: self.u,
+            v: self.v,
+            w: self.w,
+            v_north: self.v_north,
+            v_east: self.v_east,
+            v_down: self.v_down,
+            gamma: self.gamma,
+            track: self.track,
+            ground_speed: self.ground_speed,
+            climb_rate: self.climb_rate,
+            tas: self.tas,
+            cas: self.cas,
+            eas: self.eas,
+            load_factor: self.load_factor,
+            specific_energy: self.specific_energy,
+            specific_excess_power: self.specific_excess_power,
         }
     }
 }
@@ -53,6 +117,22 @@ impl PartialEq for StateExtendWrapper {
             && (self.mach - other.mach).abs() < f64::EPSILON
             && (self.qbar - other.qbar).abs() < f64::EPSILON
             && (self.ps - other.ps).abs() < f64::EPSILON
+            && (self.u - other.u).abs() < f64::EPSILON
+            && (self.v - other.v).abs() < f64::EPSILON
+            && (self.w - other.w).abs() < f64::EPSILON
+            && (self.v_north - other.v_north).abs() < f64::EPSILON
+            && (self.v_east - other.v_east).abs() < f64::EPSILON
+            && (self.v_down - other.v_down).abs() < f64::EPSILON
+            && (self.gamma - other.gamma).abs() < f64::EPSILON
+            && (self.track - other.track).abs() < f64::EPSILON
+            && (self.ground_speed - other.ground_speed).abs() < f64::EPSILON
+            && (self.climb_rate - other.climb_rate).abs() < f64::EPSILON
+            && (self.tas - other.tas).abs() < f64::EPSILON
+            && (self.cas - other.cas).abs() < f64::EPSILON
+            && (self.eas - other.eas).abs() < f64::EPSILON
+            && (self.load_factor - other.load_factor).abs() < f64::EPSILON
+            && (self.specific_energy - other.specific_energy).abs() < f64::EPSILON
+            && (self.specific_excess_power - other.specific_excess_power).abs() < f64::EPSILON
     }
 }
 
@@ -66,6 +146,22 @@ impl Hash for StateExtendWrapper {
         let mach_bits: u64 = self.mach.to_bits();
         let qbar_bits: u64 = self.qbar.to_bits();
         let ps_bits: u64 = self.ps.to_bits();
+        let u_bits: u64 = self.u.to_bits();
+        let v_bits: u64 = self.v.to_bits();
+        let w_bits: u64 = self.w.to_bits();
+        let v_north_bits: u64 = self.v_north.to_bits();
+        let v_east_bits: u64 = self.v_east.to_bits();
+        let v_down_bits: u64 = self.v_down.to_bits();
+        let gamma_bits: u64 = self.gamma.to_bits();
+        let track_bits: u64 = self.track.to_bits();
+        let ground_speed_bits: u64 = self.ground_speed.to_bits();
+        let climb_rate_bits: u64 = self.climb_rate.to_bits();
+        let tas_bits: u64 = self.tas.to_bits();
+        let cas_bits: u64 = self.cas.to_bits();
+        let eas_bits: u64 = self.eas.to_bits();
+        let load_factor_bits: u64 = self.load_factor.to_bits();
+        let specific_energy_bits: u64 = self.specific_energy.to_bits();
+        let specific_excess_power_bits: u64 = self.specific_excess_power.to_bits();
 
         nx_bits.hash(state);
         ny_bits.hash(state);
@@ -73,13 +169,52 @@ impl Hash for StateExtendWrapper {
         mach_bits.hash(state);
         qbar_bits.hash(state);
         ps_bits.hash(state);
+        u_bits.hash(state);
+        v_bits.hash(state);
+        w_bits.hash(state);
+        v_north_bits.hash(state);
+        v_east_bits.hash(state);
+        v_down_bits.hash(state);
+        gamma_bits.hash(state);
+        track_bits.hash(state);
+        ground_speed_bits.hash(state);
+        climb_rate_bits.hash(state);
+        tas_bits.hash(state);
+        cas_bits.hash(state);
+        eas_bits.hash(state);
+        load_factor_bits.hash(state);
+        specific_energy_bits.hash(state);
+        specific_excess_power_bits.hash(state);
     }
 }
 
 #[pymethods]
 impl StateExtendWrapper {
     #[new]
-    fn new(nx: f64, ny: f64, nz: f64, mach: f64, qbar: f64, ps: f64) -> Self {
+    fn new(
+        nx: f64,
+        ny: f64,
+        nz: f64,
+        mach: f64,
+        qbar: f64,
+        ps: f64,
+        u: f64,
+        v: f64,
+        w: f64,
+        v_north: f64,
+        v_east: f64,
+        v_down: f64,
+        gamma: f64,
+        track: f64,
+        ground_speed: f64,
+        climb_rate: f64,
+        tas: f64,
+        cas: f64,
+        eas: f64,
+        load_factor: f64,
+        specific_energy: f64,
+        specific_excess_power: f64,
+    ) -> Self {
         Self {
             nx,
             ny,
@@ -87,6 +222,22 @@ impl StateExtendWrapper {
             mach,
             qbar,
             ps,
+            u,
+            v,
+            w,
+            v_north,
+            v_east,
+            v_down,
+            gamma,
+            track,
+            ground_speed,
+            climb_rate,
+            tas,
+            cas,
+            eas,
+            load_factor,
+            specific_energy,
+            specific_excess_power,
         }
     }
 
@@ -0,0 +1,98 @@
+use crate::UuidWrapper;
+use fly_ruler_codec::{OutputField, Subscription};
+use pyo3::prelude::*;
+
+#[pyclass]
+#[derive(Clone, Debug)]
+pub enum OutputFieldWrapper {
+    State,
+    Control,
+    StateExtend,
+}
+
+impl From<OutputField> for OutputFieldWrapper {
+    fn from(value: OutputField) -> Self {
+        match value {
+            OutputField::State => OutputFieldWrapper::State,
+            OutputField::Control => OutputFieldWrapper::Control,
+            OutputField::StateExtend => OutputFieldWrapper::StateExtend,
+        }
+    }
+}
+
+impl Into<OutputField> for OutputFieldWrapper {
+    fn into(self) -> OutputField {
+        match self {
+            OutputFieldWrapper::State => OutputField::State,
+            OutputFieldWrapper::Control => OutputField::Control,
+            OutputFieldWrapper::StateExtend => OutputField::StateExtend,
+        }
+    }
+}
+
+/// planes whose outputs are forwarded to the client
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct SubscriptionWrapper {
+    #[pyo3(get, set)]
+    pub all: bool,
+    #[pyo3(get, set)]
+    pub plane_ids: Vec<UuidWrapper>,
+    /// address of the clients whose planes are subscribed
+    #[pyo3(get, set)]
+    pub owners: Vec<String>,
+    /// forward one of every `decimation` outputs of a plane, 0 keeps the current rate
+    #[pyo3(get, set)]
+    pub decimation: u32,
+    /// parts of the output to send, empty keeps the current mask
+    #[pyo3(get, set)]
+    pub fields: Vec<OutputFieldWrapper>,
+}
+
+impl From<Subscription> for SubscriptionWrapper {
+    fn from(value: Subscription) -> Self {
+        Self {
+            all: value.all,
+            plane_ids: value
+                .plane_ids
+                .iter()
+                .map(|id| UuidWrapper::parse_str(id).unwrap())
+                .collect(),
+            owners: value.owners,
+            decimation: value.decimation,
+            fields: value.fields.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl Into<Subscription> for SubscriptionWrapper {
+    fn into(self) -> Subscription {
+        Subscription {
+            all: self.all,
+            plane_ids: self.plane_ids.iter().map(|id| id.0.to_string()).collect(),
+            owners: self.owners,
+            decimation: self.decimation,
+            fields: self.fields.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[pymethods]
+impl SubscriptionWrapper {
+    #[new]
+    pub fn new(
+        all: bool,
+        plane_ids: Vec<UuidWrapper>,
+        owners: Vec<String>,
+        decimation: u32,
+        fields: Vec<OutputFieldWrapper>,
+    ) -> Self {
+        Self {
+            all,
+            plane_ids,
+            owners,
+            decimation,
+            fields,
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use crate::{ControlWrapper, InitStateWrapper, StateExtendWrapper, StateWrapper};
+use fly_ruler_core::{
+    algorithm::nelder_mead::NelderMeadResult,
+    parts::trim::{InitState, TrimOutput},
+};
+use fly_ruler_utils::Vector;
+use pyo3::prelude::*;
+
+#[pyclass]
+#[derive(Clone, Debug)]
+pub struct NelderMeadResultWrapper {
+    #[pyo3(get, set)]
+    pub x: Vec<f64>,
+    #[pyo3(get, set)]
+    pub fval: f64,
+    #[pyo3(get, set)]
+    pub iter: usize,
+    #[pyo3(get, set)]
+    pub fun_evals: usize,
+}
+
+impl From<NelderMeadResult> for NelderMeadResultWrapper {
+    fn from(value: NelderMeadResult) -> Self {
+        Self {
+            x: value.x.data,
+            fval: value.fval,
+            iter: value.iter,
+            fun_evals: value.fun_evals,
+        }
+    }
+}
+
+impl Into<NelderMeadResult> for NelderMeadResultWrapper {
+    fn into(self) -> NelderMeadResult {
+        NelderMeadResult {
+            x: Vector::from(self.x),
+            fval: self.fval,
+            iter: self.iter,
+            fun_evals: self.fun_evals,
+        }
+    }
+}
+
+#[pymethods]
+impl NelderMeadResultWrapper {
+    #[new]
+    pub fn new(x: Vec<f64>, fval: f64, iter: usize, fun_evals: usize) -> Self {
+        Self {
+            x,
+            fval,
+            iter,
+            fun_evals,
+        }
+    }
+}
+
+#[pyclass]
+#[derive(Clone)]
+pub struct TrimOutputWrapper {
+    #[pyo3(get, set)]
+    pub state: StateWrapper,
+    #[pyo3(get, set)]
+    pub control: ControlWrapper,
+    #[pyo3(get, set)]
+    pub state_extend: StateExtendWrapper,
+    #[pyo3(get, set)]
+    pub nelder_mead_result: NelderMeadResultWrapper,
+}
+
+impl From<TrimOutput> for TrimOutputWrapper {
+    fn from(value: TrimOutput) -> Self {
+        Self {
+            state: value.state.into(),
+            control: value.control.into(),
+            state_extend: value.state_extend.into(),
+            nelder_mead_result: value.nelder_mead_result.into(),
+        }
+    }
+}
+
+impl Into<TrimOutput> for TrimOutputWrapper {
+    fn into(self) -> TrimOutput {
+        TrimOutput {
+            state: self.state.into(),
+            control: self.control.into(),
+            state_extend: self.state_extend.into(),
+            nelder_mead_result: self.nelder_mead_result.into(),
+        }
+    }
+}
+
+#[pymethods]
+impl TrimOutputWrapper {
+    #[new]
+    pub fn new(
+        state: StateWrapper,
+        control: ControlWrapper,
+        state_extend: StateExtendWrapper,
+        nelder_mead_result: NelderMeadResultWrapper,
+    ) -> Self {
+        Self {
+            state,
+            control,
+            state_extend,
+            nelder_mead_result,
+        }
+    }
+
+    /// reuse this trim result as the initial state of a new plane
+    pub fn init_state(&self) -> InitStateWrapper {
+        let trim_output: TrimOutput = self.clone().into();
+        InitState::from(&trim_output).into()
+    }
+}
@@ -48,8 +48,10 @@ async def main_task(n):
     plane_ids = []
     
     for i in range(n):
-        plane_id = await client.push_plane((f16_id, plane_init_cfg))
+        plane_id, trim_output = await client.push_plane((f16_id, plane_init_cfg))
         print(f"New F-16 {i}: {plane_id}")
+        if trim_output is not None:
+            control = trim_output.control
         plane_ids.append(plane_id)
         await client.send_control((plane_id, control))
     
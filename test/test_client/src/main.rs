@@ -1,6 +1,6 @@
 use fly_ruler_codec::{
-    Args, GetModelInfosResponse, PushPlaneRequest, RequestFrame, Response, ResponseFrame,
-    ServiceCall,
+    Args, ClientRole, GetModelInfosResponse, Hello, PushPlaneRequest, RequestFrame, Response,
+    ResponseFrame, ServiceCall, PROTOCOL_VERSION,
 };
 use fly_ruler_utils::plane_model::Control;
 use futures_util::{SinkExt, StreamExt};
@@ -27,8 +27,28 @@ fn main() {
         let stream = TcpStream::connect("127.0.0.1:2350").await.unwrap();
 
         let (reader, writer) = stream.into_split();
-        let mut reader = FramedRead::new(reader, ResponseFrame);
-        let mut writer = FramedWrite::new(writer, RequestFrame);
+        let mut reader = FramedRead::new(reader, ResponseFrame::default());
+        let mut writer = FramedWrite::new(writer, RequestFrame::default());
+
+        let hello = ServiceCall {
+            name: "Hello".to_string(),
+            request_id: 1,
+            args: Some(Args::Hello(Hello {
+                protocol_version: PROTOCOL_VERSION,
+                client_name: "test_client".to_string(),
+                client_version: env!("CARGO_PKG_VERSION").to_string(),
+                role: ClientRole::Pilot,
+                features: Vec::new(),
+                compressions: Vec::new(),
+                token: String::new(),
+                session: String::new(),
+                room: String::new(),
+                create_room: false,
+            })),
+        };
+        writer.send(hello).await.unwrap();
+        let hello_ack = reader.next().await.unwrap().unwrap();
+        dbg!(hello_ack);
 
         let (tx, mut rx) = channel::<ServiceCall>(100);
 
@@ -46,6 +66,7 @@ fn main() {
         let main_task = tokio::spawn(async move {
             let call = ServiceCall {
                 name: "GetModelInfos".to_string(),
+                request_id: 2,
                 args: Some(Args::GetModelInfos),
             };
             tx.send(call).await.unwrap();
@@ -63,6 +84,7 @@ fn main() {
 
                                 let call = ServiceCall {
                                     name: "PushPlane".to_string(),
+                                    request_id: 3,
                                     args: Some(Args::PushPlane(PushPlaneRequest {
                                         model_id: f16_key,
                                         plane_init_cfg: None,
@@ -77,13 +99,8 @@ fn main() {
                                 if let Some(Response::PushPlane(response)) = plane_id.response {
                                     let plane_id = response.plane_id;
                                     let h1 = tokio::spawn(async move {
-                                        loop {
-                                            let response = reader.next().await;
-                                            if let None = response {
-                                                tokio::task::yield_now().await;
-                                            } else {
-                                                println!("{:?}", response.unwrap())
-                                            };
+                                        while let Some(response) = reader.next().await {
+                                            println!("{:?}", response)
                                         }
                                     });
 
@@ -92,6 +109,7 @@ fn main() {
                                         loop {
                                             let call = ServiceCall {
                                                 name: "SendControl".to_string(),
+                                                request_id: 0,
                                                 args: Some(Args::SendControl(
                                                     fly_ruler_codec::SendControlRequest {
                                                         plane_id: plane_id.clone(),
@@ -105,6 +123,7 @@ fn main() {
                                             if count == 100 {
                                                 let call = ServiceCall {
                                                     name: "Tick".to_string(),
+                                                    request_id: 0,
                                                     args: Some(Args::Tick),
                                                 };
                                                 tx1.send(call).await.unwrap();
@@ -126,9 +145,7 @@ fn main() {
                             dbg!(e);
                         }
                     },
-                    None => {
-                        tokio::task::yield_now().await;
-                    }
+                    None => break,
                 }
             }
         });